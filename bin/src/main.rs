@@ -3,7 +3,10 @@ mod error;
 
 use error::Result;
 use quest_core::Object;
+use quest_core::sandbox::Sandbox;
+use quest_core::types::Env;
 use clap::Clap;
+use std::time::Duration;
 
 /// Run the Quest programming language
 #[derive(Clap, Debug)]
@@ -17,6 +20,34 @@ struct Opts {
 	#[clap(short, long, conflicts_with="file")]
 	eval: Option<String>,
 
+	/// Add a directory to search for imports in. Can be given multiple times.
+	#[clap(short="I", long="include", number_of_values=1)]
+	include: Vec<std::path::PathBuf>,
+
+	/// Compile code to bytecode and run it in a virtual machine, rather than walking the syntax tree.
+	#[clap(long)]
+	vm: bool,
+
+	/// Run without access to the filesystem, the network, other processes, environment variables, or `quit`.
+	#[clap(long)]
+	sandbox: bool,
+
+	/// The most steps a sandboxed program can take.
+	#[clap(long, requires="sandbox")]
+	max_steps: Option<u64>,
+
+	/// How deeply stackframes can be nested in a sandboxed program. Defaults to 200.
+	#[clap(long, requires="sandbox")]
+	max_depth: Option<usize>,
+
+	/// How many bits integers can have in a sandboxed program. Defaults to 262144.
+	#[clap(long, requires="sandbox")]
+	max_integer_bits: Option<u64>,
+
+	/// The most seconds a sandboxed program can run for.
+	#[clap(long, requires="sandbox", parse(try_from_str=parse_timeout))]
+	timeout: Option<Duration>,
+
 	#[clap(last=true)]
 	args: Vec<String>
 
@@ -27,7 +58,54 @@ struct Opts {
 }
 
 
-fn run_options(Opts { file, eval, args, .. }: Opts) -> Result<Object> {
+fn parse_timeout(timeout: &str) -> std::result::Result<Duration, String> {
+	let secs = timeout.parse::<f64>().map_err(|err| err.to_string())?;
+
+	Duration::try_from_secs_f64(secs)
+		.map_err(|_| format!("{} isn't a valid number of seconds", timeout))
+}
+
+fn sandbox(opts: &Opts) -> Option<Sandbox> {
+	if !opts.sandbox {
+		return None;
+	}
+
+	let mut sandbox = Sandbox::new();
+
+	if let Some(max_steps) = opts.max_steps {
+		sandbox = sandbox.max_steps(max_steps);
+	}
+
+	if let Some(max_depth) = opts.max_depth {
+		sandbox = sandbox.max_depth(max_depth);
+	}
+
+	if let Some(max_integer_bits) = opts.max_integer_bits {
+		sandbox = sandbox.max_integer_bits(max_integer_bits);
+	}
+
+	if let Some(timeout) = opts.timeout {
+		sandbox = sandbox.timeout(timeout);
+	}
+
+	Some(sandbox)
+}
+
+fn run_options(opts: Opts) -> Result<Object> {
+	let _sandbox = sandbox(&opts).map(Sandbox::enter);
+	let Opts { file, eval, include, vm, args, .. } = opts;
+
+	if vm {
+		qvm::init();
+	}
+
+	for dir in include {
+		quest_parser::import::add_search_path(dir)?;
+	}
+
+	Env::set_args(args.clone());
+	Env::set_script(file.clone().filter(|file| file.to_str() != Some("-")));
+
 	let mut args: Vec<Object> = args.into_iter().map(Object::from).collect();
 
 	if let Some(file) = file.as_ref() {
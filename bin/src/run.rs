@@ -10,6 +10,11 @@ use std::convert::TryFrom;
 
 pub trait Runner {
 	fn run(self) -> Result<Object>;
+
+	/// The file that's being run, if there is one.
+	fn file(&self) -> Option<&Path> {
+		None
+	}
 }
 
 pub fn run_file<P: AsRef<Path>>(path: P, args: Args) -> Result<Object> { 
@@ -32,8 +37,23 @@ pub fn run<R: Runner>(runner: R, args: Args) -> quest_core::Result<Object> {
 	let main = Object::new(quest_core::types::Scope);
 	main.set_attr_lit("name", Object::from("main"))?;
 
+	if let Some(file) = runner.file() {
+		main.set_attr_lit("__file__", Object::from(file.display().to_string()))?;
+	}
+
 	Binding::new_stackframe(Some(main), args, move |_| {
-		runner.run().map_err(|err| quest_core::Error::Boxed(Box::new(err)))
+		let result = runner.run().map_err(|err| match err {
+			// unwrap these so their stack trace is kept
+			crate::error::Error::Quest(err) => err,
+			other => quest_core::Error::Boxed(Box::new(other))
+		});
+
+		// `at_exit` blocks are run from the main stackframe, even if the program raised an error.
+		let at_exit = quest_core::types::Env::run_at_exit();
+		let result = result?;
+		at_exit?;
+
+		Ok(result)
 	})
 }
 
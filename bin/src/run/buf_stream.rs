@@ -3,10 +3,16 @@ use std::io::BufRead;
 
 impl<B: BufRead> crate::run::Runner for BufStream<B> {
 	fn run(self) -> crate::Result<quest_core::Object> {
-		use quest_parser::{Stream, Expression, expression::Executable};
+		use quest_parser::{Stream, Expression};
 
-		Expression::parse_stream(self.tokens())?
-			.execute()
-			.map_err(Into::into)
+		let expression = Expression::parse_stream(self.tokens())?;
+
+		quest_parser::run(&expression).map_err(Into::into)
+	}
+
+	fn file(&self) -> Option<&std::path::Path> {
+		use quest_parser::Contexted;
+
+		self.context().file.as_deref()
 	}
 }
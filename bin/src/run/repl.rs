@@ -1,46 +1,100 @@
-use quest_parser::{Stream, Context, Contexted};
+use quest_parser::{Stream, Expression};
+use quest_parser::stream::BufStream;
+use quest_core::{Object, Literal, types::Text};
+use rustyline::{Editor, error::ReadlineError};
 use crate::run::Runner;
-use std::io::{self, Seek, SeekFrom};
+use std::io::Cursor;
 
-#[derive(Debug, Clone)]
+const PROMPT: &str = "quest> ";
+const CONTINUATION_PROMPT: &str = "   ..> ";
+
+/// An interactive Quest session.
+///
+/// Every input is executed in the same stackframe, so variables defined on one line are visible
+/// on the next. Input that ends early (eg an unclosed `{` or an unterminated quote) causes the
+/// next line to be read in as a continuation.
 pub struct Repl {
-	context: Context
+	editor: Editor<()>,
+	buf: String
 }
 
 impl Runner for Repl {
-	fn run(self) -> crate::Result<quest_core::Object> {
-		unimplemented!()
+	fn run(mut self) -> crate::Result<Object> {
+		let mut last = Object::default();
+
+		while let Some(expr) = self.next_expression()? {
+			match quest_parser::run(&expr) {
+				Ok(result) => {
+					display_result(&result);
+					last = result;
+				},
+				Err(err) => eprintln!("{}", err.traceback())
+			}
+		}
+
+		Ok(last)
 	}
 }
 
 impl Repl {
 	pub fn new() -> Self {
-		Repl { context: Context::new(Some("<repl>".into())) }
+		Repl { editor: Editor::new(), buf: String::new() }
 	}
-}
-impl Iterator for Repl {
-	type Item = quest_parser::Result<char>;
-	fn next(&mut self) -> Option<Self::Item> {
-		unimplemented!()
-	}
-}
 
-impl Seek for Repl {
-	fn seek(&mut self, _pos: SeekFrom) -> io::Result<u64> {
-		unimplemented!()
+	/// Reads lines in until a complete expression is formed, returning `None` once the user's done.
+	fn next_expression(&mut self) -> crate::Result<Option<Expression>> {
+		self.buf.clear();
+
+		loop {
+			let prompt = if self.buf.is_empty() { PROMPT } else { CONTINUATION_PROMPT };
+
+			match self.editor.readline(prompt) {
+				Ok(line) => {
+					self.buf.push_str(line.trim_end_matches(|c| c == '\n' || c == '\r'));
+					self.buf.push('\n');
+				},
+				// `^C` discards whatever's currently been typed.
+				Err(ReadlineError::Interrupted) => {
+					self.buf.clear();
+					continue;
+				},
+				Err(ReadlineError::Eof) => return Ok(None),
+				Err(ReadlineError::Io(err)) => return Err(err.into()),
+				Err(err) => return Err(std::io::Error::new(std::io::ErrorKind::Other, err).into())
+			}
+
+			if self.buf.trim().is_empty() {
+				self.buf.clear();
+				continue;
+			}
+
+			match self.parse() {
+				Ok(expr) => {
+					self.editor.add_history_entry(self.buf.trim_end());
+					return Ok(Some(expr));
+				},
+				Err(err) if err.is_incomplete_input() => continue,
+				Err(err) => {
+					self.editor.add_history_entry(self.buf.trim_end());
+					self.buf.clear();
+					eprintln!("{}", err);
+				}
+			}
+		}
 	}
-}
 
-impl Contexted for Repl {
-	fn context(&self) -> &Context {
-		&self.context
+	fn parse(&self) -> quest_parser::Result<Expression> {
+		let stream = BufStream::new(Cursor::new(self.buf.as_str()), Some("<repl>".into()));
+
+		Expression::parse_stream(stream.tokens())
 	}
 }
 
-impl Stream for Repl {
-	fn starts_with(&mut self, _s: &str) -> quest_parser::Result<bool> {
-		unimplemented!()
-		// self.read_next_line_if_applicable()?;
-		// Ok(self.as_ref().starts_with(s))
+fn display_result(result: &Object) {
+	match result.call_attr_lit(&Literal::INSPECT, &[])
+		.and_then(|inspected| inspected.call_downcast::<Text>().map(|text| text.to_string()))
+	{
+		Ok(inspected) => println!("{}", inspected),
+		Err(err) => eprintln!("<unable to inspect result: {}>", err)
 	}
 }
@@ -1,6 +1,6 @@
 use std::fs::read_dir;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output};
 
 // Examples to skip. Right now, just `guessing_game` which waits for user input.
 //
@@ -8,8 +8,17 @@ use std::process::Command;
 // this, but for a single item it's not worth it.
 const SKIP: &[&str] = &["guessing-game.qs", "knight/guess.kn"];
 
+// The flags to run the examples with, one for each engine.
+const ENGINES: &[&[&str]] = &[&[], &["--vm"]];
+
 fn should_skip(p: &Path) -> bool {
-	SKIP.iter().any(|to_skip| p.ends_with(*to_skip))
+	// directories hold programs made of several files, which aren't run directly.
+	p.is_dir() || SKIP.iter().any(|to_skip| p.ends_with(*to_skip))
+}
+
+// `quest-bin` exits successfully even when there's an uncaught error, so check what it printed too.
+fn succeeded(out: &Output) -> bool {
+	out.status.success() && !String::from_utf8_lossy(&out.stderr).contains("uncaught error")
 }
 
 #[test]
@@ -27,25 +36,30 @@ fn run_examples() {
 			continue;
 		}
 
-		let mut cmd = Command::new(exe);
-		// Todo: Should run with timeout
-		let out = cmd
-			.arg("-f")
-			.arg(&example_path)
-			.output()
-			.unwrap_or_else(|e| {
-				panic!(
-					"Failed to run: `{} -f {}`: {:?}",
-					exe,
-					example_path.display(),
-					e,
-				)
-			});
-		if out.status.success() {
-			eprintln!("\tPASS");
-		} else {
-			eprintln!("\tFAIL\nOutput: {:#?}", out);
-			failed = true
+		// each example is run with the syntax tree walker and then with the virtual machine.
+		for engine in ENGINES {
+			let mut cmd = Command::new(exe);
+			// Todo: Should run with timeout
+			let out = cmd
+				.args(*engine)
+				.arg("-f")
+				.arg(&example_path)
+				.output()
+				.unwrap_or_else(|e| {
+					panic!(
+						"Failed to run: `{} {:?} -f {}`: {:?}",
+						exe,
+						engine,
+						example_path.display(),
+						e,
+					)
+				});
+			if succeeded(&out) {
+				eprintln!("\tPASS {:?}", engine);
+			} else {
+				eprintln!("\tFAIL {:?}\nOutput: {:#?}", engine, out);
+				failed = true
+			}
 		}
 	}
 	assert!(
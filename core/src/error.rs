@@ -1,16 +1,19 @@
 //! Errors that can occur within Quest.
 use crate::{Object, Binding};
+use crate::types::Frame;
 use std::fmt::{self, Display, Formatter};
 
 mod key_error;
 mod type_error;
 mod value_error;
 mod argument_error;
+mod sandbox_error;
 
 pub use type_error::TypeError;
 pub use key_error::KeyError;
 pub use value_error::ValueError;
 pub use argument_error::ArgumentError;
+pub use sandbox_error::SandboxError;
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -36,9 +39,26 @@ pub enum Error {
 	/// Some quest assertion failed.
 	AssertionFailed(Option<String>),
 
+	/// The current sandbox stopped something from happening.
+	SandboxError(SandboxError),
+
 	/// Boxed error
 	Boxed(Box<dyn std::error::Error + Send + Sync + 'static>),
 
+	/// An object that was thrown from within Quest via `Kernel::throw`.
+	Thrown(Object),
+
+	/// An error that unwound out of a stackframe, along with the stack at the time.
+	///
+	/// Errors are wrapped in this the first time they leave a stackframe, so the trace always
+	/// starts at the innermost frame.
+	Traced {
+		/// The error that occurred.
+		error: Box<Error>,
+		/// The stack when the error occurred, with the most recent frame first.
+		trace: Vec<Frame>
+	},
+
 	/// Returning a value.
 	///
 	/// While this isn't technically an "error" in the strict sense of an error, it's much easier
@@ -65,14 +85,99 @@ impl Display for Error {
 			Self::IoError(err) => Display::fmt(&err, f),
 			Self::ValueError(err) => Display::fmt(&err, f),
 			Self::ArgumentError(err) => Display::fmt(&err, f),
+			Self::SandboxError(err) => Display::fmt(&err, f),
 			Self::AssertionFailed(Some(err)) => write!(f, "assertion failed: {}", err),
 			Self::AssertionFailed(None) => write!(f, "assertion failed"),
 			Self::Boxed(err) => Display::fmt(&err, f),
+			Self::Thrown(obj) => match obj.call_downcast::<crate::types::Text>() {
+				Ok(text) => Display::fmt(&*text, f),
+				Err(_) => write!(f, "{:?}", obj)
+			},
+			Self::Traced { error, .. } => Display::fmt(&error, f),
 			Self::Return { to, obj } => write!(f, "uncaught return to {:?}: {:?}", to, obj)
 		}
 	}
 }
 
+impl Error {
+	/// Converts `self` into an object that can be caught from within Quest.
+	///
+	/// Thrown objects are returned as-is, and every other error is converted to an
+	/// [`Exception`](crate::types::Exception). Since returning isn't really an error,
+	/// [`Error::Return`]s are given back unchanged.
+	pub fn into_exception(self) -> std::result::Result<Object, Self> {
+		if let Self::Thrown(obj) = self.untraced() {
+			return Ok(obj.clone());
+		}
+
+		match self {
+			Self::Return { .. } => Err(self),
+			other => Ok(crate::types::Exception::from_error(&other))
+		}
+	}
+
+	/// Records the current stack in `self`, unless it's already been recorded.
+	///
+	/// [`Error::Return`]s aren't really errors, and so are never traced.
+	pub fn traced(self) -> Self {
+		match self {
+			Self::Return { .. } | Self::Traced { .. } => self,
+			other => Self::Traced { error: Box::new(other), trace: Frame::capture() }
+		}
+	}
+
+	/// Gets the error without any trace that's been attached to it.
+	pub fn untraced(&self) -> &Self {
+		match self {
+			Self::Traced { error, .. } => error,
+			other => other
+		}
+	}
+
+	/// Gets the stack at the time the error occurred, with the most recent frame first.
+	///
+	/// This is empty if the error never left a stackframe.
+	pub fn trace(&self) -> &[Frame] {
+		match self {
+			Self::Traced { trace, .. } => trace,
+			_ => &[]
+		}
+	}
+
+	/// Gets a [`Display`]able traceback, formatted in the same style as parse errors.
+	pub fn traceback(&self) -> Traceback<'_> {
+		Traceback(self)
+	}
+}
+
+/// The message and stack of an [`Error`]; created via [`Error::traceback`].
+///
+/// The first frame with a known location is shown alongside the error message, and the rest are
+/// listed underneath it. Frames without a location are omitted.
+#[derive(Debug, Clone, Copy)]
+pub struct Traceback<'a>(&'a Error);
+
+impl Display for Traceback<'_> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut locations = self.0.trace().iter().filter_map(Frame::location);
+
+		let innermost =
+			match locations.next() {
+				Some(location) => location,
+				None => return Display::fmt(&self.0, f)
+			};
+
+		writeln!(f, "{}: {}", innermost, self.0)?;
+		innermost.fmt_snippet(f)?;
+
+		for location in locations {
+			write!(f, "\ncalled from {}\n", location)?;
+			location.fmt_snippet(f)?;
+		}
+
+		Ok(())
+	}
+}
 
 impl From<std::io::Error> for Error {
 	#[inline]
@@ -82,10 +187,10 @@ impl From<std::io::Error> for Error {
 }
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-		if let Self::Boxed(err) = self {
-			Some(err.as_ref())
-		} else {
-			None
+		match self {
+			Self::Boxed(err) => Some(err.as_ref()),
+			Self::Traced { error, .. } => error.source(),
+			_ => None
 		}
 	}
 }
@@ -0,0 +1,47 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// Something was stopped by the current [`Sandbox`](crate::sandbox::Sandbox).
+#[derive(Debug, Clone)]
+pub enum SandboxError {
+	/// A function needed a capability that the sandbox doesn't allow.
+	Denied {
+		/// The function that was called.
+		func: &'static str,
+		/// The name of the capability it needed.
+		capability: &'static str
+	},
+
+	/// More steps were taken than the sandbox allows.
+	StepLimit(u64),
+
+	/// Stackframes were nested deeper than the sandbox allows.
+	DepthLimit(usize),
+
+	/// An integer would've had more bits than the sandbox allows.
+	SizeLimit(u64),
+
+	/// The sandbox's time limit ran out.
+	Timeout(Duration)
+}
+
+impl From<SandboxError> for super::Error {
+	#[inline]
+	fn from(err: SandboxError) -> Self {
+		Self::SandboxError(err)
+	}
+}
+
+impl Display for SandboxError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "sandbox error: ")?;
+		match self {
+			SandboxError::Denied { func, capability } =>
+				write!(f, "`{}` isn't allowed (it needs the {} capability)", func, capability),
+			SandboxError::StepLimit(limit) => write!(f, "step limit of {} exceeded", limit),
+			SandboxError::DepthLimit(limit) => write!(f, "recursion depth limit of {} exceeded", limit),
+			SandboxError::SizeLimit(limit) => write!(f, "integer size limit of {} bits exceeded", limit),
+			SandboxError::Timeout(limit) => write!(f, "time limit of {:?} exceeded", limit),
+		}
+	}
+}
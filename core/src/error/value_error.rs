@@ -4,7 +4,17 @@ use std::fmt::{self, Display, Formatter};
 #[derive(Debug, Clone)]
 pub enum ValueError {
 	/// When a more specific error isn't available
-	Messaged(String)
+	Messaged(String),
+
+	/// Text couldn't be parsed as JSON.
+	BadJson {
+		/// What was wrong with it.
+		message: String,
+		/// The line the problem is on, starting from one.
+		line: usize,
+		/// The column within the line, starting from one.
+		column: usize
+	}
 }
 
 impl From<ValueError> for super::Error {
@@ -19,6 +29,8 @@ impl Display for ValueError {
 		write!(f, "value error: ")?;
 		match self {
 			ValueError::Messaged(msg) => Display::fmt(&msg, f),
+			ValueError::BadJson { message, line, column } =>
+				write!(f, "invalid JSON at line {}, column {}: {}", line, column, message),
 		}
 	}
 }
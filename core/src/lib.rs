@@ -19,6 +19,7 @@ mod literal;
 pub mod utils;
 pub mod error;
 pub mod types;
+pub mod sandbox;
 
 use shared_cow::SharedCow;
 pub use literal::Literal;
@@ -44,8 +45,10 @@ pub fn init() {
 	INITIALIZE.call_once(||
 		initialize!(
 			Pristine, Basic, Boolean, BoundFunction, Comparable, Function, Kernel,
-			List, Null, Number, Regex, RustFn, RustClosure, Scope, Text, Iterable, Tcp,
-			BoundRustFn, Io, types::io::File // todo: remove it?
+			List, Map, Exception, Frame, SourceLocation, Null, Number, Rational, Regex, RustFn, RustClosure, Scope, Text, Bytes, Iterable,
+			Thread, Channel, Mutex, TcpListener, TcpStream, UdpSocket,
+			Http, types::http::Response, Process, types::process::Child, types::process::Output, Env, OptParse, Time, Duration, Instant, Json, Fs, types::fs::Stat, Path,
+			BoundRustFn, Io, types::io::File, types::io::Dir // todo: remove it?
 		)
 	)
 }
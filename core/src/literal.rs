@@ -116,6 +116,9 @@ literals! {
 
 	__KEYS__ "__keys__" __ARGS__ "__args__" __STACK__ "__stack__"
 
+	// stackframes
+	SOURCE_LOCATION "source_location"
+
 	// conversions
 	AT_BOOL "@bool" AT_TEXT "@text" AT_NUM "@num" AT_LIST "@list"
 
@@ -220,6 +220,15 @@ impl Internal {
 		self.attrs.get_lit(attr)
 	}
 
+	#[inline]
+	fn get_own_lit<L>(&self, attr: &L) -> Option<Value>
+	where
+		Literal: Borrow<L>,
+		L: ?Sized + Hash + Eq
+	{
+		self.attrs.get_own_lit(attr)
+	}
+
 	#[inline]
 	fn set_lit(&self, attr: impl Into<Literal>, value: impl Into<Value>) -> crate::Result<()> {
 		self.attrs.set_lit(attr, value);
@@ -269,6 +278,11 @@ impl Internal {
 	fn keys(&self, include_parents: bool) -> crate::Result<Vec<Object>> {
 		self.attrs.keys(include_parents)
 	}
+
+	#[inline]
+	fn parents(&self) -> crate::Result<Vec<Object>> {
+		self.attrs.parents()
+	}
 }
 
 /// Methods to interact with the Object's attributes.
@@ -315,6 +329,15 @@ impl Object {
 		}.into())
 	}
 
+	/// Fetches the attribute `attr` if it's defined directly on `self`, ignoring parents.
+	pub fn get_own_attr_lit<L>(&self, attr: &L) -> Option<Self>
+	where
+		Literal: Borrow<L>,
+		L: ?Sized + Hash + Eq
+	{
+		self.0.get_own_lit(attr).map(Self::from)
+	}
+
 	/// Sets the attribute `attr` to `value`.
 	pub fn set_value_lit(&self, attr: impl Into<Literal>, value: impl Into<Value>)
 		-> crate::Result<()>
@@ -441,6 +464,30 @@ impl Object {
 		self.0.prepend_parent(val)
 	}
 
+	/// Gets the list of this object's direct parents.
+	#[inline]
+	pub fn parents(&self) -> crate::Result<Vec<Self>> {
+		self.0.parents()
+	}
+
+	/// Checks to see if `ancestor` is either `self` or somewhere within `self`'s parents.
+	pub fn is_descendant_of(&self, ancestor: &Self) -> crate::Result<bool> {
+		let mut seen = std::collections::HashSet::new();
+		let mut queue = vec![self.clone()];
+
+		while let Some(obj) = queue.pop() {
+			if obj.is_identical(ancestor) {
+				return Ok(true);
+			}
+
+			if seen.insert(obj.id()) {
+				queue.extend(obj.parents()?);
+			}
+		}
+
+		Ok(false)
+	}
+
 	/// Gets the list of keys corresponding to this object.
 	#[inline]
 	pub(crate) fn mapping_keys(&self, include_parents: bool) -> crate::Result<Vec<Self>> {
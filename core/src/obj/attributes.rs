@@ -3,12 +3,15 @@ use crate::types::Text;
 use std::fmt::{self, Debug, Formatter};
 use std::borrow::Borrow;
 use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 mod parents;
 mod attrmap;
 mod value;
+mod cache;
 
 use attrmap::AttrMap;
+use cache::{Cache, Dependencies, Version};
 pub use value::Value;
 pub use parents::Parents;
 
@@ -22,7 +25,10 @@ struct Inner {
 #[derive(Default)]
 pub struct Attributes {
 	data: SharedCow<Inner>,
-	id: usize
+	id: usize,
+	version: Version,
+	cache: Cache,
+	searches: AtomicUsize
 }
 
 impl Debug for Attributes {
@@ -52,10 +58,15 @@ impl Attributes {
 	}
 
 	fn from_data(data: SharedCow<Inner>) -> Self {
-		use std::sync::atomic::{AtomicUsize, Ordering};
 		static ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
-		Attributes { data, id: ID_COUNTER.fetch_add(1, Ordering::Relaxed) }
+		Attributes {
+			data,
+			id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+			version: Version::default(),
+			cache: Cache::default(),
+			searches: AtomicUsize::new(0)
+		}
 	}
 
 	/// Gets the id associated with these attributes.
@@ -64,14 +75,56 @@ impl Attributes {
 		self.id
 	}
 
+	/// Whether lookups through our parents should be cached, which is called each time they're
+	/// searched.
+	///
+	/// Only objects whose parents are searched often (such as `Number` or a class) benefit from
+	/// caching; most objects, such as numbers and stackframes, are too short-lived to.
+	fn caches_lookups(&self) -> bool {
+		/// How many times the parents need to be searched before we start caching.
+		const MIN_SEARCHES: usize = 64;
+
+		let searches = self.searches.load(Ordering::Relaxed);
+
+		if searches < MIN_SEARCHES {
+			// this isn't atomic, but it doesn't matter if we occasionally miss a search.
+			self.searches.store(searches + 1, Ordering::Relaxed);
+			false
+		} else {
+			true
+		}
+	}
+
+	/// Notes that the attributes or parents have changed, invalidating cached lookups that went
+	/// through `self`.
+	///
+	/// If the parents changed, `self`'s own cache is also cleared, as its entries don't depend on
+	/// the parents list itself.
+	fn changed(&self, parents_changed: bool) {
+		self.version.bump();
+
+		if parents_changed {
+			self.cache.clear();
+		}
+	}
+
 	/// Add a parent to the list of parents.
 	pub fn add_parent(&self, parent: Object) -> Result<()> {
-		self.data.write().parents.add_parent(parent)
+		self.data.write().parents.add_parent(parent)?;
+		self.changed(true);
+		Ok(())
 	}
 
 	/// Add a parent to the list of parents.
 	pub fn prepend_parent(&self, parent: Object) -> Result<()> {
-		self.data.write().parents.prepend_parent(parent)
+		self.data.write().parents.prepend_parent(parent)?;
+		self.changed(true);
+		Ok(())
+	}
+
+	/// Get the list of parents.
+	pub fn parents(&self) -> Result<Vec<Object>> {
+		self.data.read().parents.keys()
 	}
 
 	/// Get a list of keys for this class, optionally including all keys defined on parents as well.
@@ -102,8 +155,7 @@ impl Attributes {
 		if key == Literal::__ID__.borrow() || key == Literal::__PARENTS__.borrow() {
 			Ok(true)
 		} else {
-			let inner = self.data.read();
-			Ok(inner.map.has_lit(key) || inner.parents.has_lit(key)?)
+			Ok(self.lookup_lit(key, None)?.is_some())
 		}
 	}
 
@@ -117,14 +169,88 @@ impl Attributes {
 			return Ok(Some(Object::from(self.id()).into()))
 		}
 
-		let inner = self.data.read();
 		if key == Literal::__PARENTS__.borrow() {
-			Ok(Some(inner.parents.to_object().into()))
-		} else if let Some(lit) = inner.map.get_lit(key).cloned() {
-			Ok(Some(lit))
+			let inner = self.data.read();
+			let was_builtin = !inner.parents.is_object();
+			let parents = inner.parents.to_object();
+
+			// once the parents are visible to quest code, they can be changed without us knowing.
+			if was_builtin {
+				self.changed(true);
+			}
+
+			Ok(Some(parents.into()))
 		} else {
-			inner.parents.get_lit(key)
+			Ok(self.lookup_lit(key, None)?.map(|(_, value)| value))
+		}
+	}
+
+	/// Finds `key` in `self` or its parents, returning the key it was stored under and its value.
+	///
+	/// If `deps` is given, every parent that was searched is recorded in it. Lookups that have to go
+	/// through the parents are cached once they've been searched enough times.
+	fn lookup_lit<L>(&self, key: &L, mut deps: Option<&mut Dependencies>) -> Result<Option<(Literal, Value)>>
+	where
+		Literal: Borrow<L>,
+		L: ?Sized + Hash + Eq
+	{
+		let inner = self.data.read();
+
+		if let Some((&lit, value)) = inner.map.get_lit_key_value(key) {
+			return Ok(Some((lit, value.clone())));
+		}
+
+		if !self.caches_lookups() || self.cache.is_disabled() {
+			return inner.parents.lookup_lit(key, deps);
+		}
+
+		if let Some(found) = self.cache.get(key, deps.as_deref_mut()) {
+			return Ok(found);
+		}
+
+		let mut parent_deps = Dependencies::default();
+
+		let found =
+			if deps.is_some() {
+				// we're already recording what's searched, so we may as well cache it too.
+				inner.parents.lookup_lit(key, Some(&mut parent_deps))?
+			} else {
+				// most lookups that aren't cached can't be, so first search without recording anything.
+				let found = inner.parents.lookup_lit(key, None)?;
+
+				if found.is_none() && interned(key).is_none() {
+					return Ok(None);
+				}
+
+				inner.parents.lookup_lit(key, Some(&mut parent_deps))?
+			};
+
+		match found {
+			Some((lit, ref value)) => {
+				// remember the literal, so misses for it can be cached elsewhere.
+				intern(lit);
+				self.cache.insert(lit, Some(value.clone()), &parent_deps);
+			},
+			// we can only remember misses for keys we already have a `Literal` for.
+			None => if let Some(lit) = interned(key) {
+				self.cache.insert(lit, None, &parent_deps);
+			}
 		}
+
+		if let Some(deps) = deps {
+			deps.extend(parent_deps);
+		}
+
+		Ok(found)
+	}
+
+	/// Gets the associated value to `key` from `self` directly, ignoring its parents.
+	pub fn get_own_lit<L>(&self, key: &L) -> Option<Value>
+	where
+		Literal: Borrow<L>,
+		L: ?Sized + Hash + Eq
+	{
+		self.data.read().map.get_lit(key).cloned()
 	}
 
 	/// Sets the associated `key` to `value` from `self` directly or its parents.
@@ -133,11 +259,15 @@ impl Attributes {
 		let key = key.into();
 		let value = value.into();
 
-		if key == Literal::__PARENTS__ {
+		let is_parents = key == Literal::__PARENTS__;
+
+		if is_parents {
 			inner.parents = Parents::from(Object::from(value));
 		} else {
 			inner.map.set_lit(key, value);
 		}
+
+		self.changed(is_parents);
 	}
 
 	/// Deletes the associated value to `key` from `self` directly or its parents.
@@ -147,12 +277,17 @@ impl Attributes {
 		L: Hash + Eq
 	{
 		let mut inner = self.data.write();
+		let is_parents = key == Literal::__PARENTS__.borrow();
 
-		if key == Literal::__PARENTS__.borrow() {
-			Some(std::mem::take(&mut inner.parents).into())
-		} else {
-			inner.map.del_lit(key)
-		}
+		let value =
+			if is_parents {
+				Some(std::mem::take(&mut inner.parents).into())
+			} else {
+				inner.map.del_lit(key)
+			};
+
+		self.changed(is_parents);
+		value
 	}
 
 	/// Checks to see if `self` directly or its parents includes `key`.
@@ -187,7 +322,9 @@ impl Attributes {
 			return Ok(());
 		}
 
-		self.data.write().map.set_obj(key, value)
+		self.data.write().map.set_obj(key, value)?;
+		self.changed(false);
+		Ok(())
 	}
 
 	/// Deletes the associated value to `key` from `self` directly or its parents.
@@ -196,29 +333,41 @@ impl Attributes {
 			return Ok(res);
 		}
 
-		self.data.write().map.del_obj(key)
+		let value = self.data.write().map.del_obj(key)?;
+		self.changed(false);
+		Ok(value)
 	}
 }
 
-fn str_to_static(key: &str) -> &'static str {
-	use std::collections::HashSet;
-	use std::cell::RefCell;
-
-	thread_local! {
-		// a list of strings that have been converted so far; this is to improve efficiency.
-		static STATIC_STRS: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
-	}
+thread_local! {
+	// a list of strings that have been converted so far; this is to improve efficiency.
+	static STATIC_STRS: std::cell::RefCell<std::collections::HashSet<Literal>> = Default::default();
+}
 
+fn str_to_static(key: &str) -> &'static str {
 	STATIC_STRS.with(|set| {
 		if let Some(static_key) = set.borrow().get(key) {
-			return *static_key;
+			return static_key.into_inner();
 		};
 
 		// leak the string to turn it static.
-		set.borrow_mut().insert(Box::leak(key.to_string().into_boxed_str()));
-		match set.borrow().get(key) {
-			Some(key) => *key,
-			None => unreachable!("we just inserted this?")
-		}
+		let static_key: &'static str = Box::leak(key.to_string().into_boxed_str());
+		set.borrow_mut().insert(Literal::from(static_key));
+		static_key
 	})
 }
+
+/// Records `lit` so that [`str_to_static`] and [`interned`] can use it.
+fn intern(lit: Literal) {
+	STATIC_STRS.with(|set| set.borrow_mut().insert(lit));
+}
+
+/// Gets the `Literal` for `key` if it's been seen by [`str_to_static`] or [`intern`] before,
+/// without leaking anything new.
+fn interned<L>(key: &L) -> Option<Literal>
+where
+	Literal: Borrow<L>,
+	L: ?Sized + Hash + Eq
+{
+	STATIC_STRS.with(|set| set.borrow().get(key).copied())
+}
@@ -31,24 +31,24 @@ impl AttrMap {
 			.chain(self.objects.iter().map(|(k, _)| k.clone()))
 	}
 
-	/// Checks to see if this map has `key`.
+	/// Gets the value associated with `key`.
 	#[inline]
-	pub fn has_lit<L: ?Sized>(&self, key: &L) -> bool
+	pub fn get_lit<L: ?Sized>(&self, key: &L) -> Option<&Value>
 	where
 		Literal: Borrow<L>,
 		L: Hash + Eq
 	{
-		self.literals.contains_key(key)
+		self.literals.get(key)
 	}
 
-	/// Gets the value associated with `key`.
+	/// Gets the key `key` is stored under, and its associated value.
 	#[inline]
-	pub fn get_lit<L: ?Sized>(&self, key: &L) -> Option<&Value>
+	pub fn get_lit_key_value<L: ?Sized>(&self, key: &L) -> Option<(&Literal, &Value)>
 	where
 		Literal: Borrow<L>,
 		L: Hash + Eq
 	{
-		self.literals.get(key)
+		self.literals.get_key_value(key)
 	}
 
 	/// Sets `key` to `value`.
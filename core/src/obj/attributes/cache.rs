@@ -0,0 +1,272 @@
+//! Caching attribute lookups that go through an object's parents.
+//!
+//! Finding a method like `Comparable::<` on a number means walking the number's parents (and
+//! their parents) until one of them has it, which is expensive to do on every call. Instead,
+//! objects whose parents are searched often remember what those searches found, along with the
+//! [`Version`] of every object that was searched along the way. If any of those objects has since
+//! been changed (for example, someone assigned `Number.square = ...`), the cached result is
+//! ignored and the lookup is redone.
+//!
+//! Lookups that found nothing are cached too, as variable lookups within stackframes search the
+//! `Scope` type (and all of its parents) before the stackframes that actually define them.
+
+use crate::{Object, Literal};
+use super::Value;
+use parking_lot::RwLock;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// A counter that's bumped whenever an object's attributes or parents change.
+#[derive(Debug, Default)]
+pub struct Version(AtomicUsize);
+
+impl Version {
+	/// Marks the object as having been changed.
+	///
+	/// This must be called _after_ the change has been made, so that lookups which saw the old
+	/// attributes are never stamped with the new version.
+	#[inline]
+	pub fn bump(&self) {
+		self.0.fetch_add(1, Ordering::Release);
+	}
+
+	#[inline]
+	fn get(&self) -> usize {
+		self.0.load(Ordering::Acquire)
+	}
+}
+
+/// The version an object was at when a lookup searched it.
+#[derive(Debug, Clone)]
+pub struct Stamp {
+	object: Object,
+	at: usize
+}
+
+impl Stamp {
+	/// Records `object`'s current version.
+	///
+	/// This must be taken _before_ the object is searched, so a concurrent change can only make
+	/// the stamp look out of date, and never the other way around.
+	#[inline]
+	pub fn new(object: &Object) -> Self {
+		Self { object: object.clone(), at: object.0.attrs.version.get() }
+	}
+
+	/// Checks to see if the object hasn't been changed since this stamp was taken.
+	#[inline]
+	fn is_current(&self) -> bool {
+		self.object.0.attrs.version.get() == self.at
+	}
+}
+
+/// Every object a lookup searched through, and therefore every object whose changes could
+/// change its result.
+#[derive(Debug)]
+pub struct Dependencies {
+	stamps: Vec<Stamp>,
+	cacheable: bool
+}
+
+impl Default for Dependencies {
+	fn default() -> Self {
+		Self { stamps: Vec::new(), cacheable: true }
+	}
+}
+
+impl Dependencies {
+	/// Records that `stamp`'s object was searched.
+	#[inline]
+	pub fn push(&mut self, stamp: Stamp) {
+		self.stamps.push(stamp);
+	}
+
+	/// Adds all the objects `other` searched.
+	pub fn extend(&mut self, other: Dependencies) {
+		self.stamps.extend(other.stamps);
+		self.cacheable &= other.cacheable;
+	}
+
+	/// Marks the lookup as going through something that can change without bumping a [`Version`],
+	/// such as a `__parents__` list that's been exposed to Quest code.
+	#[inline]
+	pub fn uncacheable(&mut self) {
+		self.cacheable = false;
+	}
+}
+
+#[derive(Debug)]
+struct Entry {
+	value: Option<Value>,
+	stamps: Vec<Stamp>
+}
+
+/// The results of previous lookups through an object's parents.
+#[derive(Debug, Default)]
+pub struct Cache {
+	// this is only allocated once something is cached, as most objects never cache anything.
+	entries: RwLock<Option<HashMap<Literal, Entry>>>,
+	disabled: AtomicBool
+}
+
+impl Cache {
+	/// Gets the cached result for `key`, if there is one and it's still valid.
+	///
+	/// If `deps` is given, the objects the original lookup went through are added to it.
+	#[allow(clippy::option_option)]
+	pub fn get<L>(&self, key: &L, deps: Option<&mut Dependencies>) -> Option<Option<(Literal, Value)>>
+	where
+		Literal: Borrow<L>,
+		L: ?Sized + Hash + Eq
+	{
+		let cache = self.entries.read();
+		let (&lit, entry) = cache.as_ref()?.get_key_value(key)?;
+
+		if !entry.stamps.iter().all(Stamp::is_current) {
+			return None;
+		}
+
+		if let Some(deps) = deps {
+			deps.stamps.extend(entry.stamps.iter().cloned());
+		}
+
+		Some(entry.value.clone().map(|value| (lit, value)))
+	}
+
+	/// Remembers that looking up `key` found `value` (or nothing, if it's `None`), as long as
+	/// nothing in `deps` changes.
+	///
+	/// If the lookup can't be cached, caching is disabled until the cache is next cleared, as
+	/// other lookups through the same parents most likely can't be cached either.
+	pub fn insert(&self, key: Literal, value: Option<Value>, deps: &Dependencies) {
+		if deps.cacheable {
+			self.entries.write()
+				.get_or_insert_with(Default::default)
+				.insert(key, Entry { value, stamps: deps.stamps.clone() });
+		} else {
+			self.disabled.store(true, Ordering::Relaxed);
+		}
+	}
+
+	/// Checks to see if lookups through the parents can't be cached.
+	#[inline]
+	pub fn is_disabled(&self) -> bool {
+		self.disabled.load(Ordering::Relaxed)
+	}
+
+	/// Forgets every cached lookup, and enables caching again.
+	pub fn clear(&self) {
+		*self.entries.write() = None;
+		self.disabled.store(false, Ordering::Relaxed);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Object, Literal};
+	use crate::types::{Number, Text, List};
+
+	/// Looks up `attr` on a new object enough times for its parents to start caching.
+	fn lookup(new: impl Fn() -> Object, attr: &str) -> Option<Object> {
+		(0..100).map(|_| new().get_attr_lit(attr).ok()).last().unwrap()
+	}
+
+	fn number(obj: Option<Object>) -> i64 {
+		obj.expect("attribute doesn't exist").downcast::<Number>().unwrap().truncate()
+	}
+
+	fn square_with(factor: i64) -> Object {
+		crate::types::RustClosure::new(move |args| {
+			let this = args.try_arg(0)?.call_downcast::<Number>()?.clone();
+			Ok((this.clone() * this * Number::from(factor)).into())
+		}).into()
+	}
+
+	fn call_square(num: i64) -> i64 {
+		let square = lookup(|| Object::from(num), "square");
+		number(square.map(|square| square.call_attr_lit(&Literal::CALL, &[&Object::from(num)]).unwrap()))
+	}
+
+	#[test]
+	fn monkey_patching_invalidates() {
+		use crate::types::{Basic, Comparable, ObjectType};
+		crate::init();
+
+		// `Number`'s parents are `Basic` and then `Comparable`.
+		Comparable::mapping().set_attr_lit("square", square_with(1)).unwrap();
+		assert_eq!(call_square(3), 9);
+
+		Number::mapping().set_attr_lit("square", square_with(2)).unwrap();
+		assert_eq!(call_square(3), 18);
+
+		Number::mapping().del_attr_lit("square").unwrap();
+		assert_eq!(call_square(3), 9);
+
+		Basic::mapping().set_attr_lit("square", square_with(3)).unwrap();
+		assert_eq!(call_square(3), 27);
+
+		Basic::mapping().del_attr_lit("square").unwrap();
+		Comparable::mapping().del_attr_lit("square").unwrap();
+		assert!(lookup(|| Object::from(3), "square").is_none());
+	}
+
+	#[test]
+	fn grandparent_changes_invalidate() {
+		let grandparent = Object::new_with_parent((), ());
+		let parent = Object::new_with_parent((), vec![grandparent.clone()]);
+		let child = || Object::new_with_parent((), vec![parent.clone()]);
+
+		grandparent.set_attr_lit("x", Object::from(1)).unwrap();
+		assert_eq!(number(lookup(child, "x")), 1);
+
+		grandparent.set_attr_lit("x", Object::from(2)).unwrap();
+		assert_eq!(number(lookup(child, "x")), 2);
+
+		// shadowing it in the parent also counts as a change.
+		parent.set_attr_lit("x", Object::from(3)).unwrap();
+		assert_eq!(number(lookup(child, "x")), 3);
+
+		grandparent.del_attr_lit("x").unwrap();
+		parent.del_attr_lit("x").unwrap();
+		assert!(lookup(child, "x").is_none());
+	}
+
+	#[test]
+	fn misses_are_invalidated() {
+		let grandparent = Object::new_with_parent((), ());
+		let parent = Object::new_with_parent((), vec![grandparent.clone()]);
+		let child = || Object::new_with_parent((), vec![parent.clone()]);
+
+		// misses are only cached for names that have been used as attributes before.
+		Object::default().set_attr(Object::from("missing"), Object::default()).unwrap();
+		assert!(lookup(child, "missing").is_none());
+
+		grandparent.set_attr_lit("missing", Object::from(1)).unwrap();
+		assert_eq!(number(lookup(child, "missing")), 1);
+	}
+
+	#[test]
+	fn parent_changes_invalidate() {
+		crate::init();
+
+		let first = Object::new_with_parent((), ());
+		let second = Object::new_with_parent((), ());
+		let parent = Object::new_with_parent((), vec![first.clone()]);
+		let child = || Object::new_with_parent((), vec![parent.clone()]);
+		let text = |obj: Option<Object>| obj.unwrap().downcast::<Text>().unwrap().to_string();
+
+		first.set_attr_lit("x", Object::from("first")).unwrap();
+		second.set_attr_lit("x", Object::from("second")).unwrap();
+		assert_eq!(text(lookup(child, "x")), "first");
+
+		parent.prepend_parent(second).unwrap();
+		assert_eq!(text(lookup(child, "x")), "second");
+
+		// lists of parents that quest code can access are changed without us knowing.
+		let parents = parent.get_attr_lit(&Literal::__PARENTS__).unwrap();
+		parents.downcast_mut::<List>().unwrap().shift();
+		assert_eq!(text(lookup(child, "x")), "first");
+	}
+}
@@ -1,6 +1,7 @@
 use crate::{Object, Result, Literal};
 use crate::types::List;
 use super::Value;
+use super::cache::{Dependencies, Stamp};
 use std::iter::FromIterator;
 use parking_lot::RwLock;
 use std::hash::Hash;
@@ -128,6 +129,11 @@ impl Parents {
 		Ok(())
 	}
 
+	/// Checks to see if `self` is a list of parents that quest code can access directly.
+	pub(super) fn is_object(&self) -> bool {
+		matches!(*self.0.read(), Inner::Object(_))
+	}
+
 	/// Converts `self` to an object.
 	pub fn to_object(&self) -> Object {
 		let mut inner = self.0.write();
@@ -160,32 +166,28 @@ impl Parents {
 		self.with_iter(|iter| Ok(iter.cloned().collect()))
 	}
 
-	/// Checks to see if any of the parents responds to `Key`.
-	pub fn has_lit<L: ?Sized>(&self, key: &L) -> Result<bool>
+	/// Finds the first parent that responds to `key`, returning the key it's stored under and its
+	/// value.
+	///
+	/// If `deps` is given, every object that was searched is recorded in it.
+	pub(super) fn lookup_lit<L>(&self, key: &L, mut deps: Option<&mut Dependencies>)
+		-> Result<Option<(Literal, Value)>>
 	where
 		Literal: Borrow<L>,
-		L: Hash + Eq
+		L: ?Sized + Hash + Eq
 	{
+		if let (Inner::Object(_), Some(deps)) = (&*self.0.read(), deps.as_deref_mut()) {
+			deps.uncacheable();
+		}
+
 		self.with_iter(|iter| {
 			for parent in iter {
-				if parent.has_attr_lit(key)? {
-					return Ok(true)
+				if let Some(deps) = deps.as_deref_mut() {
+					deps.push(Stamp::new(parent));
 				}
-			}
-			Ok(false)
-		})
-	}
 
-	/// Gets a value associated with `key` from the parents.
-	pub fn get_lit<L: ?Sized>(&self, key: &L) -> Result<Option<Value>>
-	where
-		Literal: Borrow<L>,
-		L: Hash + Eq
-	{
-		self.with_iter(|iter| {
-			for parent in iter {
-				if let Some(value) = parent.get_value_lit(key)? {
-					return Ok(Some(value))
+				if let Some(found) = parent.0.attrs.lookup_lit(key, deps.as_deref_mut())? {
+					return Ok(Some(found))
 				}
 			}
 			Ok(None)
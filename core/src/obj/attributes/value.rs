@@ -14,6 +14,9 @@ impl Value {
 	/// Calls this value, returning the result.
 	pub fn call<'o>(&self, owner: &'o Object, args: Args<'_, 'o>) -> Result<Object> {
 		use std::borrow::Cow;
+
+		crate::sandbox::step()?;
+
 		match self {
 			Value::RustFn(rustfn) => rustfn.call_with_owner(owner, args),
 			Value::Object(object) => {
@@ -0,0 +1,339 @@
+//! Restricting what Quest code is able to do.
+//!
+//! A [`Sandbox`] is entered on a single thread, and lasts until the [`SandboxGuard`] it returns is
+//! dropped. While it's active:
+//!
+//! - Functions that need a [`Capability`] the sandbox doesn't allow raise a
+//!   [`SandboxError::Denied`] instead of running.
+//! - Every call made counts as a step, and going over the step limit raises a
+//!   [`SandboxError::StepLimit`].
+//! - Every stackframe counts towards the depth, and going over the depth limit raises a
+//!   [`SandboxError::DepthLimit`].
+//! - Once the time limit has passed, the next step raises a [`SandboxError::Timeout`].
+//! - Integer arithmetic that'd produce a result larger than the size limit raises a
+//!   [`SandboxError::SizeLimit`] before doing any work, since a single operation on enormous
+//!   integers can take longer than any time limit.
+//!
+//! Once a limit is hit, every step after it fails too, so catching the error doesn't let code
+//! keep running.
+
+use crate::error::SandboxError;
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+bitflags::bitflags! {
+	/// The things that code within a sandbox can be allowed to do.
+	#[derive(Default)]
+	pub struct Capability : u8 {
+		/// Opening files and importing code.
+		const FILESYSTEM = 1 << 0;
+		/// Running other programs and spawning threads.
+		const PROCESS    = 1 << 1;
+		/// Opening sockets and sending HTTP requests.
+		const NETWORK    = 1 << 2;
+		/// Exiting the program via `quit`, `abort` and `Env::exit`.
+		const EXIT       = 1 << 3;
+		/// Reading and changing environment variables and the working directory.
+		const ENV        = 1 << 4;
+	}
+}
+
+impl Capability {
+	fn name(self) -> &'static str {
+		match self {
+			Capability::FILESYSTEM => "filesystem",
+			Capability::PROCESS => "process",
+			Capability::NETWORK => "network",
+			Capability::EXIT => "exit",
+			Capability::ENV => "environment",
+			_ => "unknown"
+		}
+	}
+}
+
+/// The restrictions placed on code, along with the limits it has to run within.
+///
+/// By default, no capabilities are allowed, stackframes can be nested at most
+/// [`DEFAULT_MAX_DEPTH`](Sandbox::DEFAULT_MAX_DEPTH) deep, integers can be at most
+/// [`DEFAULT_MAX_INTEGER_BITS`](Sandbox::DEFAULT_MAX_INTEGER_BITS) bits, and there are no other
+/// limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandbox {
+	allowed: Capability,
+	max_steps: Option<u64>,
+	max_depth: Option<usize>,
+	max_integer_bits: Option<u64>,
+	timeout: Option<Duration>
+}
+
+impl Default for Sandbox {
+	fn default() -> Self {
+		Self {
+			allowed: Capability::default(),
+			max_steps: None,
+			max_depth: Some(Self::DEFAULT_MAX_DEPTH),
+			max_integer_bits: Some(Self::DEFAULT_MAX_INTEGER_BITS),
+			timeout: None
+		}
+	}
+}
+
+impl Sandbox {
+	/// How deeply stackframes can be nested unless [`max_depth`](Self::max_depth) is given.
+	///
+	/// Without a limit, deep recursion overflows the native stack and aborts the whole process,
+	/// which can't be caught. This leaves plenty of room on the main thread's stack.
+	pub const DEFAULT_MAX_DEPTH: usize = 200;
+
+	/// How many bits integers can have unless [`max_integer_bits`](Self::max_integer_bits) is
+	/// given; this is just under 80,000 decimal digits.
+	pub const DEFAULT_MAX_INTEGER_BITS: u64 = 1 << 18;
+
+	/// Creates a new sandbox that doesn't allow anything, and only has the default depth and
+	/// integer size limits.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Allows `capability` within the sandbox.
+	#[inline]
+	pub fn allow(mut self, capability: Capability) -> Self {
+		self.allowed |= capability;
+		self
+	}
+
+	/// Sets the maximum number of steps code can take.
+	#[inline]
+	pub fn max_steps(mut self, max_steps: u64) -> Self {
+		self.max_steps = Some(max_steps);
+		self
+	}
+
+	/// Sets the maximum number of stackframes that can be nested.
+	#[inline]
+	pub fn max_depth(mut self, max_depth: usize) -> Self {
+		self.max_depth = Some(max_depth);
+		self
+	}
+
+	/// Sets the maximum number of bits that the result of integer arithmetic can have.
+	#[inline]
+	pub fn max_integer_bits(mut self, max_integer_bits: u64) -> Self {
+		self.max_integer_bits = Some(max_integer_bits);
+		self
+	}
+
+	/// Sets the maximum amount of time code can run for.
+	#[inline]
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// Checks to see if `capability` is allowed.
+	#[inline]
+	pub fn allows(&self, capability: Capability) -> bool {
+		self.allowed.contains(capability)
+	}
+
+	/// Enters the sandbox on the current thread, with fresh limits.
+	///
+	/// The previous sandbox (if any) is restored when the returned guard is dropped.
+	#[must_use = "the sandbox is exited when the guard is dropped"]
+	pub fn enter(self) -> SandboxGuard {
+		let state = State {
+			deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+			sandbox: self,
+			steps: 0,
+			depth: 0
+		};
+
+		SandboxGuard(STATE.with(|current| current.borrow_mut().replace(state)))
+	}
+
+	/// Gets the sandbox that's active on the current thread, if any.
+	pub fn current() -> Option<Self> {
+		STATE.with(|state| state.borrow().as_ref().map(|state| state.sandbox.clone()))
+	}
+}
+
+/// Exits a sandbox when dropped; created via [`Sandbox::enter`].
+#[derive(Debug)]
+pub struct SandboxGuard(Option<State>);
+
+impl Drop for SandboxGuard {
+	fn drop(&mut self) {
+		let previous = self.0.take();
+
+		STATE.with(|state| *state.borrow_mut() = previous);
+	}
+}
+
+#[derive(Debug)]
+struct State {
+	sandbox: Sandbox,
+	steps: u64,
+	depth: usize,
+	deadline: Option<Instant>
+}
+
+thread_local! {
+	static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+/// Ensures the current sandbox allows `capability`, which `func` needs.
+///
+/// If there's no sandbox, everything's allowed.
+pub fn require(capability: Capability, func: &'static str) -> crate::Result<()> {
+	STATE.with(|state| match state.borrow().as_ref() {
+		Some(state) if !state.sandbox.allows(capability) =>
+			Err(SandboxError::Denied { func, capability: capability.name() }.into()),
+		_ => Ok(())
+	})
+}
+
+/// Gets how much time is left before the current sandbox's time limit.
+pub fn remaining_time() -> Option<Duration> {
+	STATE.with(|state| {
+		state.borrow().as_ref()
+			.and_then(|state| state.deadline)
+			.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+	})
+}
+
+/// Counts a step, raising an error if the step or time limit has been exceeded.
+pub(crate) fn step() -> crate::Result<()> {
+	STATE.with(|state| {
+		let mut state = state.borrow_mut();
+		let state =
+			match state.as_mut() {
+				Some(state) => state,
+				None => return Ok(())
+			};
+
+		state.steps += 1;
+
+		if let Some(max_steps) = state.sandbox.max_steps {
+			if state.steps > max_steps {
+				return Err(SandboxError::StepLimit(max_steps).into());
+			}
+		}
+
+		match (state.deadline, state.sandbox.timeout) {
+			(Some(deadline), Some(timeout)) if Instant::now() >= deadline =>
+				Err(SandboxError::Timeout(timeout).into()),
+			_ => Ok(())
+		}
+	})
+}
+
+/// Ensures that an integer with `bits` bits is allowed, raising an error if it's too large.
+///
+/// This is called before arithmetic that could produce enormous integers, with an estimate of how
+/// large the result will be.
+pub(crate) fn check_integer_bits(bits: u64) -> crate::Result<()> {
+	STATE.with(|state| match state.borrow().as_ref().and_then(|state| state.sandbox.max_integer_bits) {
+		Some(max_integer_bits) if bits > max_integer_bits =>
+			Err(SandboxError::SizeLimit(max_integer_bits).into()),
+		_ => Ok(())
+	})
+}
+
+/// Keeps track of a stackframe's depth; created via [`enter_frame`].
+pub(crate) struct FrameGuard(bool);
+
+impl Drop for FrameGuard {
+	fn drop(&mut self) {
+		if self.0 {
+			STATE.with(|state| {
+				if let Some(state) = state.borrow_mut().as_mut() {
+					state.depth = state.depth.saturating_sub(1);
+				}
+			});
+		}
+	}
+}
+
+/// Enters a new stackframe, raising an error if that'd exceed the depth limit.
+pub(crate) fn enter_frame() -> crate::Result<FrameGuard> {
+	STATE.with(|state| {
+		let mut state = state.borrow_mut();
+		let state =
+			match state.as_mut() {
+				Some(state) => state,
+				None => return Ok(FrameGuard(false))
+			};
+
+		if let Some(max_depth) = state.sandbox.max_depth {
+			if state.depth >= max_depth {
+				return Err(SandboxError::DepthLimit(max_depth).into());
+			}
+		}
+
+		state.depth += 1;
+		Ok(FrameGuard(true))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Error;
+
+	#[test]
+	fn capabilities() {
+		assert!(require(Capability::PROCESS, "Kernel::system").is_ok());
+
+		let guard = Sandbox::new().allow(Capability::NETWORK).enter();
+		assert!(require(Capability::NETWORK, "Http::get").is_ok());
+		assert!(matches!(
+			require(Capability::PROCESS, "Kernel::system"),
+			Err(Error::SandboxError(SandboxError::Denied { func: "Kernel::system", capability: "process" }))
+		));
+
+		drop(guard);
+		assert!(require(Capability::PROCESS, "Kernel::system").is_ok());
+		assert_eq!(Sandbox::current(), None);
+	}
+
+	#[test]
+	fn default_depth_limit() {
+		let _guard = Sandbox::new().enter();
+		let mut frames = Vec::new();
+
+		for _ in 0..Sandbox::DEFAULT_MAX_DEPTH {
+			frames.push(enter_frame().unwrap());
+		}
+
+		assert!(matches!(enter_frame().map(drop),
+			Err(Error::SandboxError(SandboxError::DepthLimit(Sandbox::DEFAULT_MAX_DEPTH)))));
+	}
+
+	#[test]
+	fn limits() {
+		let _guard = Sandbox::new().max_steps(2).max_depth(1).enter();
+
+		assert!(step().is_ok());
+		assert!(step().is_ok());
+		assert!(matches!(step(), Err(Error::SandboxError(SandboxError::StepLimit(2)))));
+		assert!(matches!(step(), Err(Error::SandboxError(SandboxError::StepLimit(2)))));
+
+		let frame = enter_frame().unwrap();
+		assert!(matches!(enter_frame().map(drop), Err(Error::SandboxError(SandboxError::DepthLimit(1)))));
+		drop(frame);
+		assert!(enter_frame().is_ok());
+
+		assert!(check_integer_bits(Sandbox::DEFAULT_MAX_INTEGER_BITS).is_ok());
+		assert!(matches!(check_integer_bits(Sandbox::DEFAULT_MAX_INTEGER_BITS + 1),
+			Err(Error::SandboxError(SandboxError::SizeLimit(Sandbox::DEFAULT_MAX_INTEGER_BITS)))));
+
+		{
+			let _inner = Sandbox::new().timeout(Duration::from_secs(0)).enter();
+			assert!(matches!(step(), Err(Error::SandboxError(SandboxError::Timeout(_)))));
+			assert_eq!(remaining_time(), Some(Duration::from_secs(0)));
+		}
+
+		assert_eq!(remaining_time(), None);
+	}
+}
@@ -415,12 +415,12 @@ mod tests {
 			let obj1 = Object::from(Basic);
 			let obj2 = Object::from(Basic);
 
-			let hash = *Basic::qs_hash(&obj1, args!()).unwrap()
-				.call_downcast::<Number>().unwrap();
+			let hash = Basic::qs_hash(&obj1, args!()).unwrap()
+				.call_downcast::<Number>().unwrap().clone();
 			// make sure repeated hashes are the same.
-			assert_eq!(hash, call_unwrap!(Basic::qs_hash(obj1) -> Number; |n| *n));
+			assert_eq!(hash, call_unwrap!(Basic::qs_hash(obj1) -> Number; |n| n.clone()));
 			// make sure two hashes aren't identical for the same object.
-			assert_ne!(hash, call_unwrap!(Basic::qs_hash(obj2) -> Number; |n| *n));
+			assert_ne!(hash, call_unwrap!(Basic::qs_hash(obj2) -> Number; |n| n.clone()));
 
 			assert_call_idempotent!(Basic::qs_hash(Basic));
 		}
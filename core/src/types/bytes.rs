@@ -0,0 +1,483 @@
+//! The [`Bytes`] type in Quest.
+
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Boolean, Number, List};
+use crate::utils::SliceIndex;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display, Formatter, Write};
+use tracing::instrument;
+
+/// An immutable sequence of bytes, for binary data that isn't necessarily valid UTF-8.
+///
+/// [`Text`] is converted to bytes with `encode`, and bytes are converted back with `decode`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Bytes(Vec<u8>);
+
+impl Debug for Bytes {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.debug_tuple("Bytes").field(&self.0).finish()
+		} else {
+			write!(f, "Bytes({})", self)
+		}
+	}
+}
+
+impl Display for Bytes {
+	/// Writes the bytes like a Rust byte string, escaping anything that isn't printable ASCII.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("b\"")?;
+
+		for &byte in &self.0 {
+			for chr in std::ascii::escape_default(byte) {
+				f.write_char(chr as char)?;
+			}
+		}
+
+		f.write_char('"')
+	}
+}
+
+impl From<Vec<u8>> for Bytes {
+	#[inline]
+	fn from(bytes: Vec<u8>) -> Self {
+		Self(bytes)
+	}
+}
+
+impl From<&[u8]> for Bytes {
+	#[inline]
+	fn from(bytes: &[u8]) -> Self {
+		Self(bytes.to_vec())
+	}
+}
+
+impl From<Bytes> for Vec<u8> {
+	#[inline]
+	fn from(bytes: Bytes) -> Self {
+		bytes.0
+	}
+}
+
+impl AsRef<[u8]> for Bytes {
+	#[inline]
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl Bytes {
+	#[inline]
+	pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+		Self(bytes.into())
+	}
+
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Gets a byte or range of bytes; negative indices count from the end.
+	#[inline]
+	pub fn get<I: SliceIndex<[u8]>>(&self, index: I) -> Option<&I::Output> {
+		index.get(&self.0)
+	}
+
+	/// The bytes as lowercase hexadecimal, two digits per byte.
+	pub fn hex(&self) -> String {
+		let mut hex = String::with_capacity(self.len() * 2);
+
+		for byte in &self.0 {
+			write!(hex, "{:02x}", byte).expect("writing to a string can't fail");
+		}
+
+		hex
+	}
+
+	/// Gets the data to write for `obj`.
+	///
+	/// [`Bytes`] are written as-is, and anything else is converted to [`Text`] and written as UTF-8.
+	pub fn data_of(obj: &Object) -> crate::Result<Vec<u8>> {
+		if let Some(bytes) = obj.downcast::<Self>() {
+			Ok(bytes.0.clone())
+		} else {
+			Ok(obj.call_downcast::<Text>()?.to_string().into_bytes())
+		}
+	}
+}
+
+/// The encodings that [`Text`] can be converted to and from [`Bytes`] with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+	#[default]
+	Utf8,
+	Ascii,
+	Latin1
+}
+
+impl TryFrom<&str> for Encoding {
+	type Error = ValueError;
+
+	/// Parses an encoding's name, ignoring case, `-`s and `_`s (so `"UTF-8"` and `"utf8"` are the same).
+	fn try_from(name: &str) -> Result<Self, Self::Error> {
+		let normalized = name.chars()
+			.filter(|&chr| chr != '-' && chr != '_')
+			.collect::<String>()
+			.to_ascii_lowercase();
+
+		match normalized.as_str() {
+			"utf8" => Ok(Self::Utf8),
+			"ascii" | "usascii" => Ok(Self::Ascii),
+			"latin1" | "iso88591" => Ok(Self::Latin1),
+			_ => Err(ValueError::Messaged(format!("unknown encoding: {:?}", name)))
+		}
+	}
+}
+
+impl Encoding {
+	/// Converts `text` to bytes, failing if it contains characters the encoding can't represent.
+	pub fn encode(self, text: &str) -> Result<Vec<u8>, ValueError> {
+		let unrepresentable = |(idx, chr): (usize, char)|
+			ValueError::Messaged(format!("{:?} (at index {}) can't be encoded as {:?}", chr, idx, self));
+
+		match self {
+			Self::Utf8 => Ok(text.as_bytes().to_vec()),
+			Self::Ascii => text.chars().enumerate()
+				.map(|(idx, chr)| if chr.is_ascii() { Ok(chr as u8) } else { Err(unrepresentable((idx, chr))) })
+				.collect(),
+			Self::Latin1 => text.chars().enumerate()
+				.map(|(idx, chr)| u8::try_from(chr as u32).map_err(|_| unrepresentable((idx, chr))))
+				.collect()
+		}
+	}
+
+	/// Converts `bytes` to text.
+	///
+	/// If `lossy` is set, invalid bytes are replaced with `U+FFFD`; otherwise, they're an error.
+	pub fn decode(self, bytes: &[u8], lossy: bool) -> Result<String, ValueError> {
+		match self {
+			Self::Utf8 if lossy => Ok(String::from_utf8_lossy(bytes).into_owned()),
+			Self::Utf8 => std::str::from_utf8(bytes)
+				.map(str::to_string)
+				.map_err(|err| ValueError::Messaged(format!("invalid utf-8: {}", err))),
+			Self::Ascii => bytes.iter().enumerate()
+				.map(|(idx, &byte)|
+					if byte.is_ascii() {
+						Ok(byte as char)
+					} else if lossy {
+						Ok(char::REPLACEMENT_CHARACTER)
+					} else {
+						Err(ValueError::Messaged(format!("invalid ascii: byte {:#04x} at index {}", byte, idx)))
+					})
+				.collect(),
+			Self::Latin1 => Ok(bytes.iter().map(|&byte| byte as char).collect())
+		}
+	}
+
+	/// Parses the optional encoding argument at `idx`, defaulting to UTF-8.
+	pub(crate) fn from_arg(args: &Args, idx: usize) -> crate::Result<Self> {
+		match args.arg(idx) {
+			Some(name) => Ok(Self::try_from(name.call_downcast::<Text>()?.as_ref())?),
+			None => Ok(Self::default())
+		}
+	}
+}
+
+/// Converts a number to a byte, which must be an integer from 0 to 255.
+fn to_byte(num: &Number) -> crate::Result<u8> {
+	i64::try_from(num.clone()).ok()
+		.and_then(|num| u8::try_from(num).ok())
+		.ok_or_else(|| ValueError::Messaged(format!("invalid byte: {}", num)).into())
+}
+
+/// Quest methods
+impl Bytes {
+	/// Creates new bytes.
+	///
+	/// # Arguments
+	/// 1. (optional, `@list`) The bytes, as numbers from 0 to 255. [`Text`] is encoded as UTF-8
+	///    instead. If omitted, the bytes are empty.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Bytes::new([104, 105]) == "hi".encode());
+	/// assert(Bytes::new("hi") == "hi".encode());
+	/// assert(Bytes::new().len() == 0);
+	/// ```
+	#[instrument(name="Bytes::new", level="trace", skip(args), fields(?args))]
+	pub fn qs_new(args: Args) -> crate::Result<Object> {
+		let arg =
+			if let Some(arg) = args.arg(0) {
+				arg
+			} else {
+				return Ok(Self::default().into());
+			};
+
+		if let Some(text) = arg.downcast::<Text>() {
+			return Ok(Self::from(text.as_ref().as_bytes()).into());
+		}
+
+		let list = arg.call_downcast::<List>()?;
+		let bytes = list.iter()
+			.map(|num| to_byte(&*num.call_downcast::<Number>()?))
+			.collect::<crate::Result<Vec<_>>>()?;
+
+		Ok(Self::from(bytes).into())
+	}
+
+	/// Gets the bytes escaped like a Rust byte string, eg `b"hi\x00"`.
+	#[instrument(name="Bytes::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+
+	#[instrument(name="Bytes::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("{:?}", *this.try_downcast::<Self>()?).into())
+	}
+
+	/// Simply returns `this`.
+	#[instrument(name="Bytes::@bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Converts `this` to a [`List`] of each byte as a [`Number`].
+	#[instrument(name="Bytes::@list", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_list(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.0.iter().map(|&byte| Object::from(Number::from(byte))).collect::<List>().into())
+	}
+
+	/// Converts `this` to a [`Boolean`], returning `false` only if there are no bytes.
+	#[instrument(name="Bytes::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(!this.try_downcast::<Self>()?.is_empty()).into())
+	}
+
+	#[instrument(name="Bytes::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::utils::hash(&*this.try_downcast::<Self>()?).into())
+	}
+
+	#[instrument(name="Bytes::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.is_some_and(|rhs| *this == *rhs).into())
+	}
+
+	/// Compares the bytes lexicographically.
+	#[instrument(name="Bytes::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>();
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.map(|rhs| this.cmp(&rhs).into()).unwrap_or_default())
+	}
+
+	/// Concatenates two sets of bytes.
+	///
+	/// # Arguments
+	/// 1. (required, `@bytes`) The bytes to append.
+	#[instrument(name="Bytes::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Self([this.0.as_slice(), rhs.0.as_slice()].concat()).into())
+	}
+
+	#[instrument(name="Bytes::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Gets a byte as a [`Number`], or a range of bytes as [`Bytes`].
+	///
+	/// Out of bounds indices return `null`.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The index of the byte, or the start of the range.
+	/// 2. (optional, `@num`) The end of the range, inclusive.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// bytes = "hello".encode();
+	/// assert(bytes.get(1) == 101);
+	/// assert(bytes.get(-1) == 111);
+	/// assert(bytes.get(1, 3) == "ell".encode());
+	/// assert(bytes.get(9) == null);
+	/// ```
+	#[instrument(name="Bytes::get", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
+		let start = isize::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
+		let stop = args.arg(1)
+			.map(|stop| stop.call_downcast::<Number>().map(|stop| stop.clone()))
+			.transpose()?
+			.map(isize::try_from)
+			.transpose()?;
+
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(match stop {
+			Some(stop) => this.get(start..=stop).map(|bytes| Self::from(bytes).into()),
+			None => this.get(start).map(|&byte| Number::from(byte).into())
+		}.unwrap_or_default())
+	}
+
+	/// Gets the bytes as lowercase hexadecimal [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Bytes::new([0, 15, 255]).hex() == "000fff");
+	/// ```
+	#[instrument(name="Bytes::hex", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hex(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.hex().into())
+	}
+
+	/// Converts the bytes to [`Text`].
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) The encoding: `"utf-8"` (the default), `"ascii"`, or `"latin-1"`.
+	/// 2. (optional, `@text`) What to do with invalid bytes: `"strict"` (the default) raises a
+	///    `ValueError`, and `"replace"` replaces them with `U+FFFD`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Bytes::new([104, 105]).decode() == "hi");
+	/// assert(Bytes::new([233]).decode("latin-1") == "é");
+	/// assert(Bytes::new([104, 255]).decode("utf-8", "replace") == "h�");
+	/// ```
+	#[instrument(name="Bytes::decode", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_decode(this: &Object, args: Args) -> crate::Result<Object> {
+		let encoding = Encoding::from_arg(&args, 0)?;
+		let lossy =
+			match args.arg(1).map(|errors| errors.call_downcast::<Text>().map(|e| e.clone())).transpose()? {
+				None => false,
+				Some(errors) => match errors.as_ref() {
+					"strict" => false,
+					"replace" => true,
+					other => return Err(ValueError::Messaged(format!("unknown error handling: {:?}", other)).into())
+				}
+			};
+
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(encoding.decode(&this.0, lossy)?.into())
+	}
+}
+
+impl_object_type!{
+for Bytes [(init_parent super::Basic super::Comparable) (parents super::Basic) (convert "@bytes")]:
+	"new" => function Self::qs_new,
+
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@bytes" => method Self::qs_at_bytes,
+	"@list" => method Self::qs_at_list,
+	"@bool" => method Self::qs_at_bool,
+	"hash" => method Self::qs_hash,
+
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+	"+" => method Self::qs_add,
+
+	"len" => method Self::qs_len,
+	"get" => method Self::qs_get,
+	"[]" => method Self::qs_get,
+	"hex" => method Self::qs_hex,
+	"decode" => method Self::qs_decode,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn bytes(data: &[u8]) -> Bytes {
+		Bytes::from(data)
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(bytes(b"hi\x00\"\xff").to_string(), r#"b"hi\x00\"\xff""#);
+		assert_eq!(format!("{:?}", bytes(b"a")), r#"Bytes(b"a")"#);
+	}
+
+	#[test]
+	fn slicing() {
+		let data = bytes(b"hello");
+
+		assert_eq!(data.get(1usize), Some(&b'e'));
+		assert_eq!(data.get(-1isize), Some(&b'o'));
+		assert_eq!(data.get(5isize), None);
+		assert_eq!(data.get(1isize..=3), Some(&b"ell"[..]));
+		assert_eq!(data.get(-2isize..=100), Some(&b"lo"[..]));
+		let (start, stop) = (3isize, 1);
+		assert_eq!(data.get(start..=stop), None);
+	}
+
+	#[test]
+	fn encodings() {
+		assert_eq!(Encoding::try_from("UTF-8").unwrap(), Encoding::Utf8);
+		assert_eq!(Encoding::try_from("iso_8859_1").unwrap(), Encoding::Latin1);
+		assert!(Encoding::try_from("ebcdic").is_err());
+
+		assert_eq!(Encoding::Utf8.encode("é").unwrap(), b"\xc3\xa9");
+		assert_eq!(Encoding::Latin1.encode("é").unwrap(), b"\xe9");
+		assert!(Encoding::Ascii.encode("é").is_err());
+		assert!(Encoding::Latin1.encode("€").is_err());
+
+		assert_eq!(Encoding::Latin1.decode(b"\xe9", false).unwrap(), "é");
+		assert!(Encoding::Utf8.decode(b"\xe9", false).is_err());
+		assert_eq!(Encoding::Utf8.decode(b"a\xe9", true).unwrap(), "a\u{fffd}");
+		assert!(Encoding::Ascii.decode(b"\x80", false).is_err());
+		assert_eq!(Encoding::Ascii.decode(b"a\x80", true).unwrap(), "a\u{fffd}");
+	}
+
+	mod qs {
+		use super::*;
+
+		#[test]
+		fn new() {
+			crate::init();
+
+			let new = |arg: Object| Bytes::qs_new(args!(arg));
+			assert_eq!(*new(vec![Object::from(1), Object::from(255)].into()).unwrap().downcast::<Bytes>().unwrap(), bytes(b"\x01\xff"));
+			assert_eq!(*new("hi".into()).unwrap().downcast::<Bytes>().unwrap(), bytes(b"hi"));
+			assert_matches!(new(vec![Object::from(256)].into()).unwrap_err(), crate::Error::ValueError(..));
+		}
+
+		#[test]
+		fn get() {
+			assert_call_eq!(Bytes::qs_get(bytes(b"hello"), 0) -> Number, 104);
+			assert_call_eq!(Bytes::qs_get(bytes(b"hello"), 1, -2) -> Bytes, bytes(b"ell"));
+		}
+
+		#[test]
+		fn add_and_compare() {
+			assert_call_eq!(Bytes::qs_add(bytes(b"ab"), bytes(b"\xff")) -> Bytes, bytes(b"ab\xff"));
+			assert_call_eq!(Bytes::qs_add(bytes(b"ab"), "cd") -> Bytes, bytes(b"abcd"));
+			assert_call_eq!(Bytes::qs_cmp(bytes(b"ab"), bytes(b"b")) -> Number, -1);
+			assert_call_eq!(Bytes::qs_eql(bytes(b"ab"), "ab") -> Boolean, false);
+		}
+
+		#[test]
+		fn encode_and_decode() {
+			assert_call_eq!(Bytes::qs_hex(bytes(b"\x00\x0f\xff")) -> Text, Text::from("000fff"));
+			assert_call_eq!(Text::qs_encode("é", "latin-1") -> Bytes, bytes(b"\xe9"));
+			assert_call_eq!(Bytes::qs_decode(bytes(b"\xc3\xa9")) -> Text, Text::from("é"));
+			assert_call_eq!(Bytes::qs_decode(bytes(b"\xff"), "utf-8", "replace") -> Text, Text::from("\u{fffd}"));
+			assert_call_err!(Bytes::qs_decode(bytes(b"\xff")), crate::Error::ValueError(..));
+		}
+	}
+}
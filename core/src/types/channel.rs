@@ -0,0 +1,454 @@
+//! The [`Channel`] type in Quest.
+
+use crate::{Object, Args};
+use crate::types::List;
+use crate::error::ValueError;
+use parking_lot::{Mutex, Condvar};
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Used by [`Channel::select`] to be woken up when any of the channels it's waiting on changes.
+#[derive(Default)]
+struct Signal {
+	ready: Mutex<bool>,
+	condvar: Condvar
+}
+
+impl Signal {
+	fn notify(&self) {
+		*self.ready.lock() = true;
+		self.condvar.notify_all();
+	}
+
+	fn wait(&self) {
+		let mut ready = self.ready.lock();
+
+		while !*ready {
+			self.condvar.wait(&mut ready);
+		}
+
+		*ready = false;
+	}
+}
+
+#[derive(Default)]
+struct State {
+	queue: VecDeque<Object>,
+	closed: bool,
+	selectors: Vec<Arc<Signal>>
+}
+
+impl State {
+	fn notify_selectors(&self) {
+		for selector in &self.selectors {
+			selector.notify();
+		}
+	}
+}
+
+#[derive(Default)]
+struct Inner {
+	state: Mutex<State>,
+	capacity: Option<usize>,
+	readable: Condvar,
+	writable: Condvar
+}
+
+/// A queue for sending values between [`Thread`](crate::types::Thread)s.
+///
+/// Channels can either be unbounded, or have a maximum capacity, in which case sending blocks
+/// until there's room. Once a channel is closed, nothing more can be sent to it, but values that
+/// were already sent can still be received.
+#[derive(Clone, Default)]
+pub struct Channel(Arc<Inner>);
+
+impl Debug for Channel {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let state = self.0.state.lock();
+
+		f.debug_struct("Channel")
+			.field("len", &state.queue.len())
+			.field("capacity", &self.0.capacity)
+			.field("closed", &state.closed)
+			.finish()
+	}
+}
+
+/// An error that's returned when sending to a closed [`Channel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Closed;
+
+impl Display for Closed {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "channel is closed")
+	}
+}
+
+impl std::error::Error for Closed {}
+
+impl From<Closed> for crate::Error {
+	fn from(err: Closed) -> Self {
+		ValueError::Messaged(err.to_string()).into()
+	}
+}
+
+impl Channel {
+	/// Creates a channel without a maximum capacity.
+	#[inline]
+	pub fn unbounded() -> Self {
+		Self::default()
+	}
+
+	/// Creates a channel that can hold at most `capacity` values.
+	///
+	/// # Panics
+	/// Panics if `capacity` is zero.
+	pub fn bounded(capacity: usize) -> Self {
+		assert_ne!(capacity, 0, "channels must have a nonzero capacity");
+
+		Self(Arc::new(Inner { capacity: Some(capacity), ..Inner::default() }))
+	}
+
+	/// The maximum amount of values the channel can hold, or `None` if it's unbounded.
+	#[inline]
+	pub fn capacity(&self) -> Option<usize> {
+		self.0.capacity
+	}
+
+	/// The amount of values waiting to be received.
+	pub fn len(&self) -> usize {
+		self.0.state.lock().queue.len()
+	}
+
+	/// Checks to see if there are no values waiting to be received.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Checks to see if the channel has been closed.
+	pub fn is_closed(&self) -> bool {
+		self.0.state.lock().closed
+	}
+
+	/// Sends `value`, waiting until there's room for it if the channel is full.
+	pub fn send(&self, value: Object) -> Result<(), Closed> {
+		let mut state = self.0.state.lock();
+
+		while !state.closed && self.0.capacity.is_some_and(|cap| cap <= state.queue.len()) {
+			self.0.writable.wait(&mut state);
+		}
+
+		if state.closed {
+			return Err(Closed);
+		}
+
+		state.queue.push_back(value);
+		state.notify_selectors();
+		self.0.readable.notify_one();
+
+		Ok(())
+	}
+
+	fn pop(&self, state: &mut State) -> Option<Object> {
+		let value = state.queue.pop_front()?;
+		self.0.writable.notify_one();
+		Some(value)
+	}
+
+	/// Receives a value, waiting until one's sent. `None` is returned if the channel is closed and
+	/// there's nothing left to receive.
+	pub fn recv(&self) -> Option<Object> {
+		let mut state = self.0.state.lock();
+
+		loop {
+			if let Some(value) = self.pop(&mut state) {
+				return Some(value);
+			} else if state.closed {
+				return None;
+			}
+
+			self.0.readable.wait(&mut state);
+		}
+	}
+
+	/// Receives a value if one's available, without waiting.
+	pub fn try_recv(&self) -> Option<Object> {
+		self.pop(&mut self.0.state.lock())
+	}
+
+	/// Closes the channel, waking up anything waiting on it.
+	pub fn close(&self) {
+		let mut state = self.0.state.lock();
+		state.closed = true;
+		state.notify_selectors();
+
+		self.0.readable.notify_all();
+		self.0.writable.notify_all();
+	}
+
+	/// Waits until any of `channels` has a value, returning its index and the value.
+	///
+	/// If the channels are all closed and empty, `None` is returned.
+	pub fn select(channels: &[Self]) -> Option<(usize, Object)> {
+		let signal = Arc::new(Signal::default());
+
+		for channel in channels {
+			channel.0.state.lock().selectors.push(signal.clone());
+		}
+
+		let selected = 'select: loop {
+			let mut any_open = false;
+
+			for (idx, channel) in channels.iter().enumerate() {
+				let mut state = channel.0.state.lock();
+
+				if let Some(value) = channel.pop(&mut state) {
+					break 'select Some((idx, value));
+				}
+
+				any_open |= !state.closed;
+			}
+
+			if !any_open {
+				break None;
+			}
+
+			signal.wait();
+		};
+
+		for channel in channels {
+			channel.0.state.lock().selectors.retain(|selector| !Arc::ptr_eq(selector, &signal));
+		}
+
+		selected
+	}
+}
+
+/// Quest methods
+impl Channel {
+	/// Creates a new channel, with an optional capacity.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The channel's maximum capacity. If omitted, the channel is unbounded.
+	///
+	/// If the capacity is less than one or too large to fit in memory, a [`ValueError`] is raised.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// chan = Channel::new(1);
+	/// spawn({ chan.send(1).send(2).close() });
+	///
+	/// assert(chan.recv() == 1);
+	/// assert(chan.recv() == 2);
+	/// assert(chan.recv() == null);
+	/// ```
+	#[instrument(name="Channel::new", level="trace", skip(args), fields(?args))]
+	pub fn qs_new(args: Args) -> crate::Result<Object> {
+		use crate::types::Number;
+		use std::convert::TryFrom;
+
+		match args.arg(0) {
+			None => Ok(Self::unbounded().into()),
+			Some(capacity) => {
+				let capacity = capacity.call_downcast::<Number>()?.floor();
+
+				match usize::try_from(capacity.clone()) {
+					Ok(capacity) if capacity != 0 => Ok(Self::bounded(capacity).into()),
+					_ => Err(ValueError::Messaged(format!("invalid channel capacity: {}", capacity)).into())
+				}
+			}
+		}
+	}
+
+	/// Sends a value, waiting until there's room for it; `this` is returned.
+	///
+	/// # Arguments
+	/// 1. (required) The value to send.
+	#[instrument(name="Channel::send", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_send(this: &Object, args: Args) -> crate::Result<Object> {
+		let value = args.try_arg(0)?.clone();
+		let channel = this.try_downcast::<Self>()?.clone();
+
+		channel.send(value)?;
+		Ok(this.clone())
+	}
+
+	/// Receives a value, waiting until one's available.
+	///
+	/// If the channel is closed and there's nothing left to receive, `null` is returned.
+	#[instrument(name="Channel::recv", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_recv(this: &Object, _: Args) -> crate::Result<Object> {
+		let channel = this.try_downcast::<Self>()?.clone();
+
+		Ok(channel.recv().unwrap_or_default())
+	}
+
+	/// Receives a value if one's available, returning `null` otherwise.
+	#[instrument(name="Channel::try_recv", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_try_recv(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.try_recv().unwrap_or_default())
+	}
+
+	/// Closes the channel; `this` is returned.
+	#[instrument(name="Channel::close", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_close(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast::<Self>()?.close();
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if the channel is closed.
+	#[instrument(name="Channel::closed?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_closed_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_closed().into())
+	}
+
+	/// Gets the amount of values waiting to be received.
+	#[instrument(name="Channel::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Gets the channel's maximum capacity, or `null` if it's unbounded.
+	#[instrument(name="Channel::capacity", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_capacity(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.capacity().map(Object::from).unwrap_or_default())
+	}
+
+	#[instrument(name="Channel::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("{:?}", *this.try_downcast::<Self>()?).into())
+	}
+
+	/// Waits until any of the given channels has a value, returning a list of the channel and the
+	/// value.
+	///
+	/// If every channel is closed and empty, `null` is returned.
+	///
+	/// # Arguments
+	/// 1. (required, rest) The channels to wait on.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// numbers = Channel::new();
+	/// words = Channel::new();
+	/// words.send("hi");
+	///
+	/// selected = Channel::select(numbers, words);
+	/// assert(selected.get(0).__id__ == words.__id__);
+	/// assert(selected.get(1) == "hi");
+	/// ```
+	#[instrument(name="Channel::select", level="trace", skip(args), fields(?args))]
+	pub fn qs_select(args: Args) -> crate::Result<Object> {
+		let channels = args.as_ref()
+			.iter()
+			.map(|chan| chan.try_downcast::<Self>().map(|chan| chan.clone()))
+			.collect::<crate::Result<Vec<_>>>()?;
+
+		Ok(Self::select(&channels)
+			.map(|(idx, value)| List::from(vec![args.as_ref()[idx].clone(), value]).into())
+			.unwrap_or_default())
+	}
+}
+
+impl_object_type!{
+for Channel [(parents super::Basic)]:
+	"new" => function Self::qs_new,
+	"select" => function Self::qs_select,
+
+	"send" => method Self::qs_send,
+	"recv" => method Self::qs_recv,
+	"try_recv" => method Self::qs_try_recv,
+	"close" => method Self::qs_close,
+	"closed?" => method Self::qs_closed_q,
+	"len" => method Self::qs_len,
+	"capacity" => method Self::qs_capacity,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Number;
+
+	fn num(obj: Option<Object>) -> Number {
+		obj.expect("no value").downcast::<Number>().expect("not a number").clone()
+	}
+
+	#[test]
+	fn send_and_recv() {
+		crate::init();
+
+		let chan = Channel::unbounded();
+		chan.send(1.into()).unwrap();
+		chan.send(2.into()).unwrap();
+		assert_eq!(chan.len(), 2);
+
+		assert_eq!(num(chan.recv()), 1);
+		assert_eq!(num(chan.try_recv()), 2);
+		assert!(chan.try_recv().is_none());
+
+		chan.send(3.into()).unwrap();
+		chan.close();
+		assert_eq!(chan.send(4.into()), Err(Closed));
+		assert_eq!(num(chan.recv()), 3);
+		assert!(chan.recv().is_none());
+	}
+
+	#[test]
+	fn bounded_blocks_sender() {
+		crate::init();
+
+		let chan = Channel::bounded(1);
+		let sender = {
+			let chan = chan.clone();
+			std::thread::spawn(move || (0..10).for_each(|i| chan.send(i.into()).unwrap()))
+		};
+
+		for i in 0..10 {
+			assert!(chan.len() <= 1);
+			assert_eq!(num(chan.recv()), i);
+		}
+
+		sender.join().unwrap();
+	}
+
+	#[test]
+	fn new_checks_capacity() {
+		crate::init();
+
+		let new = |capacity: Number| Channel::qs_new(args!(capacity));
+		let capacity = |chan: Object| chan.downcast::<Channel>().unwrap().capacity();
+
+		assert_eq!(capacity(Channel::qs_new(args!()).unwrap()), None);
+		assert_eq!(capacity(new(Number::from(3.5)).unwrap()), Some(3));
+
+		assert!(matches!(new(Number::from(0)), Err(crate::Error::ValueError(..))));
+		assert!(matches!(new(Number::from(0.5)), Err(crate::Error::ValueError(..))));
+		assert!(matches!(new(Number::from(-1)), Err(crate::Error::ValueError(..))));
+		assert!(matches!(new(Number::from(num_bigint::BigInt::from(1) << 64)),
+			Err(crate::Error::ValueError(..))));
+	}
+
+	#[test]
+	fn select() {
+		crate::init();
+
+		let (first, second) = (Channel::unbounded(), Channel::unbounded());
+		let sender = {
+			let second = second.clone();
+			std::thread::spawn(move || second.send(12.into()).unwrap())
+		};
+
+		let (idx, value) = Channel::select(&[first.clone(), second.clone()]).unwrap();
+		assert_eq!(idx, 1);
+		assert_eq!(num(Some(value)), 12);
+		sender.join().unwrap();
+
+		first.close();
+		second.close();
+		assert!(Channel::select(&[first, second]).is_none());
+	}
+}
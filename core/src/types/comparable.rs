@@ -20,7 +20,7 @@ pub struct Comparable;
 /// Compare the two sides.
 fn compare(lhs: &Object, rhs: &Object) -> crate::Result<Option<Ordering>> {
 	let num = lhs.call_attr_lit(&Literal::CMP, &[rhs])?;
-	if let Some(num) = num.downcast::<Number>().map(|n| *n) {
+	if let Some(num) = num.downcast::<Number>().map(|n| n.clone()) {
 		Ok(Some(num.cmp(&Number::ZERO)))
 	} else {
 		Ok(None)
@@ -0,0 +1,382 @@
+//! Information about the running program and its environment.
+
+pub mod opt_parse;
+
+pub use opt_parse::OptParse;
+
+use crate::{Object, Args, Literal};
+use crate::error::ValueError;
+use crate::sandbox::{self, Capability};
+use crate::types::{Text, Null, Number, List, Map, Path};
+use parking_lot::{Mutex, RwLock};
+use std::convert::TryFrom;
+use std::path::PathBuf;
+use tracing::instrument;
+
+lazy_static::lazy_static! {
+	static ref ARGS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+	static ref SCRIPT: RwLock<Option<PathBuf>> = RwLock::new(None);
+	static ref AT_EXIT: Mutex<Vec<Object>> = Mutex::new(Vec::new());
+}
+
+/// The namespace for the program's arguments, environment variables, working directory, and
+/// exiting.
+///
+/// Reading or changing environment variables and the working directory needs the `ENV`
+/// [`Capability`] when sandboxed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Env;
+
+impl Env {
+	/// Sets the arguments that were given to the script, not including the script itself.
+	pub fn set_args<I: IntoIterator<Item=String>>(args: I) {
+		*ARGS.write() = args.into_iter().collect();
+	}
+
+	/// Gets the arguments that were given to the script.
+	pub fn args() -> Vec<String> {
+		ARGS.read().clone()
+	}
+
+	/// Sets the path of the script that's being run.
+	pub fn set_script(script: Option<PathBuf>) {
+		*SCRIPT.write() = script;
+	}
+
+	/// Gets the path of the script that's being run, if there is one.
+	pub fn script() -> Option<PathBuf> {
+		SCRIPT.read().clone()
+	}
+
+	/// Calls every block registered with `Env::at_exit`, most recently registered first.
+	///
+	/// Each block is only ever called once. If any of them raise an error, the rest are still
+	/// called, and the first error is returned.
+	pub fn run_at_exit() -> crate::Result<()> {
+		let mut result = Ok(());
+
+		// take them one at a time, so blocks can register more blocks. The lock has to be released
+		// before calling the block, which is why this isn't a `while let`.
+		loop {
+			let block = AT_EXIT.lock().pop();
+			let block =
+				match block {
+					Some(block) => block,
+					None => break
+				};
+
+			if let Err(err) = block.call_attr_lit(&Literal::CALL, &[]) {
+				if result.is_ok() {
+					result = Err(err);
+				}
+			}
+		}
+
+		result
+	}
+
+	/// Runs the `at_exit` blocks, and then exits with `code`.
+	pub fn exit(code: i32) -> ! {
+		if let Err(err) = Self::run_at_exit() {
+			eprintln!("uncaught error in an at_exit block:\n{}", err);
+		}
+
+		std::process::exit(code)
+	}
+}
+
+/// Gets the name of an environment variable, making sure they can be accessed first.
+fn var_arg(args: &Args, func: &'static str) -> crate::Result<String> {
+	sandbox::require(Capability::ENV, func)?;
+
+	Ok(args.try_arg(0)?.call_downcast::<Text>()?.to_string())
+}
+
+/// Quest methods
+impl Env {
+	/// Gets a [`List`] of the arguments given to the script, not including the script itself.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// # run with `quest -f greet.qs -- world`
+	/// disp("hello,", Env::args().get(0));
+	/// ```
+	#[instrument(name="Env::args", level="trace", skip(_args), fields(args=?_args))]
+	pub fn qs_args(_args: Args) -> crate::Result<Object> {
+		Ok(Self::args().into_iter().map(Object::from).collect::<List>().into())
+	}
+
+	/// Gets the [`Path`] of the script that's being run, or `null` if there isn't one.
+	#[instrument(name="Env::script", level="trace", skip(_args), fields(args=?_args))]
+	pub fn qs_script(_args: Args) -> crate::Result<Object> {
+		Ok(Self::script().map(|path| Path::from(path).into()).unwrap_or_default())
+	}
+
+	/// Gets an environment variable.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The variable's name.
+	/// 2. (optional) What to return if it isn't set; defaults to `null`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// editor = Env::get("EDITOR", "vi");
+	/// ```
+	#[instrument(name="Env::get", level="trace", skip(args), fields(?args))]
+	pub fn qs_get(args: Args) -> crate::Result<Object> {
+		let name = var_arg(&args, "Env::get")?;
+
+		match std::env::var_os(name) {
+			Some(value) => Ok(value.to_string_lossy().into_owned().into()),
+			None => Ok(args.arg(1).cloned().unwrap_or_default())
+		}
+	}
+
+	/// Sets an environment variable, returning the value.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The variable's name.
+	/// 2. (required, `@text`) Its new value; if it's `null`, the variable is deleted instead.
+	#[instrument(name="Env::set", level="trace", skip(args), fields(?args))]
+	pub fn qs_set(args: Args) -> crate::Result<Object> {
+		let name = var_arg(&args, "Env::set")?;
+		let value = args.try_arg(1)?;
+
+		if name.is_empty() || name.contains('=') || name.contains('\0') {
+			return Err(ValueError::Messaged(format!("invalid environment variable name: {:?}", name)).into());
+		}
+
+		if value.is_a::<Null>() {
+			std::env::remove_var(name);
+		} else {
+			std::env::set_var(name, value.call_downcast::<Text>()?.as_ref());
+		}
+
+		Ok(value.clone())
+	}
+
+	/// Deletes an environment variable, returning its old value or `null` if it wasn't set.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The variable's name.
+	#[instrument(name="Env::delete", level="trace", skip(args), fields(?args))]
+	pub fn qs_delete(args: Args) -> crate::Result<Object> {
+		let name = var_arg(&args, "Env::delete")?;
+		let old = std::env::var_os(&name);
+
+		if old.is_some() {
+			std::env::remove_var(name);
+		}
+
+		Ok(old.map(|old| old.to_string_lossy().into_owned().into()).unwrap_or_default())
+	}
+
+	/// Gets a [`Map`] of every environment variable, sorted by name.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Env::vars().each((name, value) -> { disp(name, "=", value) });
+	/// ```
+	#[instrument(name="Env::vars", level="trace", skip(_args), fields(args=?_args))]
+	pub fn qs_vars(_args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::ENV, "Env::vars")?;
+
+		let mut vars = std::env::vars_os()
+			.map(|(name, value)| (name.to_string_lossy().into_owned(), value.to_string_lossy().into_owned()))
+			.collect::<Vec<_>>();
+		vars.sort();
+
+		let mut map = Map::new();
+
+		for (name, value) in vars {
+			map.insert(name.into(), value.into())?;
+		}
+
+		Ok(map.into())
+	}
+
+	/// Gets the current working directory as a [`Path`].
+	#[instrument(name="Env::cwd", level="trace", skip(_args), fields(args=?_args))]
+	pub fn qs_cwd(_args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::ENV, "Env::cwd")?;
+
+		Ok(Path::from(std::env::current_dir()?).into())
+	}
+
+	/// Changes the current working directory.
+	///
+	/// If a block's given, it's called in the new directory, the old one is restored once it
+	/// returns (or raises an error), and the block's result is returned. Otherwise, the new
+	/// directory is returned.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The directory to change to.
+	/// 2. (optional, `()`) A block to call in the new directory.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Env::chdir("/tmp", {
+	/// 	assert(Env::cwd() == Path("/tmp"));
+	/// });
+	/// ```
+	#[instrument(name="Env::chdir", level="trace", skip(args), fields(?args))]
+	pub fn qs_chdir(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::ENV, "Env::chdir")?;
+
+		let dir = Path::from_object(args.try_arg(0)?)?;
+
+		let block =
+			match args.arg(1) {
+				Some(block) => block,
+				None => {
+					std::env::set_current_dir(&dir)?;
+					return Ok(Path::from(dir).into());
+				}
+			};
+
+		let previous = std::env::current_dir()?;
+		std::env::set_current_dir(&dir)?;
+
+		let result = block.call_attr_lit(&Literal::CALL, &[]);
+		std::env::set_current_dir(previous)?;
+
+		result
+	}
+
+	/// Gets the id of the running process.
+	#[instrument(name="Env::pid", level="trace", skip(_args), fields(args=?_args))]
+	pub fn qs_pid(_args: Args) -> crate::Result<Object> {
+		Ok(std::process::id().into())
+	}
+
+	/// Registers a block to be called when the program exits, returning the block.
+	///
+	/// Blocks are called in the reverse order that they're registered, both when the program
+	/// finishes normally and when `Env::exit` or `quit` are called.
+	///
+	/// # Arguments
+	/// 1. (required, `()`) The block to call.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// tmp = Io::File("scratch.txt", "wc");
+	/// Env::at_exit({ Fs::remove("scratch.txt") });
+	/// ```
+	#[instrument(name="Env::at_exit", level="trace", skip(args), fields(?args))]
+	pub fn qs_at_exit(args: Args) -> crate::Result<Object> {
+		let block = args.try_arg(0)?.clone();
+
+		AT_EXIT.lock().push(block.clone());
+
+		Ok(block)
+	}
+
+	/// Calls the `at_exit` blocks, and then exits the program.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The exit code; defaults to `0`.
+	#[instrument(name="Env::exit", level="trace", skip(args), fields(?args))]
+	pub fn qs_exit(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::EXIT, "Env::exit")?;
+
+		let code =
+			match args.arg(0) {
+				Some(code) => i32::try_from(code.call_downcast::<Number>()?.clone())?,
+				None => 0
+			};
+
+		Self::exit(code)
+	}
+}
+
+impl_object_type!{
+for Env [(parents super::Basic)]:
+	"OptParse" => const OptParse::mapping().clone(),
+	"args" => function Self::qs_args,
+	"script" => function Self::qs_script,
+	"get" => function Self::qs_get,
+	"set" => function Self::qs_set,
+	"delete" => function Self::qs_delete,
+	"vars" => function Self::qs_vars,
+	"cwd" => function Self::qs_cwd,
+	"chdir" => function Self::qs_chdir,
+	"pid" => function Self::qs_pid,
+	"at_exit" => function Self::qs_at_exit,
+	"exit" => function Self::qs_exit,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::RustFn;
+
+	fn text(obj: crate::Result<Object>) -> String {
+		obj.unwrap().downcast::<Text>().unwrap().to_string()
+	}
+
+	#[test]
+	fn vars() {
+		crate::init();
+
+		const NAME: &str = "QUEST_ENV_TEST_VAR";
+
+		assert!(Env::qs_get(args!(NAME)).unwrap().is_a::<Null>());
+		assert_eq!(text(Env::qs_get(args!(NAME, "fallback"))), "fallback");
+
+		Env::qs_set(args!(NAME, "value")).unwrap();
+		assert_eq!(text(Env::qs_get(args!(NAME))), "value");
+		assert!(Env::qs_set(args!("A=B", "value")).is_err());
+
+		let vars = Env::qs_vars(args!()).unwrap();
+		let value = vars.downcast::<Map>().unwrap().get(&NAME.into()).unwrap().cloned();
+		assert_eq!(value.unwrap().downcast::<Text>().unwrap().as_ref(), "value");
+
+		assert_eq!(text(Env::qs_delete(args!(NAME))), "value");
+		assert!(Env::qs_delete(args!(NAME)).unwrap().is_a::<Null>());
+
+		let _guard = crate::sandbox::Sandbox::new().enter();
+		assert!(Env::qs_get(args!(NAME)).is_err());
+	}
+
+	#[test]
+	fn chdir() {
+		crate::init();
+
+		let start = std::env::current_dir().unwrap();
+		let tmp = std::env::temp_dir().canonicalize().unwrap();
+
+		let block = RustFn::function("block", |_| Env::qs_cwd(args!()));
+		let inside = Env::qs_chdir(args!(tmp.display().to_string(), Object::from(block))).unwrap();
+
+		assert_eq!(inside.downcast::<Path>().unwrap().as_path(), tmp);
+		assert_eq!(std::env::current_dir().unwrap(), start);
+	}
+
+	#[test]
+	fn at_exit() {
+		crate::init();
+
+		Env::set_args(vec!["a".to_string(), "b".to_string()]);
+		assert_eq!(Env::args(), ["a", "b"]);
+
+		let failing = RustFn::function("failing", |_| Err(ValueError::Messaged("oops".into()).into()));
+		let setting = RustFn::function("setting", |_| Env::qs_set(args!("QUEST_AT_EXIT_RAN", "yes")));
+
+		Env::qs_at_exit(args!(Object::from(setting))).unwrap();
+		Env::qs_at_exit(args!(Object::from(failing))).unwrap();
+
+		assert!(Env::run_at_exit().is_err());
+		assert_eq!(std::env::var("QUEST_AT_EXIT_RAN").as_deref(), Ok("yes"));
+		assert!(Env::run_at_exit().is_ok());
+
+		// blocks can register more blocks, which are run too.
+		let nesting = RustFn::function("nesting", |_| {
+			let nested = RustFn::function("nested", |_| Env::qs_set(args!("QUEST_AT_EXIT_NESTED", "yes")));
+			Env::qs_at_exit(args!(Object::from(nested)))
+		});
+
+		Env::qs_at_exit(args!(Object::from(nesting))).unwrap();
+		assert!(Env::run_at_exit().is_ok());
+		assert_eq!(std::env::var("QUEST_AT_EXIT_NESTED").as_deref(), Ok("yes"));
+	}
+}
@@ -0,0 +1,446 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Boolean, Null, List, Map};
+use std::fmt::Write;
+use tracing::instrument;
+
+/// The key that positional arguments are stored under in parsed options.
+const ARGS_KEY: &str = "args";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+	Flag,
+	Option { default: Option<String> }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Spec {
+	name: String,
+	short: Option<char>,
+	help: Option<String>,
+	kind: Kind
+}
+
+impl Spec {
+	fn describe(&self) -> String {
+		let mut described =
+			match self.short {
+				Some(short) => format!("-{}, --{}", short, self.name),
+				None => format!("    --{}", self.name)
+			};
+
+		if let Kind::Option { .. } = self.kind {
+			described.push_str(" VALUE");
+		}
+
+		described
+	}
+}
+
+/// The value of a flag or option after parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+	/// Whether a flag was given.
+	Flag(bool),
+	/// What an option was given, or its default if it wasn't.
+	Option(Option<String>)
+}
+
+/// The result of [`OptParse::parse`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Parsed {
+	/// The value of each flag and option, in the order they were defined.
+	pub values: Vec<(String, Value)>,
+	/// Every argument that wasn't a flag or option.
+	pub args: Vec<String>
+}
+
+/// A parser for command line arguments.
+///
+/// Flags and options have a long name (`--verbose`) and optionally a short one (`-v`). Options
+/// take a value, which can be given as `--output out.txt`, `--output=out.txt`, `-o out.txt` or
+/// `-oout.txt`; short flags can be combined, as in `-vq`. Everything after `--` is taken as a
+/// positional argument.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptParse {
+	program: String,
+	description: Option<String>,
+	specs: Vec<Spec>
+}
+
+impl OptParse {
+	/// Creates a new parser for `program`, which is only used in the usage message.
+	pub fn new(program: impl Into<String>) -> Self {
+		Self { program: program.into(), ..Self::default() }
+	}
+
+	/// Sets the description shown in the usage message.
+	pub fn description(mut self, description: impl Into<String>) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+
+	/// Adds a flag, which is either given or not.
+	pub fn flag(mut self, name: impl Into<String>, short: Option<char>, help: Option<String>) -> Self {
+		self.specs.push(Spec { name: name.into(), short, help, kind: Kind::Flag });
+		self
+	}
+
+	/// Adds an option, which takes a value and is `default` if it's not given.
+	pub fn option(mut self, name: impl Into<String>, short: Option<char>, help: Option<String>, default: Option<String>) -> Self {
+		self.specs.push(Spec { name: name.into(), short, help, kind: Kind::Option { default } });
+		self
+	}
+
+	/// Makes sure `name` and `short` can be used for a new flag or option.
+	fn check_names(&self, name: &str, short: Option<char>) -> crate::Result<()> {
+		if name.is_empty() || name.starts_with('-') || name.contains('=') || name == ARGS_KEY {
+			return Err(ValueError::Messaged(format!("invalid option name: {:?}", name)).into());
+		}
+
+		if short.is_some_and(|short| short == '-' || short.is_whitespace()) {
+			return Err(ValueError::Messaged(format!("invalid short option: {:?}", short)).into());
+		}
+
+		if self.specs.iter().any(|spec| spec.name == name || (short.is_some() && spec.short == short)) {
+			return Err(ValueError::Messaged(format!("option {:?} is already defined", name)).into());
+		}
+
+		Ok(())
+	}
+
+	/// Parses `args`, returning an error message if they're invalid.
+	pub fn parse<S: AsRef<str>>(&self, args: &[S]) -> Result<Parsed, String> {
+		let mut parsed = Parsed {
+			values: self.specs.iter()
+				.map(|spec| (spec.name.clone(), match spec.kind {
+					Kind::Flag => Value::Flag(false),
+					Kind::Option { ref default } => Value::Option(default.clone())
+				}))
+				.collect(),
+			args: Vec::new()
+		};
+
+		let mut args = args.iter().map(AsRef::as_ref);
+
+		while let Some(arg) = args.next() {
+			if arg == "--" {
+				parsed.args.extend(args.map(String::from));
+				break;
+			}
+
+			if let Some(long) = arg.strip_prefix("--") {
+				let (name, inline) =
+					match long.split_once('=') {
+						Some((name, value)) => (name, Some(value)),
+						None => (long, None)
+					};
+
+				let idx = self.specs.iter().position(|spec| spec.name == name)
+					.ok_or_else(|| format!("unknown option: --{}", name))?;
+
+				parsed.values[idx].1 =
+					match (&self.specs[idx].kind, inline) {
+						(Kind::Flag, None) => Value::Flag(true),
+						(Kind::Flag, Some(_)) => return Err(format!("--{} doesn't take a value", name)),
+						(Kind::Option { .. }, Some(value)) => Value::Option(Some(value.to_string())),
+						(Kind::Option { .. }, None) => Value::Option(Some(args.next()
+							.ok_or_else(|| format!("--{} needs a value", name))?
+							.to_string()))
+					};
+			} else if arg.len() > 1 && arg.starts_with('-') {
+				for (pos, short) in arg.char_indices().skip(1) {
+					let idx = self.specs.iter().position(|spec| spec.short == Some(short))
+						.ok_or_else(|| format!("unknown option: -{}", short))?;
+
+					if let Kind::Flag = self.specs[idx].kind {
+						parsed.values[idx].1 = Value::Flag(true);
+						continue;
+					}
+
+					// the rest of the argument is the value, or the next one if there isn't any.
+					let rest = &arg[pos + short.len_utf8()..];
+					let value =
+						if rest.is_empty() {
+							args.next().ok_or_else(|| format!("-{} needs a value", short))?
+						} else {
+							rest
+						};
+
+					parsed.values[idx].1 = Value::Option(Some(value.to_string()));
+					break;
+				}
+			} else {
+				parsed.args.push(arg.to_string());
+			}
+		}
+
+		Ok(parsed)
+	}
+
+	/// Gets the usage message, which describes each flag and option.
+	pub fn usage(&self) -> String {
+		let mut usage = format!("usage: {} [options] [args...]\n", self.program);
+
+		if let Some(ref description) = self.description {
+			write!(usage, "\n{}\n", description).unwrap();
+		}
+
+		if self.specs.is_empty() {
+			return usage;
+		}
+
+		usage.push_str("\noptions:\n");
+
+		let described = self.specs.iter().map(Spec::describe).collect::<Vec<_>>();
+		let width = described.iter().map(String::len).max().unwrap_or(0);
+
+		for (spec, described) in self.specs.iter().zip(described) {
+			let mut line = format!("  {:width$}  {}", described, spec.help.as_deref().unwrap_or(""), width = width);
+
+			if let Kind::Option { default: Some(ref default) } = spec.kind {
+				write!(line, " (default: {})", default).unwrap();
+			}
+
+			usage.push_str(line.trim_end());
+			usage.push('\n');
+		}
+
+		usage
+	}
+}
+
+/// Converts an optional argument to text, treating `null` as missing.
+fn optional_text(arg: Option<&Object>) -> crate::Result<Option<String>> {
+	match arg {
+		Some(arg) if !arg.is_a::<Null>() => Ok(Some(arg.call_downcast::<Text>()?.to_string())),
+		_ => Ok(None)
+	}
+}
+
+/// Converts an optional short name, which must be a single character.
+fn short_arg(arg: Option<&Object>) -> crate::Result<Option<char>> {
+	let short =
+		match optional_text(arg)? {
+			Some(short) => short,
+			None => return Ok(None)
+		};
+
+	let mut chars = short.chars();
+
+	match (chars.next(), chars.next()) {
+		(Some(chr), None) => Ok(Some(chr)),
+		_ => Err(ValueError::Messaged(format!("short options must be one character, not {:?}", short)).into())
+	}
+}
+
+/// Quest methods
+impl OptParse {
+	/// Creates a new parser.
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) The program's name, used in the usage message; defaults to the name
+	///    of the script.
+	/// 2. (optional, `@text`) A description of the program, used in the usage message.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// parser = OptParse("greet", "Greets people.")
+	/// 	.flag("loud", "l", "shout the greeting")
+	/// 	.option("greeting", "g", "what to say", "hello");
+	///
+	/// opts = parser.parse(["-l", "--greeting=hi", "world"]);
+	///
+	/// assert(opts.get("loud"));
+	/// assert(opts.get("greeting") == "hi");
+	/// assert(opts.get("args") == ["world"]);
+	/// ```
+	#[instrument(name="OptParse::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let program =
+			match optional_text(args.arg(0))? {
+				Some(program) => program,
+				None => super::Env::script()
+					.and_then(|script| script.file_name().map(|name| name.to_string_lossy().into_owned()))
+					.unwrap_or_else(|| "quest".to_string())
+			};
+
+		let mut parser = Self::new(program);
+		parser.description = optional_text(args.arg(1))?;
+
+		Ok(parser.into())
+	}
+
+	/// Adds a flag, which is `true` if it's given and `false` otherwise, returning the parser.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The long name, without the leading `--`.
+	/// 2. (optional, `@text`) The one-character short name, without the leading `-`; it can be
+	///    `null` to have no short name.
+	/// 3. (optional, `@text`) What the flag does, for the usage message.
+	#[instrument(name="OptParse::flag", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_flag(this: &Object, args: Args) -> crate::Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let short = short_arg(args.arg(1))?;
+		let help = optional_text(args.arg(2))?;
+
+		let mut parser = this.try_downcast_mut::<Self>()?;
+		parser.check_names(&name, short)?;
+		parser.specs.push(Spec { name, short, help, kind: Kind::Flag });
+
+		Ok(this.clone())
+	}
+
+	/// Adds an option, which takes a value, returning the parser.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The long name, without the leading `--`.
+	/// 2. (optional, `@text`) The one-character short name, without the leading `-`; it can be
+	///    `null` to have no short name.
+	/// 3. (optional, `@text`) What the option does, for the usage message.
+	/// 4. (optional, `@text`) The value if the option isn't given; defaults to `null`.
+	#[instrument(name="OptParse::option", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_option(this: &Object, args: Args) -> crate::Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let short = short_arg(args.arg(1))?;
+		let help = optional_text(args.arg(2))?;
+		let default = optional_text(args.arg(3))?;
+
+		let mut parser = this.try_downcast_mut::<Self>()?;
+		parser.check_names(&name, short)?;
+		parser.specs.push(Spec { name, short, help, kind: Kind::Option { default } });
+
+		Ok(this.clone())
+	}
+
+	/// Parses arguments, returning a [`Map`] of each flag and option's value, with the positional
+	/// arguments in a [`List`] under `"args"`.
+	///
+	/// Invalid arguments, such as unknown options, raise a `ValueError`.
+	///
+	/// # Arguments
+	/// 1. (optional, `@list`) The arguments to parse; defaults to `Env::args()`.
+	#[instrument(name="OptParse::parse", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_parse(this: &Object, args: Args) -> crate::Result<Object> {
+		let given =
+			match args.arg(0) {
+				Some(list) => list.call_downcast::<List>()?
+					.iter()
+					.map(|arg| arg.call_downcast::<Text>().map(|text| text.to_string()))
+					.collect::<crate::Result<Vec<_>>>()?,
+				None => super::Env::args()
+			};
+
+		let parsed = this.try_downcast::<Self>()?.parse(&given)
+			.map_err(ValueError::Messaged)?;
+
+		let mut map = Map::new();
+
+		for (name, value) in parsed.values {
+			let value =
+				match value {
+					Value::Flag(given) => Boolean::new(given).into(),
+					Value::Option(value) => value.map(Object::from).unwrap_or_default()
+				};
+
+			map.insert(name.into(), value)?;
+		}
+
+		map.insert(ARGS_KEY.into(), parsed.args.into_iter().map(Object::from).collect::<List>().into())?;
+
+		Ok(map.into())
+	}
+
+	/// Gets the usage message, which describes each flag and option.
+	#[instrument(name="OptParse::usage", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_usage(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.usage().into())
+	}
+}
+
+impl_object_type!{
+for OptParse [(parents crate::types::Basic)]:
+	"()" => method Self::qs_call,
+	"flag" => method Self::qs_flag,
+	"option" => method Self::qs_option,
+	"parse" => method Self::qs_parse,
+	"usage" => method Self::qs_usage,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parser() -> OptParse {
+		OptParse::new("tool")
+			.description("Does things.")
+			.flag("verbose", Some('v'), Some("print more".into()))
+			.flag("quiet", Some('q'), None)
+			.option("output", Some('o'), Some("where to write".into()), Some("out.txt".into()))
+			.option("level", None, None, None)
+	}
+
+	#[test]
+	fn parsing() {
+		let parsed = parser().parse(&["-vo", "a.txt", "input", "--level=3", "--", "--quiet"]).unwrap();
+
+		assert_eq!(parsed.values, [
+			("verbose".to_string(), Value::Flag(true)),
+			("quiet".to_string(), Value::Flag(false)),
+			("output".to_string(), Value::Option(Some("a.txt".into()))),
+			("level".to_string(), Value::Option(Some("3".into())))
+		]);
+		assert_eq!(parsed.args, ["input", "--quiet"]);
+
+		let parsed = parser().parse(&["-qoa.txt", "-", "--level", "-1"]).unwrap();
+		assert_eq!(parsed.values[1].1, Value::Flag(true));
+		assert_eq!(parsed.values[2].1, Value::Option(Some("a.txt".into())));
+		assert_eq!(parsed.values[3].1, Value::Option(Some("-1".into())));
+		assert_eq!(parsed.args, ["-"]);
+
+		let defaults = parser().parse::<&str>(&[]).unwrap();
+		assert_eq!(defaults.values[2].1, Value::Option(Some("out.txt".into())));
+		assert_eq!(defaults.values[3].1, Value::Option(None));
+
+		assert_eq!(parser().parse(&["--nope"]).unwrap_err(), "unknown option: --nope");
+		assert_eq!(parser().parse(&["-x"]).unwrap_err(), "unknown option: -x");
+		assert_eq!(parser().parse(&["--verbose=yes"]).unwrap_err(), "--verbose doesn't take a value");
+		assert_eq!(parser().parse(&["-o"]).unwrap_err(), "-o needs a value");
+	}
+
+	#[test]
+	fn usage() {
+		assert_eq!(parser().usage(), "\
+usage: tool [options] [args...]
+
+Does things.
+
+options:
+  -v, --verbose       print more
+  -q, --quiet
+  -o, --output VALUE  where to write (default: out.txt)
+      --level VALUE
+");
+	}
+
+	#[test]
+	fn quest_methods() {
+		crate::init();
+
+		let parser = OptParse::qs_call(&Object::default(), args!("tool")).unwrap();
+		OptParse::qs_flag(&parser, args!("verbose", "v")).unwrap();
+		assert!(OptParse::qs_flag(&parser, args!("verbose")).is_err());
+		assert!(OptParse::qs_option(&parser, args!("args")).is_err());
+		assert!(OptParse::qs_option(&parser, args!("output", "out")).is_err());
+
+		let given = Object::from(List::from(vec![Object::from("-v"), Object::from("file")]));
+		let opts = OptParse::qs_parse(&parser, args!(given)).unwrap();
+		let opts = opts.downcast::<Map>().unwrap();
+
+		assert!(opts.get(&"verbose".into()).unwrap().unwrap().downcast::<Boolean>().unwrap().into_inner());
+		assert_eq!(opts.get(&"args".into()).unwrap().unwrap().downcast::<List>().unwrap().len(), 1);
+
+		let bad = Object::from(List::from(vec![Object::from("--what")]));
+		assert!(OptParse::qs_parse(&parser, args!(bad)).is_err());
+	}
+}
@@ -0,0 +1,294 @@
+//! Exceptions that can be thrown and caught from within Quest.
+
+use crate::{Object, Args, Literal, Error};
+use crate::types::{Class, Text, List, Frame, ObjectType};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use tracing::instrument;
+
+/// An exception in Quest.
+///
+/// Exceptions are created by calling [`Exception`]'s class (or one of its subclasses, such as
+/// `KeyError`) with a message. Any object can be thrown via `Kernel::throw`, but errors raised from
+/// within Rust are always converted into exceptions before they're handed to `catch` blocks.
+#[derive(Clone)]
+pub struct Exception {
+	kind: Text,
+	message: Text,
+	trace: Vec<Frame>
+}
+
+impl Debug for Exception {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		// we explicitly don't include the trace because it's far too verbose.
+		f.debug_struct("Exception")
+			.field("kind", &self.kind)
+			.field("message", &self.message)
+			.finish()
+	}
+}
+
+impl Display for Exception {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if self.message.is_empty() {
+			Display::fmt(&self.kind, f)
+		} else {
+			write!(f, "{}: {}", self.kind, self.message)
+		}
+	}
+}
+
+/// The builtin subclasses of [`Exception`] that Rust-side errors are converted into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+	/// A generic error with no specific kind, such as [`Error::Messaged`].
+	RuntimeError,
+	/// An [`Error::IoError`].
+	IoError,
+	/// An [`Error::KeyError`].
+	KeyError,
+	/// An [`Error::ArgumentError`].
+	ArgumentError,
+	/// An [`Error::TypeError`].
+	TypeError,
+	/// An [`Error::ValueError`].
+	ValueError,
+	/// An [`Error::AssertionFailed`].
+	AssertionError,
+	/// An [`Error::SandboxError`].
+	SandboxError,
+}
+
+impl Kind {
+	/// Every builtin kind.
+	pub const ALL: [Kind; 8] = [
+		Kind::RuntimeError, Kind::IoError, Kind::KeyError, Kind::ArgumentError, Kind::TypeError,
+		Kind::ValueError, Kind::AssertionError, Kind::SandboxError
+	];
+
+	/// Get the name of this kind.
+	pub fn name(self) -> &'static str {
+		match self {
+			Kind::RuntimeError => "RuntimeError",
+			Kind::IoError => "IoError",
+			Kind::KeyError => "KeyError",
+			Kind::ArgumentError => "ArgumentError",
+			Kind::TypeError => "TypeError",
+			Kind::ValueError => "ValueError",
+			Kind::AssertionError => "AssertionError",
+			Kind::SandboxError => "SandboxError",
+		}
+	}
+
+	/// Get the class that corresponds to this kind; its only parent is [`Exception`].
+	pub fn class(self) -> &'static Object {
+		lazy_static::lazy_static! {
+			static ref CLASSES: HashMap<Kind, Object> = Kind::ALL.iter()
+				.map(|&kind| {
+					let class = Object::new_with_parent(
+						Class::new(kind.name()),
+						vec![Exception::mapping().clone()]
+					);
+
+					class.set_attr_lit(Literal::NAME, kind.name().into())
+						.expect("couldn't set exception name");
+
+					(kind, class)
+				}).collect();
+		}
+
+		&CLASSES[&self]
+	}
+
+	/// Get the kind of exception `err` corresponds to.
+	///
+	/// # Panics
+	/// This will panic if given an [`Error::Return`] or [`Error::Thrown`], as neither should ever be
+	/// converted to exceptions.
+	pub fn of(err: &Error) -> Self {
+		match err {
+			Error::Messaged(_) | Error::Boxed(_) => Kind::RuntimeError,
+			Error::IoError(_) => Kind::IoError,
+			Error::KeyError(_) => Kind::KeyError,
+			Error::ArgumentError(_) => Kind::ArgumentError,
+			Error::TypeError(_) => Kind::TypeError,
+			Error::ValueError(_) => Kind::ValueError,
+			Error::AssertionFailed(_) => Kind::AssertionError,
+			Error::SandboxError(_) => Kind::SandboxError,
+			Error::Traced { error, .. } => Kind::of(error),
+			Error::Return { .. } | Error::Thrown(_) => unreachable!("can't get the kind of {:?}", err)
+		}
+	}
+}
+
+impl Exception {
+	/// Create a new exception, capturing the current stack.
+	pub fn new(kind: impl Into<Text>, message: impl Into<Text>) -> Self {
+		Self::with_trace(kind, message, Frame::capture())
+	}
+
+	/// Create a new exception with the given stack.
+	pub fn with_trace(kind: impl Into<Text>, message: impl Into<Text>, trace: Vec<Frame>) -> Self {
+		Self { kind: kind.into(), message: message.into(), trace }
+	}
+
+	/// Converts a Rust-side error into an exception whose parent is the kind's class.
+	///
+	/// If the error was [traced](Error::traced), the exception uses its stack rather than the
+	/// current one.
+	pub fn from_error(err: &Error) -> Object {
+		let kind = Kind::of(err);
+		let message =
+			match err.untraced() {
+				Error::AssertionFailed(msg) => msg.clone().unwrap_or_default(),
+				other => other.to_string()
+			};
+
+		let exception =
+			if err.trace().is_empty() {
+				Self::new(kind.name(), message)
+			} else {
+				Self::with_trace(kind.name(), message, err.trace().to_vec())
+			};
+
+		Object::new_with_parent(exception, vec![kind.class().clone()])
+	}
+
+	/// The kind of exception this is; this is the name of the class that created it.
+	#[inline]
+	pub fn kind(&self) -> &Text {
+		&self.kind
+	}
+
+	/// The message associated with this exception.
+	#[inline]
+	pub fn message(&self) -> &Text {
+		&self.message
+	}
+
+	/// The stack at the time this exception was created, with the most recent frame first.
+	#[inline]
+	pub fn trace(&self) -> &[Frame] {
+		&self.trace
+	}
+}
+
+/// Quest methods
+impl Exception {
+	/// Creates a new exception whose parent is `this`.
+	///
+	/// # Arguments
+	///
+	/// 1. (optional, `@text`) The exception's message. Defaults to an empty message.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// err = KeyError("oops");
+	///
+	/// assert(err.message() == "oops");
+	/// assert(err.kind() == "KeyError");
+	/// ```
+	#[instrument(name="Exception::()", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_call(this: &Object, args: Args) -> crate::Result<Object> {
+		let kind = this.get_attr_lit(&Literal::NAME)?.call_downcast::<Text>()?.clone();
+		let message = args.arg(0)
+			.map(|msg| msg.call_downcast::<Text>().map(|msg| msg.clone()))
+			.transpose()?
+			.unwrap_or_default();
+
+		Ok(Object::new_with_parent(Self::new(kind, message), vec![this.clone()]))
+	}
+
+	/// Get the exception's message.
+	#[instrument(name="Exception::message", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_message(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.message.clone().into())
+	}
+
+	/// Get the name of the class the exception was created from.
+	#[instrument(name="Exception::kind", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_kind(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.kind.clone().into())
+	}
+
+	/// Get a [`List`] of the stackframes that were active when the exception was created.
+	#[instrument(name="Exception::stack", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stack(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?
+			.trace
+			.iter()
+			.map(|frame| Object::from(frame.binding().clone()))
+			.collect::<List>()
+			.into())
+	}
+
+	/// Get a [`List`] of [`Frame`]s, which includes where each stackframe's block was defined.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// trace = try({ throw(Exception("oops")) }, err -> { err.trace() });
+	///
+	/// assert(trace.get(0).lineno() == 1);
+	/// ```
+	#[instrument(name="Exception::trace", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_trace(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(List::from(this.try_downcast::<Self>()?.trace.clone()).into())
+	}
+
+	/// Converts the exception to `kind: message`, or just `kind` if there's no message.
+	#[instrument(name="Exception::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+
+	#[instrument(name="Exception::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("{}({:?})", this.kind, this.message.as_ref()).into())
+	}
+}
+
+impl_object_type!{
+for Exception [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"message" => method Self::qs_message,
+	"kind" => method Self::qs_kind,
+	"stack" => method Self::qs_stack,
+	"trace" => method Self::qs_trace,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::KeyError;
+
+	#[test]
+	fn from_error() {
+		crate::init();
+
+		let err = Error::from(KeyError::OutOfBounds { idx: 3, len: 1 });
+		let exception = Exception::from_error(&err);
+
+		assert!(exception.is_descendant_of(Kind::KeyError.class()).unwrap());
+		assert!(exception.is_descendant_of(Exception::mapping()).unwrap());
+		assert!(!exception.is_descendant_of(Kind::TypeError.class()).unwrap());
+
+		let exception = exception.downcast::<Exception>().unwrap();
+		assert_eq!(*exception.kind(), *"KeyError");
+		assert_eq!(*exception.message(), *err.to_string());
+	}
+
+	#[test]
+	fn assertion_message() {
+		crate::init();
+
+		let exception = Exception::from_error(&Error::AssertionFailed(Some("bad".into())));
+		assert_eq!(exception.downcast::<Exception>().unwrap().to_string(), "AssertionError: bad");
+
+		let exception = Exception::from_error(&Error::AssertionFailed(None));
+		assert_eq!(exception.downcast::<Exception>().unwrap().to_string(), "AssertionError");
+	}
+}
@@ -0,0 +1,224 @@
+//! Stackframes and the locations in source code that they were created at.
+
+use crate::{Object, Args, Literal, Binding};
+use crate::types::{Text, List, Number};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::path::PathBuf;
+use tracing::instrument;
+
+/// A location within Quest source code.
+///
+/// Blocks store one of these on their stackframe under [`Literal::SOURCE_LOCATION`] when they're
+/// called, which is then used to build up [`Frame`]s when an error occurs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SourceLocation {
+	/// The file, if one exists, that this location is in.
+	pub file: Option<PathBuf>,
+	/// The line number of this location.
+	pub lineno: usize,
+	/// The column within [`line`](#structfield.line) of this location.
+	pub column: usize,
+	/// The source code of the line this location is on.
+	pub line: String
+}
+
+impl SourceLocation {
+	/// The name of the file, or `<eval>` if there's no file associated with this location.
+	pub fn file_name(&self) -> String {
+		self.file.as_ref()
+			.map(|file| file.display().to_string())
+			.unwrap_or_else(|| "<eval>".to_string())
+	}
+
+	/// Writes the source line with a `^` under the column, in the same style as parse errors.
+	pub fn fmt_snippet(&self, f: &mut Formatter) -> fmt::Result {
+		const TAB_REPLACEMENT: &str = "  ";
+
+		// replace tabs with a standardized representation for error messages
+		let mut line = self.line.clone();
+		let mut column = self.column;
+		while let Some(tab_pos) = line.find('\t') {
+			line.replace_range(tab_pos..=tab_pos, TAB_REPLACEMENT);
+			column += TAB_REPLACEMENT.len() - 1;
+		}
+
+		write!(f, concat!("    |",
+					 "\n {lineno:<3}| {context}",
+					 "\n    |{padding}^ here"),
+			lineno=self.lineno,
+			context=line.trim_end(),
+			padding=" ".repeat(column))
+	}
+}
+
+impl Display for SourceLocation {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}:{}:{}", self.file_name(), self.lineno, self.column)
+	}
+}
+
+/// A single stackframe, along with the location of the block that created it.
+///
+/// Frames that weren't created by a block (such as the main frame, or ones created from within
+/// Rust) don't have a location.
+#[derive(Clone)]
+pub struct Frame {
+	binding: Binding,
+	location: Option<SourceLocation>
+}
+
+impl Debug for Frame {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		// we explicitly don't include the binding because it can cause infinite regression.
+		f.debug_struct("Frame")
+			.field("location", &self.location)
+			.finish()
+	}
+}
+
+impl Display for Frame {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self.location {
+			Some(ref location) => Display::fmt(location, f),
+			None => write!(f, "<unknown>")
+		}
+	}
+}
+
+impl From<Binding> for Frame {
+	fn from(binding: Binding) -> Self {
+		let location = binding.get_own_attr_lit(&Literal::SOURCE_LOCATION)
+			.and_then(|location| location.downcast::<SourceLocation>().map(|loc| loc.clone()));
+
+		Self { binding, location }
+	}
+}
+
+impl Frame {
+	/// Gets every frame on the current stack, with the most recent first.
+	pub fn capture() -> Vec<Self> {
+		Binding::stack().into_iter().map(Self::from).collect()
+	}
+
+	/// The stackframe itself.
+	#[inline]
+	pub fn binding(&self) -> &Binding {
+		&self.binding
+	}
+
+	/// Where the block that created this frame was defined, if it's known.
+	#[inline]
+	pub fn location(&self) -> Option<&SourceLocation> {
+		self.location.as_ref()
+	}
+}
+
+/// Quest methods
+impl SourceLocation {
+	/// Converts the location to `file:lineno:column`.
+	#[instrument(name="SourceLocation::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+}
+
+/// Quest methods
+impl Frame {
+	/// Get the stackframe's binding.
+	#[instrument(name="Frame::binding", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_binding(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.binding.clone().into())
+	}
+
+	/// Get the name of the file the frame's block was defined in, or `null` if it's unknown.
+	#[instrument(name="Frame::file", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_file(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.location
+			.as_ref()
+			.map(|location| Object::from(location.file_name()))
+			.unwrap_or_default())
+	}
+
+	/// Get the line the frame's block was defined on, or `null` if it's unknown.
+	#[instrument(name="Frame::lineno", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_lineno(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.location
+			.as_ref()
+			.map(|location| Object::from(Number::from(location.lineno)))
+			.unwrap_or_default())
+	}
+
+	/// Get the column the frame's block was defined at, or `null` if it's unknown.
+	#[instrument(name="Frame::column", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_column(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.location
+			.as_ref()
+			.map(|location| Object::from(Number::from(location.column)))
+			.unwrap_or_default())
+	}
+
+	/// Get the source code of the line the frame's block was defined on, or `null` if it's unknown.
+	#[instrument(name="Frame::line", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_line(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.location
+			.as_ref()
+			.map(|location| Object::from(Text::from(location.line.trim_end().to_string())))
+			.unwrap_or_default())
+	}
+
+	/// Converts the frame to `file:lineno:column`, or `<unknown>` if there's no location.
+	#[instrument(name="Frame::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+
+	#[instrument(name="Frame::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("Frame({})", *this.try_downcast::<Self>()?).into())
+	}
+}
+
+impl From<Vec<Frame>> for List {
+	fn from(frames: Vec<Frame>) -> Self {
+		frames.into_iter().map(Object::from).collect()
+	}
+}
+
+impl_object_type!{
+for SourceLocation [(parents super::Basic)]:
+	"@text" => method Self::qs_at_text,
+}
+
+impl_object_type!{
+for Frame [(parents super::Basic)]:
+	"binding" => method Self::qs_binding,
+	"file" => method Self::qs_file,
+	"lineno" => method Self::qs_lineno,
+	"column" => method Self::qs_column,
+	"line" => method Self::qs_line,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn location_from_own_attr() {
+		crate::init();
+
+		let location = SourceLocation { file: Some("foo.qs".into()), lineno: 3, column: 4, line: "x".into() };
+		let binding = Binding::from(Object::new(crate::types::Scope));
+		binding.set_attr_lit(Literal::SOURCE_LOCATION, location.clone().into()).unwrap();
+
+		let frame = Frame::from(binding.clone());
+		assert_eq!(frame.location(), Some(&location));
+		assert_eq!(frame.to_string(), "foo.qs:3:4");
+
+		// child frames shouldn't inherit their parent's location.
+		let child = Object::new(crate::types::Scope);
+		child.add_parent(binding.as_ref().clone()).unwrap();
+		assert_eq!(Frame::from(Binding::from(child)).location(), None);
+	}
+}
@@ -0,0 +1,275 @@
+//! Working with the filesystem.
+
+mod stat;
+pub mod glob;
+
+pub use stat::{Stat, Kind};
+
+use crate::{Object, Args};
+use crate::sandbox::{self, Capability};
+use crate::types::{Boolean, Null, List, Path};
+use crate::types::io::Dir;
+use std::fs;
+use tracing::instrument;
+
+/// The namespace for working with files, directories and their metadata.
+///
+/// Every function that takes a path accepts either a [`Path`] or anything with a `@text`. Problems
+/// with the filesystem, such as a file not existing, raise an `IoError`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fs;
+
+/// Gets the path at `idx`, making sure the filesystem can be accessed first.
+fn path_arg(args: &Args, idx: usize, func: &'static str) -> crate::Result<std::path::PathBuf> {
+	sandbox::require(Capability::FILESYSTEM, func)?;
+
+	Path::from_object(args.try_arg(idx)?)
+}
+
+impl Fs {
+	/// Checks to see if something exists at a path, following symlinks.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The path to check.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Fs::exists?("/"));
+	/// assert(!Fs::exists?("/does/not/exist"));
+	/// ```
+	#[instrument(name="Fs::exists?", level="trace", skip(args), fields(?args))]
+	pub fn qs_exists_q(args: Args) -> crate::Result<Object> {
+		let path = path_arg(&args, 0, "Fs::exists?")?;
+
+		Ok(Boolean::new(path.exists()).into())
+	}
+
+	/// Checks to see if a path is a file, following symlinks.
+	#[instrument(name="Fs::file?", level="trace", skip(args), fields(?args))]
+	pub fn qs_file_q(args: Args) -> crate::Result<Object> {
+		let path = path_arg(&args, 0, "Fs::file?")?;
+
+		Ok(Boolean::new(path.is_file()).into())
+	}
+
+	/// Checks to see if a path is a directory, following symlinks.
+	#[instrument(name="Fs::dir?", level="trace", skip(args), fields(?args))]
+	pub fn qs_dir_q(args: Args) -> crate::Result<Object> {
+		let path = path_arg(&args, 0, "Fs::dir?")?;
+
+		Ok(Boolean::new(path.is_dir()).into())
+	}
+
+	/// Gets information about a path, such as its size and when it was modified.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The path.
+	/// 2. (optional, `@bool`) Whether to follow symlinks; defaults to `true`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// stat = Fs::stat("/");
+	///
+	/// assert(stat.dir?());
+	/// assert(stat.kind() == "dir");
+	/// ```
+	#[instrument(name="Fs::stat", level="trace", skip(args), fields(?args))]
+	pub fn qs_stat(args: Args) -> crate::Result<Object> {
+		let path = path_arg(&args, 0, "Fs::stat")?;
+		let follow = args.arg(1).map_or(Ok(true), |follow| follow.call_downcast::<Boolean>().map(|b| b.into_inner()))?;
+
+		let metadata = if follow { fs::metadata(path)? } else { fs::symlink_metadata(path)? };
+
+		Ok(Stat::from(metadata).into())
+	}
+
+	/// Removes a file or directory.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The path to remove.
+	/// 2. (optional, `@bool`) Whether to remove a directory's contents too; without it, only empty
+	///    directories can be removed.
+	#[instrument(name="Fs::remove", level="trace", skip(args), fields(?args))]
+	pub fn qs_remove(args: Args) -> crate::Result<Object> {
+		let path = path_arg(&args, 0, "Fs::remove")?;
+		let recursive = args.arg(1).map_or(Ok(false), |recursive| recursive.call_downcast::<Boolean>().map(|b| b.into_inner()))?;
+
+		if !fs::symlink_metadata(&path)?.is_dir() {
+			fs::remove_file(path)?;
+		} else if recursive {
+			fs::remove_dir_all(path)?;
+		} else {
+			fs::remove_dir(path)?;
+		}
+
+		Ok(Null.into())
+	}
+
+	/// Moves a file or directory, replacing the destination if it's a file, returning the new
+	/// [`Path`].
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The path to move.
+	/// 2. (required, `@text`) Where to move it to.
+	#[instrument(name="Fs::rename", level="trace", skip(args), fields(?args))]
+	pub fn qs_rename(args: Args) -> crate::Result<Object> {
+		let from = path_arg(&args, 0, "Fs::rename")?;
+		let to = path_arg(&args, 1, "Fs::rename")?;
+
+		fs::rename(from, &to)?;
+
+		Ok(Path::from(to).into())
+	}
+
+	/// Copies a file, replacing the destination if it exists, returning how many bytes were
+	/// copied.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The file to copy.
+	/// 2. (required, `@text`) Where to copy it to.
+	#[instrument(name="Fs::copy", level="trace", skip(args), fields(?args))]
+	pub fn qs_copy(args: Args) -> crate::Result<Object> {
+		let from = path_arg(&args, 0, "Fs::copy")?;
+		let to = path_arg(&args, 1, "Fs::copy")?;
+
+		Ok(fs::copy(from, to)?.into())
+	}
+
+	/// Finds every path that matches a pattern, returning a sorted [`List`] of [`Path`]s.
+	///
+	/// Within each part of the pattern, `*` matches any amount of characters, `?` matches exactly
+	/// one, `[abc]` and `[a-z]` match one of the given characters (or one that isn't, if they start
+	/// with `!`), and `\` escapes the next character. A part that's just `**` matches any amount of
+	/// directories. Wildcards don't match hidden files unless the part starts with a `.` too.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The pattern.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Fs::glob("src/**/*.rs").each(path -> { disp(path) });
+	/// ```
+	#[instrument(name="Fs::glob", level="trace", skip(args), fields(?args))]
+	pub fn qs_glob(args: Args) -> crate::Result<Object> {
+		let pattern = path_arg(&args, 0, "Fs::glob")?;
+
+		Ok(glob::glob(&pattern.to_string_lossy())
+			.into_iter()
+			.map(|path| Object::from(Path::from(path)))
+			.collect::<List>()
+			.into())
+	}
+}
+
+impl_object_type!{
+for Fs [(parents super::Basic)]:
+	"Stat" => const Stat::mapping().clone(),
+	"exists?" => function Self::qs_exists_q,
+	"file?" => function Self::qs_file_q,
+	"dir?" => function Self::qs_dir_q,
+	"stat" => function Self::qs_stat,
+	"remove" => function Self::qs_remove,
+	"rename" => function Self::qs_rename,
+	"copy" => function Self::qs_copy,
+	"glob" => function Self::qs_glob,
+	"list" => function Dir::qs_list,
+	"walk" => function Dir::qs_walk,
+	"mkdir" => function Dir::qs_mkdir,
+	"mkdir_p" => function Dir::qs_mkdir_p,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Iter;
+	use std::path::PathBuf;
+
+	/// A directory that's removed once the test is over.
+	struct TempDir(PathBuf);
+
+	impl TempDir {
+		fn new(name: &str) -> Self {
+			let dir = std::env::temp_dir().join(format!("quest-fs-{}-{}", std::process::id(), name));
+			let _ = fs::remove_dir_all(&dir);
+			fs::create_dir_all(&dir).unwrap();
+			Self(dir)
+		}
+
+		fn path(&self, path: &str) -> String {
+			self.0.join(path).display().to_string()
+		}
+	}
+
+	impl Drop for TempDir {
+		fn drop(&mut self) {
+			let _ = fs::remove_dir_all(&self.0);
+		}
+	}
+
+	fn truthy(result: crate::Result<Object>) -> bool {
+		result.unwrap().downcast::<Boolean>().unwrap().into_inner()
+	}
+
+	fn paths(obj: Object, root: &TempDir) -> Vec<String> {
+		let list = obj.call_downcast::<List>().unwrap().clone();
+
+		list.iter()
+			.map(|path| path.downcast::<Path>().unwrap().as_path()
+				.strip_prefix(&root.0).unwrap().display().to_string())
+			.collect()
+	}
+
+	#[test]
+	fn files_and_dirs() {
+		crate::init();
+		let tmp = TempDir::new("files_and_dirs");
+
+		Dir::qs_mkdir_p(args!(tmp.path("a/b/c"))).unwrap();
+		fs::write(tmp.path("a/one.txt"), "hello").unwrap();
+
+		assert!(truthy(Fs::qs_exists_q(args!(tmp.path("a/one.txt")))));
+		assert!(truthy(Fs::qs_dir_q(args!(tmp.path("a/b")))));
+		assert!(!truthy(Fs::qs_file_q(args!(tmp.path("a/b")))));
+
+		let stat = Fs::qs_stat(args!(tmp.path("a/one.txt"))).unwrap();
+		let stat = stat.downcast::<Stat>().unwrap();
+		assert_eq!(stat.size(), 5);
+		assert_eq!(stat.kind(), Kind::File);
+		assert!(stat.mtime().is_some());
+
+		let copied = Fs::qs_copy(args!(tmp.path("a/one.txt"), tmp.path("a/two.txt"))).unwrap();
+		assert_eq!(*copied.downcast::<crate::types::Number>().unwrap(), crate::types::Number::from(5));
+		Fs::qs_rename(args!(tmp.path("a/two.txt"), tmp.path("a/b/two.txt"))).unwrap();
+		assert_eq!(fs::read_to_string(tmp.path("a/b/two.txt")).unwrap(), "hello");
+
+		let walked = Dir::qs_walk(args!(tmp.path("a"))).unwrap()
+			.call_downcast::<Iter>().unwrap().clone()
+			.collect::<crate::Result<Vec<_>>>().unwrap();
+		assert_eq!(paths(List::from(walked).into(), &tmp), ["a/b", "a/b/c", "a/b/two.txt", "a/one.txt"]);
+
+		assert!(Fs::qs_remove(args!(tmp.path("a/b"))).is_err());
+		Fs::qs_remove(args!(tmp.path("a/b"), true)).unwrap();
+		assert!(!truthy(Fs::qs_exists_q(args!(tmp.path("a/b")))));
+		assert!(Dir::qs_mkdir(args!(tmp.path("x/y"))).is_err());
+	}
+
+	#[test]
+	fn globbing() {
+		crate::init();
+		let tmp = TempDir::new("globbing");
+
+		fs::create_dir_all(tmp.path("src/nested/.hidden")).unwrap();
+		for file in &["src/lib.rs", "src/main.rs", "src/nested/mod.rs", "src/nested/.hidden/x.rs", "src/notes.txt", "src/.dot.rs"] {
+			fs::write(tmp.path(file), "").unwrap();
+		}
+
+		let glob = |pattern: &str| paths(Fs::qs_glob(args!(tmp.path(pattern))).unwrap(), &tmp);
+
+		assert_eq!(glob("src/*.rs"), ["src/lib.rs", "src/main.rs"]);
+		assert_eq!(glob("src/**/*.rs"), ["src/lib.rs", "src/main.rs", "src/nested/mod.rs"]);
+		assert_eq!(glob("src/.*.rs"), ["src/.dot.rs"]);
+		assert_eq!(glob("src/[lm]*.r?"), ["src/lib.rs", "src/main.rs"]);
+		assert_eq!(glob("src/nested"), ["src/nested"]);
+		assert!(glob("src/missing/*").is_empty());
+	}
+}
@@ -0,0 +1,170 @@
+//! Finding paths that match glob patterns, such as `src/**/*.rs`.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Checks to see if `chr` is special within glob patterns.
+fn is_special(chr: char) -> bool {
+	matches!(chr, '*' | '?' | '[' | '\\')
+}
+
+/// Parses the character class at the start of `pattern` (just after its `[`), returning whether
+/// it matches `chr` and the pattern after the class.
+///
+/// `None` is returned if the class is never closed, in which case the `[` is taken literally.
+fn match_class(pattern: &[char], chr: char) -> Option<(bool, &[char])> {
+	let (negated, mut rest) =
+		match pattern.split_first() {
+			Some(('!', rest)) | Some(('^', rest)) => (true, rest),
+			_ => (false, pattern)
+		};
+
+	let mut matched = false;
+	let mut first = true;
+
+	loop {
+		match rest {
+			[']', after @ ..] if !first => return Some((matched != negated, after)),
+			[start, '-', end, after @ ..] if *end != ']' => {
+				matched |= (*start..=*end).contains(&chr);
+				rest = after;
+			},
+			[lit, after @ ..] => {
+				matched |= *lit == chr;
+				rest = after;
+			},
+			[] => return None
+		}
+
+		first = false;
+	}
+}
+
+fn matches_chars(pattern: &[char], name: &[char]) -> bool {
+	match pattern.split_first() {
+		None => name.is_empty(),
+		Some(('*', rest)) => (0..=name.len()).any(|skip| matches_chars(rest, &name[skip..])),
+		Some(('?', rest)) => !name.is_empty() && matches_chars(rest, &name[1..]),
+		Some(('[', rest)) => match name.split_first() {
+			Some((&chr, name_rest)) => match match_class(rest, chr) {
+				Some((matched, after)) => matched && matches_chars(after, name_rest),
+				// an unclosed `[` is just a normal character.
+				None => chr == '[' && matches_chars(rest, name_rest)
+			},
+			None => false
+		},
+		Some(('\\', [escaped, rest @ ..])) => name.first() == Some(escaped) && matches_chars(rest, &name[1..]),
+		Some((chr, rest)) => name.first() == Some(chr) && matches_chars(rest, &name[1..])
+	}
+}
+
+/// Checks to see if `name` matches `pattern`.
+///
+/// `*` matches any amount of characters, `?` matches exactly one, `[abc]` and `[a-z]` match one
+/// of the given characters (or one that isn't, if they start with `!` or `^`), and `\` escapes the
+/// next character.
+pub fn matches(pattern: &str, name: &str) -> bool {
+	let pattern = pattern.chars().collect::<Vec<_>>();
+	let name = name.chars().collect::<Vec<_>>();
+
+	matches_chars(&pattern, &name)
+}
+
+/// Reads the names of the entries of `dir`, or nothing if it can't be read.
+fn entry_names(dir: &Path) -> Vec<String> {
+	let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+
+	std::fs::read_dir(dir)
+		.map(|entries| entries
+			.filter_map(Result::ok)
+			.map(|entry| entry.file_name().to_string_lossy().into_owned())
+			.collect())
+		.unwrap_or_default()
+}
+
+fn is_dir(path: &Path) -> bool {
+	let path = if path.as_os_str().is_empty() { Path::new(".") } else { path };
+
+	std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// Adds `dir` and every directory within it to `dirs`, skipping hidden ones.
+fn add_subdirs(dir: PathBuf, dirs: &mut Vec<PathBuf>) {
+	let names = entry_names(&dir);
+	dirs.push(dir.clone());
+
+	for name in names {
+		let path = dir.join(&name);
+
+		if !name.starts_with('.') && is_dir(&path) {
+			add_subdirs(path, dirs);
+		}
+	}
+}
+
+/// Finds every path that matches `pattern`, in sorted order.
+///
+/// Each part of the pattern is matched with [`matches`], and a part that's just `**` matches any
+/// amount of directories. Wildcards don't match names starting with `.` unless the part of the
+/// pattern does too.
+pub fn glob(pattern: &str) -> Vec<PathBuf> {
+	let mut paths = vec![PathBuf::new()];
+
+	for component in Path::new(pattern).components() {
+		let part =
+			match component {
+				Component::Normal(part) => part.to_string_lossy(),
+				other => {
+					paths.iter_mut().for_each(|path| path.push(other));
+					continue;
+				}
+			};
+
+		if part == "**" {
+			let mut dirs = Vec::new();
+
+			for path in paths.into_iter().filter(|path| is_dir(path)) {
+				add_subdirs(path, &mut dirs);
+			}
+
+			paths = dirs;
+		} else if part.chars().any(is_special) {
+			let mut matched = Vec::new();
+
+			for path in paths {
+				for name in entry_names(&path) {
+					if (!name.starts_with('.') || part.starts_with('.')) && matches(&part, &name) {
+						matched.push(path.join(name));
+					}
+				}
+			}
+
+			paths = matched;
+		} else {
+			paths.iter_mut().for_each(|path| path.push(&*part));
+		}
+	}
+
+	paths.retain(|path| !path.as_os_str().is_empty() && std::fs::symlink_metadata(path).is_ok());
+	paths.sort();
+	paths.dedup();
+	paths
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matching() {
+		assert!(matches("*.rs", "lib.rs"));
+		assert!(!matches("*.rs", "lib.rsx"));
+		assert!(matches("l?b.*", "lib.rs"));
+		assert!(matches("[a-c]at", "bat"));
+		assert!(!matches("[!a-c]at", "bat"));
+		assert!(matches("[]]", "]"));
+		assert!(matches("[abc", "[abc"));
+		assert!(matches("\\*", "*"));
+		assert!(!matches("\\*", "a"));
+		assert!(matches("*", ""));
+	}
+}
@@ -0,0 +1,166 @@
+use crate::{Object, Args};
+use crate::types::{Boolean, Null};
+use std::fs::Metadata;
+use std::time::UNIX_EPOCH;
+use tracing::instrument;
+
+/// What kind of thing a path refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+	File,
+	Dir,
+	Symlink,
+	Other
+}
+
+impl Kind {
+	/// Gets the name of this kind, as returned by `Stat::kind`.
+	pub fn name(self) -> &'static str {
+		match self {
+			Kind::File => "file",
+			Kind::Dir => "dir",
+			Kind::Symlink => "symlink",
+			Kind::Other => "other"
+		}
+	}
+}
+
+/// Information about a path on the filesystem, as returned from `Fs::stat`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stat {
+	size: u64,
+	mtime: Option<f64>,
+	permissions: u32,
+	readonly: bool,
+	kind: Kind
+}
+
+impl From<Metadata> for Stat {
+	fn from(metadata: Metadata) -> Self {
+		let file_type = metadata.file_type();
+		let kind =
+			if file_type.is_symlink() {
+				Kind::Symlink
+			} else if file_type.is_dir() {
+				Kind::Dir
+			} else if file_type.is_file() {
+				Kind::File
+			} else {
+				Kind::Other
+			};
+
+		#[cfg(unix)]
+		let permissions = std::os::unix::fs::PermissionsExt::mode(&metadata.permissions()) & 0o7777;
+
+		// without unix modes, approximate them with the read-only flag.
+		#[cfg(not(unix))]
+		let permissions = if metadata.permissions().readonly() { 0o444 } else { 0o666 };
+
+		Self {
+			size: metadata.len(),
+			mtime: metadata.modified().ok()
+				.and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+				.map(|since| since.as_secs_f64()),
+			permissions,
+			readonly: metadata.permissions().readonly(),
+			kind
+		}
+	}
+}
+
+impl Stat {
+	/// The size, in bytes.
+	#[inline]
+	pub fn size(&self) -> u64 {
+		self.size
+	}
+
+	/// When the path was last modified, in seconds since the unix epoch.
+	#[inline]
+	pub fn mtime(&self) -> Option<f64> {
+		self.mtime
+	}
+
+	/// The permission bits, such as `0o644`.
+	#[inline]
+	pub fn permissions(&self) -> u32 {
+		self.permissions
+	}
+
+	/// What the path refers to.
+	#[inline]
+	pub fn kind(&self) -> Kind {
+		self.kind
+	}
+}
+
+impl Stat {
+	/// The size, in bytes.
+	#[instrument(name="Stat::size", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_size(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.size.into())
+	}
+
+	/// When the path was last modified, in seconds since the unix epoch, or `null` if that isn't
+	/// available.
+	#[instrument(name="Stat::mtime", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_mtime(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.mtime.map_or_else(|| Null.into(), Object::from))
+	}
+
+	/// The permission bits, such as `0o644`.
+	#[instrument(name="Stat::permissions", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_permissions(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.permissions.into())
+	}
+
+	/// Checks to see if the path is read-only.
+	#[instrument(name="Stat::readonly?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_readonly_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::new(this.try_downcast::<Self>()?.readonly).into())
+	}
+
+	/// What the path refers to: one of `"file"`, `"dir"`, `"symlink"` or `"other"`.
+	#[instrument(name="Stat::kind", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_kind(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.kind.name().into())
+	}
+
+	/// Checks to see if the path is a file.
+	#[instrument(name="Stat::file?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_file_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::new(this.try_downcast::<Self>()?.kind == Kind::File).into())
+	}
+
+	/// Checks to see if the path is a directory.
+	#[instrument(name="Stat::dir?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_dir_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::new(this.try_downcast::<Self>()?.kind == Kind::Dir).into())
+	}
+
+	/// Checks to see if the path is a symlink; this is only ever true if links weren't followed.
+	#[instrument(name="Stat::symlink?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_symlink_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::new(this.try_downcast::<Self>()?.kind == Kind::Symlink).into())
+	}
+
+	#[instrument(name="Stat::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("Stat({}, {} bytes, {:o})", this.kind.name(), this.size, this.permissions).into())
+	}
+}
+
+impl_object_type!{
+for Stat [(parents crate::types::Basic)]:
+	"size" => method Self::qs_size,
+	"mtime" => method Self::qs_mtime,
+	"permissions" => method Self::qs_permissions,
+	"readonly?" => method Self::qs_readonly_q,
+	"kind" => method Self::qs_kind,
+	"file?" => method Self::qs_file_q,
+	"dir?" => method Self::qs_dir_q,
+	"symlink?" => method Self::qs_symlink_q,
+	"inspect" => method Self::qs_inspect,
+}
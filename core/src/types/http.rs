@@ -0,0 +1,275 @@
+//! Making HTTP requests.
+
+mod response;
+
+pub use response::Response;
+
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Map, Null, Bytes};
+use std::io;
+use std::time::Duration;
+use tracing::instrument;
+
+/// The namespace for making HTTP requests.
+///
+/// Requests that can't be made, such as when the host can't be reached or the connection times
+/// out, raise an `IoError`; malformed URLs raise a `ValueError`. Responses with error statuses,
+/// such as `404`, are returned normally.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Http;
+
+/// The options for a request, aside from its method and URL.
+#[derive(Debug, Default)]
+pub struct Request {
+	/// Headers to send, in order.
+	pub headers: Vec<(String, String)>,
+	/// The body to send, if any.
+	pub body: Option<Vec<u8>>,
+	/// How long the entire request may take, or `None` to wait forever.
+	pub timeout: Option<Duration>
+}
+
+/// Converts an error that `ureq` encountered into an [`Error`](crate::Error).
+fn convert_error(err: &ureq::Error) -> crate::Error {
+	match err {
+		ureq::Error::BadUrl(_) | ureq::Error::UnknownScheme(_)
+			=> ValueError::Messaged(err.to_string()).into(),
+		ureq::Error::Io(err) => io::Error::new(err.kind(), err.to_string()).into(),
+		ureq::Error::ConnectionFailed(_)
+			=> io::Error::new(io::ErrorKind::ConnectionRefused, err.to_string()).into(),
+		_ => io::Error::other(err.to_string()).into()
+	}
+}
+
+impl Http {
+	/// Sends a `method` request to `url`, waiting for the entire response.
+	pub fn request(method: &str, url: &str, options: &Request) -> crate::Result<Response> {
+		crate::sandbox::require(crate::sandbox::Capability::NETWORK, "Http::request")?;
+
+		let mut request = ureq::request(&method.to_ascii_uppercase(), url);
+
+		for (name, value) in &options.headers {
+			request.set(name, value);
+		}
+
+		if let Some(timeout) = options.timeout {
+			request.timeout(timeout);
+		}
+
+		let response =
+			match options.body {
+				Some(ref body) => request.send_bytes(body),
+				None => request.call()
+			};
+
+		if let Some(err) = response.synthetic_error() {
+			return Err(convert_error(err));
+		}
+
+		Response::read_from(response)
+	}
+}
+
+/// Quest methods
+impl Http {
+	/// Parses the optional headers, body, and timeout arguments starting at `start`.
+	fn request_options(args: &Args, start: usize) -> crate::Result<Request> {
+		let given = |idx| args.arg(idx).filter(|arg| !arg.is_a::<Null>());
+		let mut options = Request::default();
+
+		if let Some(headers) = given(start) {
+			for (name, value) in headers.call_downcast::<Map>()?.iter() {
+				let name = name.call_downcast::<Text>()?.to_string();
+				let value = value.call_downcast::<Text>()?.to_string();
+				options.headers.push((name, value));
+			}
+		}
+
+		if let Some(body) = given(start + 1) {
+			options.body = Some(Bytes::data_of(body)?);
+		}
+
+		if let Some(timeout) = args.arg(start + 2) {
+			options.timeout = super::net::timeout(timeout)?;
+		}
+
+		Ok(options)
+	}
+
+	/// Sends a request, returning the [`Response`].
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The method, such as `"GET"`; it's case insensitive.
+	/// 2. (required, `@text`) The URL.
+	/// 3. (optional, `@map`) Headers to send.
+	/// 4. (optional, `@text` or `Bytes`) The body to send.
+	/// 5. (optional, `@num`) How long to wait, in seconds, before raising an `IoError`.
+	///
+	/// Any of the optional arguments can be `null` to skip it.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// response = Http::request("GET", "http://example.com", Map::new(["Accept", "text/html"]));
+	///
+	/// assert(response.status() == 200);
+	/// ```
+	#[instrument(name="Http::request", level="trace", skip(args), fields(?args))]
+	pub fn qs_request(args: Args) -> crate::Result<Object> {
+		let method = args.try_arg(0)?.call_downcast::<Text>()?.clone();
+		let url = args.try_arg(1)?.call_downcast::<Text>()?.clone();
+		let options = Self::request_options(&args, 2)?;
+
+		Ok(Self::request(method.as_ref(), url.as_ref(), &options)?.into())
+	}
+
+	/// Sends a `method` request, taking the same arguments as [`qs_request`](Self::qs_request)
+	/// without the method.
+	fn request_with(method: &str, args: Args) -> crate::Result<Object> {
+		let url = args.try_arg(0)?.call_downcast::<Text>()?.clone();
+		let options = Self::request_options(&args, 1)?;
+
+		Ok(Self::request(method, url.as_ref(), &options)?.into())
+	}
+
+	/// Sends a `GET` request; see `Http::request` for the arguments after the URL.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// response = Http::get("http://example.com");
+	///
+	/// assert(response.ok?());
+	/// assert(response.text().len() > 0);
+	/// ```
+	#[instrument(name="Http::get", level="trace", skip(args), fields(?args))]
+	pub fn qs_get(args: Args) -> crate::Result<Object> {
+		Self::request_with("GET", args)
+	}
+
+	/// Sends a `POST` request; see `Http::request` for the arguments after the URL.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// headers = Map::new(["Content-Type", "application/json"]);
+	/// response = Http::post("http://example.com/users", headers, '{"name": "sam"}');
+	/// ```
+	#[instrument(name="Http::post", level="trace", skip(args), fields(?args))]
+	pub fn qs_post(args: Args) -> crate::Result<Object> {
+		Self::request_with("POST", args)
+	}
+
+	/// Sends a `PUT` request; see `Http::request` for the arguments after the URL.
+	#[instrument(name="Http::put", level="trace", skip(args), fields(?args))]
+	pub fn qs_put(args: Args) -> crate::Result<Object> {
+		Self::request_with("PUT", args)
+	}
+
+	/// Sends a `DELETE` request; see `Http::request` for the arguments after the URL.
+	#[instrument(name="Http::delete", level="trace", skip(args), fields(?args))]
+	pub fn qs_delete(args: Args) -> crate::Result<Object> {
+		Self::request_with("DELETE", args)
+	}
+}
+
+impl_object_type!{
+for Http [(parents super::Basic)]:
+	"Response" => const Response::mapping().clone(),
+	"request" => function Self::qs_request,
+	"get" => function Self::qs_get,
+	"post" => function Self::qs_post,
+	"put" => function Self::qs_put,
+	"delete" => function Self::qs_delete,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{BufRead, BufReader, Read, Write};
+	use std::net::TcpListener;
+	use std::thread::JoinHandle;
+
+	/// Serves a single request on loopback with `response`, returning the URL to request and a
+	/// handle that yields the raw request that was received.
+	fn serve_once(response: &'static str) -> (String, JoinHandle<String>) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let url = format!("http://{}/path", listener.local_addr().unwrap());
+
+		let handle = std::thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut reader = BufReader::new(stream.try_clone().unwrap());
+			let mut request = String::new();
+			let mut content_length = 0;
+
+			loop {
+				let mut line = String::new();
+				reader.read_line(&mut line).unwrap();
+
+				if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+					content_length = len.trim().parse().unwrap();
+				}
+
+				request.push_str(&line);
+
+				if line == "\r\n" {
+					break;
+				}
+			}
+
+			let mut body = vec![0; content_length];
+			reader.read_exact(&mut body).unwrap();
+			request.push_str(&String::from_utf8(body).unwrap());
+
+			(&stream).write_all(response.as_bytes()).unwrap();
+			request
+		});
+
+		(url, handle)
+	}
+
+	#[test]
+	fn get() {
+		let (url, server) = serve_once(
+			"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nX-Thing: a\r\nConnection: close\r\n\r\nhello");
+
+		let response = Http::request("get", &url, &Request::default()).unwrap();
+		let request = server.join().unwrap();
+
+		assert!(request.starts_with("GET /path HTTP/1.1\r\n"));
+		assert_eq!(response.status(), 200);
+		assert_eq!(response.body(), b"hello");
+		assert_eq!(response.header("x-thing"), Some("a"));
+	}
+
+	#[test]
+	fn post_with_headers_and_body() {
+		let (url, server) = serve_once(
+			"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+
+		let options = Request {
+			headers: vec![("X-Custom".into(), "yes".into())],
+			body: Some(b"data".to_vec()),
+			timeout: Some(Duration::from_secs(5))
+		};
+
+		let response = Http::request("POST", &url, &options).unwrap();
+		let request = server.join().unwrap();
+
+		assert!(request.starts_with("POST /path HTTP/1.1\r\n"));
+		assert!(request.contains("X-Custom: yes\r\n"));
+		assert!(request.ends_with("\r\n\r\ndata"));
+		assert_eq!(response.status(), 404);
+		assert!(response.body().is_empty());
+	}
+
+	#[test]
+	fn failures_are_errors() {
+		crate::init();
+
+		// nothing's listening on a port that was just freed.
+		let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+		let url = format!("http://127.0.0.1:{}/", port);
+
+		assert_matches!(Http::qs_get(args!(url)).unwrap_err(), crate::Error::IoError(..));
+		assert_matches!(Http::qs_get(args!("nope://x")).unwrap_err(), crate::Error::ValueError(..));
+	}
+}
@@ -0,0 +1,149 @@
+use crate::{Object, Args};
+use crate::types::{Text, Map, Number, Bytes};
+use std::io::Read;
+use tracing::instrument;
+
+/// The response to an HTTP request.
+#[derive(Debug, Clone)]
+pub struct Response {
+	status: u16,
+	status_text: String,
+	headers: Vec<(String, String)>,
+	body: Vec<u8>
+}
+
+impl Response {
+	/// Reads the entirety of a `ureq` response.
+	pub(super) fn read_from(response: ureq::Response) -> crate::Result<Self> {
+		let status = response.status();
+		let status_text = response.status_text().to_string();
+		let mut headers = Vec::<(String, String)>::new();
+
+		// header names are lowercased, and repeated headers are combined into one.
+		for name in response.headers_names() {
+			if !headers.iter().any(|(seen, _)| *seen == name) {
+				let value = response.all(&name).join(", ");
+				headers.push((name, value));
+			}
+		}
+
+		let mut body = Vec::new();
+		response.into_reader().read_to_end(&mut body)?;
+
+		Ok(Self { status, status_text, headers, body })
+	}
+
+	/// The status code, such as `200`.
+	pub fn status(&self) -> u16 {
+		self.status
+	}
+
+	/// The status text, such as `"OK"`.
+	pub fn status_text(&self) -> &str {
+		&self.status_text
+	}
+
+	/// Checks to see if the status is a success, ie in `200..=299`.
+	pub fn is_ok(&self) -> bool {
+		(200..=299).contains(&self.status)
+	}
+
+	/// The headers, with lowercased names.
+	pub fn headers(&self) -> &[(String, String)] {
+		&self.headers
+	}
+
+	/// Gets a header by its name, ignoring case.
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers.iter()
+			.find(|(key, _)| key.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// The body of the response.
+	pub fn body(&self) -> &[u8] {
+		&self.body
+	}
+}
+
+/// Quest methods
+impl Response {
+	/// Gets the status code.
+	#[instrument(name="Http::Response::status", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_status(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Number::from(this.try_downcast::<Self>()?.status()).into())
+	}
+
+	/// Gets the status text, such as `"Not Found"`.
+	#[instrument(name="Http::Response::status_text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_status_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.status_text().to_string().into())
+	}
+
+	/// Checks to see if the status is between `200` and `299`.
+	#[instrument(name="Http::Response::ok?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_ok_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_ok().into())
+	}
+
+	/// Gets a [`Map`] of the headers; names are lowercased.
+	#[instrument(name="Http::Response::headers", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_headers(this: &Object, _: Args) -> crate::Result<Object> {
+		let mut map = Map::new();
+
+		for (name, value) in this.try_downcast::<Self>()?.headers() {
+			map.insert(name.clone().into(), value.clone().into())?;
+		}
+
+		Ok(map.into())
+	}
+
+	/// Gets a header by name, ignoring case, or `null` if it wasn't sent.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The header's name.
+	#[instrument(name="Http::Response::header", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_header(this: &Object, args: Args) -> crate::Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?.clone();
+
+		Ok(this.try_downcast::<Self>()?
+			.header(name.as_ref())
+			.map(|value| Object::from(value.to_string()))
+			.unwrap_or_default())
+	}
+
+	/// Gets the body as [`Text`]; anything that isn't valid UTF-8 is replaced with `U+FFFD`.
+	#[instrument(name="Http::Response::text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_text(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(String::from_utf8_lossy(this.body()).into_owned().into())
+	}
+
+	/// Gets the body as [`Bytes`].
+	#[instrument(name="Http::Response::bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Bytes::from(this.body()).into())
+	}
+
+	#[instrument(name="Http::Response::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("Response({} {})", this.status(), this.status_text()).into())
+	}
+}
+
+impl_object_type!{
+for Response [(parents crate::types::Basic)]:
+	"status" => method Self::qs_status,
+	"status_text" => method Self::qs_status_text,
+	"ok?" => method Self::qs_ok_q,
+	"headers" => method Self::qs_headers,
+	"header" => method Self::qs_header,
+	"text" => method Self::qs_text,
+	"bytes" => method Self::qs_bytes,
+	"inspect" => method Self::qs_inspect,
+}
@@ -1,6 +1,8 @@
 mod file;
+mod dir;
 
 pub use file::File;
+pub use dir::Dir;
 
 use crate::{Object, Args, Literal};
 use crate::types::Null;
@@ -36,6 +38,7 @@ impl Io {
 impl_object_type!{
 for Io [(parents super::Iterable)]:
 	"File" => const file::File::mapping().clone(),
+	"Dir" => const dir::Dir::mapping().clone(),
 	"Stdin" => const file::File::from_fd(0, true, false).expect("cant create stdin"),
 	"Stdout" => const file::File::from_fd(1, false, true).expect("cant create stdout"),
 	"Stderr" => const file::File::from_fd(2, false, true).expect("cant create stdout"),
@@ -0,0 +1,122 @@
+use crate::{Object, Args};
+use crate::sandbox::{self, Capability};
+use crate::types::{Iter, Path};
+use std::io;
+use std::path::PathBuf;
+use tracing::instrument;
+
+/// The namespace for working with directories, accessible via `Io::Dir`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dir;
+
+/// Gets the optional directory at `idx`, defaulting to the current one.
+fn dir_arg(args: &Args, idx: usize) -> crate::Result<PathBuf> {
+	args.arg(idx).map_or_else(|| Ok(PathBuf::from(".")), Path::from_object)
+}
+
+impl Dir {
+	/// Gets the entries of `dir`, sorted by name.
+	pub fn entries(dir: &std::path::Path) -> io::Result<Vec<PathBuf>> {
+		let mut entries = std::fs::read_dir(dir)?
+			.map(|entry| entry.map(|entry| entry.path()))
+			.collect::<io::Result<Vec<_>>>()?;
+
+		entries.sort();
+		Ok(entries)
+	}
+
+	/// Iterates over every path within `root`, depth first.
+	///
+	/// Directories come before their contents, and symlinks to directories aren't followed.
+	pub fn walk(root: &std::path::Path) -> io::Result<Iter> {
+		let mut pending = Self::entries(root)?;
+		pending.reverse();
+
+		Ok(Iter::new(std::iter::from_fn(move || {
+			let path = pending.pop()?;
+
+			if std::fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_dir()) {
+				match Self::entries(&path) {
+					Ok(entries) => pending.extend(entries.into_iter().rev()),
+					Err(err) => return Some(Err(err.into()))
+				}
+			}
+
+			Some(Ok(Path::from(path).into()))
+		})))
+	}
+
+	/// Gets an [`Iter`] of the [`Path`]s within a directory, sorted by name.
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) The directory; defaults to the current one.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io::Dir::list("src").each(path -> { disp(path.basename()) });
+	/// ```
+	#[instrument(name="Dir::list", level="trace", skip(args), fields(?args))]
+	pub fn qs_list(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::FILESYSTEM, "Dir::list")?;
+
+		let entries = Self::entries(&dir_arg(&args, 0)?)?;
+
+		Ok(Iter::with_objects(entries.into_iter().map(Path::from).map(Object::from).collect::<Vec<_>>()).into())
+	}
+
+	/// Gets an [`Iter`] of every [`Path`] within a directory and its subdirectories.
+	///
+	/// Subdirectories are only read once they're reached, and come before their contents.
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) The directory; defaults to the current one.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// sources = Io::Dir::walk("src").select(path -> { path.extension() == "qs" });
+	/// ```
+	#[instrument(name="Dir::walk", level="trace", skip(args), fields(?args))]
+	pub fn qs_walk(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::FILESYSTEM, "Dir::walk")?;
+
+		Ok(Self::walk(&dir_arg(&args, 0)?)?.into())
+	}
+
+	/// Creates a directory, whose parent must already exist, returning its [`Path`].
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The directory to create.
+	#[instrument(name="Dir::mkdir", level="trace", skip(args), fields(?args))]
+	pub fn qs_mkdir(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::FILESYSTEM, "Dir::mkdir")?;
+
+		let dir = Path::from_object(args.try_arg(0)?)?;
+		std::fs::create_dir(&dir)?;
+
+		Ok(Path::from(dir).into())
+	}
+
+	/// Creates a directory and any missing parents, returning its [`Path`].
+	///
+	/// Nothing happens if the directory already exists.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The directory to create.
+	#[instrument(name="Dir::mkdir_p", level="trace", skip(args), fields(?args))]
+	pub fn qs_mkdir_p(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::FILESYSTEM, "Dir::mkdir_p")?;
+
+		let dir = Path::from_object(args.try_arg(0)?)?;
+		std::fs::create_dir_all(&dir)?;
+
+		Ok(Path::from(dir).into())
+	}
+}
+
+impl_object_type!{
+for Dir [(parents crate::types::Basic)]:
+	"list" => function Self::qs_list,
+	"walk" => function Self::qs_walk,
+	"mkdir" => function Self::qs_mkdir,
+	"mkdir_p" => function Self::qs_mkdir_p,
+}
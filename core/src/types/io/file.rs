@@ -1,12 +1,12 @@
 #![allow(unused)]
 use crate::{Object, Args, Literal};
 use crate::error::ValueError;
-use crate::types::{Text, Number, Null, Regex};
+use crate::types::{Text, Number, Null, Regex, Bytes};
 use tracing::instrument;
 use parking_lot::Mutex;
 use std::convert::TryFrom;
 use std::sync::Arc;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fmt::{self, Display, Formatter};
 use std::fs;
 use std::io::{self, Read, Write, Seek, SeekFrom, BufReader, BufRead};
@@ -14,7 +14,8 @@ use std::io::{self, Read, Write, Seek, SeekFrom, BufReader, BufRead};
 // is an `Arc<Mutex>` really the best way to do this
 #[derive(Debug)]
 pub struct File {
-	file: Option<BufReader<fs::File>>
+	file: Option<BufReader<fs::File>>,
+	path: Option<PathBuf>
 }
 
 impl Clone for File {
@@ -22,7 +23,18 @@ impl Clone for File {
 		Self { 
 			file: self.file.as_ref()
 				.map(|x| x.get_ref().try_clone().expect("unable to clone file"))
-				.map(BufReader::new)
+				.map(BufReader::new),
+			path: self.path.clone()
+		}
+	}
+}
+
+impl Display for File {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match (&self.path, &self.file) {
+			(_, None) => write!(f, "File(closed)"),
+			(Some(path), Some(_)) => write!(f, "File({:?})", path.display().to_string()),
+			(None, Some(file)) => write!(f, "File({:?})", file.get_ref())
 		}
 	}
 }
@@ -40,14 +52,79 @@ impl File {
 		self.file.take();
 	}
 
-	pub fn write(&mut self, what: &[u8]) -> io::Result<()> {
-		if let Some(ref mut file) = self.file {
-			file.get_mut().write(what);
+	/// Checks to see if the file's been closed.
+	#[inline]
+	pub fn is_closed(&self) -> bool {
+		self.file.is_none()
+	}
+
+	fn inner(&mut self) -> io::Result<&mut BufReader<fs::File>> {
+		self.file.as_mut().ok_or_else(|| io::Error::other("file is closed"))
+	}
+
+	/// Throws away anything that's been read ahead, moving the underlying file back to where
+	/// reading left off.
+	fn discard_buffer(&mut self) -> io::Result<()> {
+		let file = self.inner()?;
+
+		// only seek when there's something to discard, as stdio usually can't be seeked. (unlike
+		// `stream_position`, seeking is what makes `BufReader` throw its buffer away.)
+		#[allow(clippy::seek_from_current)]
+		if !file.buffer().is_empty() {
+			file.seek(SeekFrom::Current(0))?;
 		}
 
 		Ok(())
 	}
 
+	/// Writes all of `what` at the current position.
+	pub fn write(&mut self, what: &[u8]) -> io::Result<()> {
+		self.discard_buffer()?;
+		self.inner()?.get_mut().write_all(what)
+	}
+
+	/// Flushes anything that's been written to the file.
+	pub fn flush(&mut self) -> io::Result<()> {
+		self.inner()?.get_mut().flush()
+	}
+
+	/// Moves to `pos`, returning the new position from the start of the file.
+	pub fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+		self.inner()?.seek(pos)
+	}
+
+	/// Gets the current position from the start of the file.
+	pub fn tell(&mut self) -> io::Result<u64> {
+		self.inner()?.stream_position()
+	}
+
+	/// Checks to see if there's nothing left to read.
+	pub fn is_eof(&mut self) -> io::Result<bool> {
+		Ok(self.inner()?.fill_buf()?.is_empty())
+	}
+
+	/// Gets the size of the file, in bytes.
+	pub fn size(&mut self) -> io::Result<u64> {
+		Ok(self.inner()?.get_ref().metadata()?.len())
+	}
+
+	/// Truncates (or extends) the file to `len` bytes, without changing the current position.
+	pub fn truncate(&mut self, len: u64) -> io::Result<()> {
+		self.discard_buffer()?;
+		self.inner()?.get_ref().set_len(len)
+	}
+
+	/// Reads the next line, including the trailing `\n`, or `None` at EOF.
+	pub fn read_line(&mut self) -> io::Result<Option<String>> {
+		let mut line = String::new();
+
+		if self.inner()?.read_line(&mut line)? == 0 {
+			Ok(None)
+		} else {
+			Ok(Some(line))
+		}
+	}
+
 	pub fn read_all(&mut self) -> io::Result<Option<String>> {
 		let mut file = 
 			if let Some(ref mut file) = self.file {
@@ -78,6 +155,27 @@ impl File {
 		Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
 	}
 
+	/// Reads `amnt` bytes, or everything if it's `None`, without any UTF-8 checks.
+	///
+	/// Fewer bytes are returned if EOF is encountered first.
+	pub fn read_bytes(&mut self, amnt: Option<usize>) -> io::Result<Option<Vec<u8>>> {
+		let file =
+			if let Some(ref mut file) = self.file {
+				file
+			} else {
+				return Ok(None);
+			};
+
+		let mut buf = Vec::new();
+
+		match amnt {
+			Some(amnt) => file.take(amnt as u64).read_to_end(&mut buf)?,
+			None => file.read_to_end(&mut buf)?
+		};
+
+		Ok(Some(buf))
+	}
+
 	// note that if EOF is encountered before the sentinel is hit, we just return everything.
 	pub fn read_until_sentinel(&mut self, sentinel: &str) -> io::Result<Option<String>> {
 		if sentinel.is_empty() {
@@ -163,7 +261,7 @@ impl File {
 impl From<fs::File> for File {
 	#[inline]
 	fn from(file: fs::File) -> Self {
-		Self { file: Some(BufReader::new(file)) }
+		Self { file: Some(BufReader::new(file)), path: None }
 	}
 }
 
@@ -254,7 +352,10 @@ impl OpenOptions {
 
 	#[inline]
 	pub fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<File> {
-		self.0.open(path).map(File::from)
+		let mut file = File::from(self.0.open(path.as_ref())?);
+		file.path = Some(path.as_ref().to_path_buf());
+
+		Ok(file)
 	}
 
 	pub fn open_fd(&self, fd: i32) -> io::Result<File> {
@@ -283,6 +384,7 @@ impl TryFrom<&str> for OpenOptions {
 	/// - `w` Sets [`write`](OpenOptions::write).
 	/// - `a` Sets [`append`](OpenOptions::append).
 	/// - `t` Sets [`truncate`](OpenOptions::truncate).
+	/// - `c` Sets [`create`](OpenOptions::create).
 	/// - `n` Sets [`create_new`](OpenOptions::create_new).
 	/// Any other character will yield an [`InvalidOptionChar`].
 	///
@@ -296,6 +398,7 @@ impl TryFrom<&str> for OpenOptions {
 				'w' | 'W' => opts.write(opt == 'w'),
 				't' | 'T' => opts.truncate(opt == 't'),
 				'r' | 'R' => opts.read(opt == 'r'),
+				'c' | 'C' => opts.create(opt == 'c'),
 				'n' | 'N' => opts.create_new(opt == 'n'),
 				invalid => return Err(InvalidOptionChar(invalid))
 			};
@@ -306,28 +409,86 @@ impl TryFrom<&str> for OpenOptions {
 }
 
 impl File {
-	#[instrument(name="File::call", level="trace", skip(args), fields(args=?args))]
-	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
-		let filename = args.try_arg(0)?;
+	/// Opens `filename` with the given mode, or the default one if it's omitted.
+	///
+	/// `filename` can also be a [`Number`], in which case that file descriptor is opened.
+	fn open_with(filename: &Object, mode: Option<&Object>) -> crate::Result<Self> {
 		let openopts =
-			if let Some(arg) = args.arg(1) {
-				OpenOptions::try_from(arg.call_downcast::<Text>()?.as_ref())
+			if let Some(mode) = mode {
+				OpenOptions::try_from(mode.call_downcast::<Text>()?.as_ref())
 					.map_err(|err| ValueError::Messaged(err.to_string()))?
 			} else {
 				OpenOptions::default()
 			};
 
-		let file = 
-			if let Some(fd) = filename.downcast::<Number>() {
-				openopts.open_fd(i32::try_from(*fd)?)?
-			} else {
-				openopts.open(filename.call_downcast::<Text>()?.as_ref())?
+		if let Some(fd) = filename.downcast::<Number>() {
+			Ok(openopts.open_fd(i32::try_from(fd.clone())?)?)
+		} else {
+			Ok(openopts.open(crate::types::Path::from_object(filename)?)?)
+		}
+	}
+
+	/// Opens a file, and passes it to a block if one's given; used by `File.open` and
+	/// `Kernel::open`.
+	///
+	/// When there's a block, the file is closed once it returns, even if it raised an error,
+	/// and the block's result is returned.
+	pub(crate) fn open_block(args: Args, func: &'static str) -> crate::Result<Object> {
+		crate::sandbox::require(crate::sandbox::Capability::FILESYSTEM, func)?;
+
+		let (mode, block) =
+			match (args.arg(1), args.arg(2)) {
+				(mode, Some(block)) => (mode, Some(block)),
+				(Some(block), None) if !block.is_a::<Text>() => (None, Some(block)),
+				(mode, None) => (mode, None)
 			};
 
-		Ok(file.into())
+		let file = Object::from(Self::open_with(args.try_arg(0)?, mode)?);
+
+		let block =
+			match block {
+				Some(block) => block,
+				None => return Ok(file)
+			};
+
+		let result = block.call_attr_lit(&Literal::CALL, &[&file]);
+		file.try_downcast_mut::<Self>()?.close();
+
+		result
+	}
+
+	#[instrument(name="File::call", level="trace", skip(args), fields(args=?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		crate::sandbox::require(crate::sandbox::Capability::FILESYSTEM, "Io::File")?;
+
+		Ok(Self::open_with(args.try_arg(0)?, args.arg(1))?.into())
+	}
+
+	/// Opens a file, optionally passing it to a block that it's closed after.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The file to open.
+	/// 2. (optional, `@text`) The mode to open it with, such as `"r"` or `"wt"`.
+	/// 3. (optional, `()`) A block to call with the file. If given, the file's closed once the
+	///    block returns (or raises an error), and the block's result is returned instead. If the
+	///    second argument isn't [`Text`], it's used as the block.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// contents = Io::File.open("README.md", "r", f -> { f.read() });
+	/// ```
+	#[instrument(name="File::open", level="trace", skip(args), fields(args=?args))]
+	pub fn qs_open(_: &Object, args: Args) -> crate::Result<Object> {
+		Self::open_block(args, "Io::File.open")
+	}
+
+	/// Gets a description of the file, eg `File("README.md")`.
+	#[instrument(name="File::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
 	}
 
-	#[instrument(name="File::open", level="trace", skip(this, args), fields(self=?this, args=?args))]
+	#[instrument(name="File::read", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_read(this: &Object, args: Args) -> crate::Result<Object> {
 		let mut this = this.try_downcast_mut::<Self>()?;
 
@@ -364,35 +525,299 @@ impl File {
 		})
 	}
 
+	/// Reads bytes from the file, returning `null` at EOF.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The maximum amount of bytes to read; if omitted, everything is read.
+	#[instrument(name="File::read_bytes", level="trace", skip(this, args), fields(self=?this, args=?args))]
+	pub fn qs_read_bytes(this: &Object, args: Args) -> crate::Result<Object> {
+		let amnt = args.arg(0).map(crate::types::net::byte_count).transpose()?;
+
+		let read = this.try_downcast_mut::<Self>()?.read_bytes(amnt)?;
+
+		Ok(read.filter(|bytes| !bytes.is_empty())
+			.map(|bytes| Bytes::from(bytes).into())
+			.unwrap_or_default())
+	}
+
+	/// Reads the next line, including the trailing `\n`, returning `null` at EOF.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// file = Io::File("README.md");
+	/// disp("the first line is:", file.read_line());
+	/// ```
+	#[instrument(name="File::read_line", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_read_line(this: &Object, _: Args) -> crate::Result<Object> {
+		let line = this.try_downcast_mut::<Self>()?.read_line()?;
+
+		Ok(line.map(Object::from).unwrap_or_default())
+	}
+
+	/// Gets an [`Iter`](crate::types::Iter) over the remaining lines of the file, without their
+	/// line endings.
+	///
+	/// Lines are only read as the iterator's advanced.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io::File("README.md").each_line().each(line -> { disp(line) });
+	/// ```
+	#[instrument(name="File::each_line", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_each_line(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast::<Self>()?;
+
+		let file = this.clone();
+		let mut done = false;
+
+		Ok(crate::types::Iter::new(std::iter::from_fn(move || {
+			if done {
+				return None;
+			}
+
+			let line = file.try_downcast_mut::<Self>()
+				.and_then(|mut file| file.read_line().map_err(From::from))
+				.transpose();
+
+			done = !matches!(line, Some(Ok(_)));
+			line.map(|line| line.map(|mut line| {
+				if line.ends_with('\n') {
+					line.pop();
+
+					if line.ends_with('\r') {
+						line.pop();
+					}
+				}
+
+				Object::from(line)
+			}))
+		})).into())
+	}
+
+	/// Writes [`Bytes`] as-is, or anything else as UTF-8 text, returning the file.
+	///
+	/// Writes happen at the current position, which is just after whatever's been read.
 	#[instrument(name="File::write", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_write(this: &Object, args: Args) -> crate::Result<Object> {
-		let to_write = args.try_arg(0)?.call_downcast::<Text>()?;
+		let to_write = Bytes::data_of(args.try_arg(0)?)?;
 
-		if let Some(ref mut file) = this.try_downcast_mut::<Self>()?.file {
-			file.get_mut().write_all(to_write.as_ref().as_ref())?;
-		}
+		this.try_downcast_mut::<Self>()?.write(&to_write)?;
 
 		Ok(this.clone())
 	}
 
+	/// Flushes anything that's been written, returning the file.
+	#[instrument(name="File::flush", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_flush(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast_mut::<Self>()?.flush()?;
+
+		Ok(this.clone())
+	}
+
+	/// Moves to a different position in the file, returning the new position from its start.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The offset to move to.
+	/// 2. (optional, `@text`) What the offset's relative to: `"start"` (the default), `"current"`
+	///    or `"end"`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// file = Io::File("README.md");
+	/// file.seek(-5, "end");
+	/// assert(file.read().length() == 5);
+	/// ```
+	#[instrument(name="File::seek", level="trace", skip(this, args), fields(self=?this, args=?args))]
+	pub fn qs_seek(this: &Object, args: Args) -> crate::Result<Object> {
+		let offset = i64::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
+		let whence = args.arg(1)
+			.map(|whence| whence.call_downcast::<Text>().map(|text| text.as_ref().to_string()))
+			.transpose()?;
+
+		let pos =
+			match whence.as_deref() {
+				None | Some("start") => SeekFrom::Start(u64::try_from(offset)
+					.map_err(|_| ValueError::Messaged(format!("can't seek to {} from the start", offset)))?),
+				Some("current") => SeekFrom::Current(offset),
+				Some("end") => SeekFrom::End(offset),
+				Some(other) => return Err(ValueError::Messaged(format!("unknown seek position: {:?}", other)).into())
+			};
+
+		Ok(this.try_downcast_mut::<Self>()?.seek(pos)?.into())
+	}
+
+	/// Gets the current position from the start of the file.
+	#[instrument(name="File::tell", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_tell(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast_mut::<Self>()?.tell()?.into())
+	}
+
+	/// Moves back to the start of the file, returning the file.
+	#[instrument(name="File::rewind", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_rewind(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast_mut::<Self>()?.seek(SeekFrom::Start(0))?;
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if there's nothing left to read.
+	#[instrument(name="File::eof?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_eof_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast_mut::<Self>()?.is_eof()?.into())
+	}
+
+	/// Truncates (or extends) the file, returning the file.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The new length; defaults to the current position.
+	#[instrument(name="File::truncate", level="trace", skip(this, args), fields(self=?this, args=?args))]
+	pub fn qs_truncate(this: &Object, args: Args) -> crate::Result<Object> {
+		let len = args.arg(0).map(crate::types::net::byte_count).transpose()?;
+		let mut file = this.try_downcast_mut::<Self>()?;
+
+		let len =
+			match len {
+				Some(len) => len as u64,
+				None => file.tell()?
+			};
+
+		file.truncate(len)?;
+
+		Ok(this.clone())
+	}
+
+	/// Gets the size of the file, in bytes.
+	#[instrument(name="File::size", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_size(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast_mut::<Self>()?.size()?.into())
+	}
+
 	#[instrument(name="File::close", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_close(this: &Object, _: Args) -> crate::Result<Object> {
 		this.try_downcast_mut::<Self>()?.close();
 
 		Ok(this.clone())
 	}
+
+	/// Checks to see if the file's been closed.
+	#[instrument(name="File::closed?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_closed_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_closed().into())
+	}
 }
 
 impl_object_type!{
 for File [(parents super::Io)]:
 	"()" => method Self::qs_call,
-	"@text" => method |_, _| panic!(),
+	"open" => method Self::qs_open,
+	"@text" => method Self::qs_at_text,
 	"read" => method Self::qs_read,
 	"lines" => method |this, args| {
 		Self::qs_read(this, args)?
 			.call_attr_lit("split", &[&Text::from("\n").into()])
 	},
+	"read_line" => method Self::qs_read_line,
+	"each_line" => method Self::qs_each_line,
+	"read_bytes" => method Self::qs_read_bytes,
 	"write" => method Self::qs_write,
+	"flush" => method Self::qs_flush,
+	"seek" => method Self::qs_seek,
+	"tell" => method Self::qs_tell,
+	"rewind" => method Self::qs_rewind,
+	"eof?" => method Self::qs_eof_q,
+	"truncate" => method Self::qs_truncate,
+	"size" => method Self::qs_size,
 	"close" => method Self::qs_close,
-	// "close" => method Self::qs_close
+	"closed?" => method Self::qs_closed_q,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Boolean, RustFn};
+
+	fn temp_file(name: &str, contents: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("quest-file-{}-{}", std::process::id(), name));
+		fs::write(&path, contents).unwrap();
+		path
+	}
+
+	#[test]
+	fn seeking() {
+		crate::init();
+		let path = temp_file("seeking", "hello\r\nworld\n");
+		let file = Object::from(OpenOptions::default().open(&path).unwrap());
+
+		assert_call_eq!(File::qs_read_line(file.clone()) -> Text, *"hello\r\n");
+		assert_call_eq!(File::qs_tell(file.clone()) -> Number, 7);
+		assert_call_eq!(File::qs_size(file.clone()) -> Number, 13);
+		assert_call_eq!(File::qs_seek(file.clone(), -3, "end") -> Number, 10);
+		assert_call_eq!(File::qs_read_line(file.clone()) -> Text, *"ld\n");
+		assert_call_eq!(File::qs_eof_q(file.clone()) -> Boolean, true);
+		assert_call!(File::qs_read_line(file.clone()) -> Null; |_| true);
+		assert!(File::qs_seek(&file, args!(-1)).is_err());
+		assert!(File::qs_seek(&file, args!(0, "middle")).is_err());
+
+		File::qs_rewind(&file, args!()).unwrap();
+		let lines = File::qs_each_line(&file, args!()).unwrap()
+			.call_downcast::<crate::types::Iter>().unwrap().clone()
+			.collect::<crate::Result<Vec<_>>>().unwrap()
+			.into_iter()
+			.map(|line| line.downcast::<Text>().unwrap().to_string())
+			.collect::<Vec<_>>();
+		assert_eq!(lines, ["hello", "world"]);
+
+		File::qs_close(&file, args!()).unwrap();
+		assert_call_eq!(File::qs_closed_q(file.clone()) -> Boolean, true);
+		assert_call_eq!(File::qs_at_text(file.clone()) -> Text, *"File(closed)");
+		assert!(File::qs_tell(&file, args!()).is_err());
+		assert!(File::qs_write(&file, args!("x")).is_err());
+
+		fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn writing() {
+		crate::init();
+		let path = temp_file("writing", "abcdef");
+		let file = Object::from(OpenOptions::try_from("rw").unwrap().open(&path).unwrap());
+
+		// the read buffer holds the whole file, but writes still happen just after what was read.
+		assert_call_eq!(File::qs_read(file.clone(), 2) -> Text, *"ab");
+		File::qs_write(&file, args!("XY")).unwrap();
+		File::qs_flush(&file, args!()).unwrap();
+		assert_call_eq!(File::qs_read(file.clone()) -> Text, *"ef");
+		assert_eq!(fs::read_to_string(&path).unwrap(), "abXYef");
+
+		File::qs_seek(&file, args!(3)).unwrap();
+		File::qs_truncate(&file, args!()).unwrap();
+		assert_eq!(fs::read_to_string(&path).unwrap(), "abX");
+
+		let readonly = Object::from(OpenOptions::default().open(&path).unwrap());
+		assert!(File::qs_write(&readonly, args!("nope")).is_err());
+
+		fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn open_with_block() {
+		crate::init();
+		let path = temp_file("open_with_block", "");
+		let path_obj = Object::from(path.display().to_string());
+
+		// the file's closed even when the block raises an error.
+		let block = RustFn::method("block", |file, _| {
+			file.call_attr_lit("write", &[&"data".into()])?;
+			Err(crate::error::ValueError::Messaged("oops".into()).into())
+		});
+
+		assert!(File::qs_open(&Object::default(), args!(path_obj.clone(), "wt", Object::from(block))).is_err());
+		assert_eq!(fs::read_to_string(&path).unwrap(), "data");
+
+		let block = RustFn::method("block", |file, _| Ok(file.clone()));
+		let file = File::qs_open(&Object::default(), args!(path_obj, Object::from(block))).unwrap();
+		assert_call_eq!(File::qs_closed_q(file) -> Boolean, true);
+
+		fs::remove_file(path).unwrap();
+	}
 }
@@ -0,0 +1,588 @@
+//! Converting objects to and from JSON.
+
+use crate::{Object, Args, Literal};
+use crate::error::{TypeError, ValueError};
+use crate::types::{Null, Boolean, Number, Text, List, Map, Scope, ObjectType};
+use std::convert::TryFrom;
+use std::iter::Peekable;
+use std::str::Chars;
+use tracing::instrument;
+
+/// The namespace for converting objects to and from JSON.
+///
+/// JSON values are parsed into their Quest equivalents: `null` is [`Null`], `true` and `false`
+/// are [`Boolean`]s, numbers are [`Number`]s, strings are [`Text`]s, arrays are [`List`]s, and
+/// objects are [`Map`]s with `Text` keys.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Json;
+
+/// How deeply arrays and objects can be nested before parsing or stringifying gives up.
+const MAX_DEPTH: usize = 512;
+
+/// Parses JSON text, keeping track of where it is for error messages.
+struct Parser<'a> {
+	src: Peekable<Chars<'a>>,
+	line: usize,
+	column: usize
+}
+
+impl<'a> Parser<'a> {
+	fn new(src: &'a str) -> Self {
+		Self { src: src.chars().peekable(), line: 1, column: 1 }
+	}
+
+	fn error_at(&self, (line, column): (usize, usize), message: impl Into<String>) -> crate::Error {
+		ValueError::BadJson { message: message.into(), line, column }.into()
+	}
+
+	/// An error at the next character.
+	fn error(&self, message: impl Into<String>) -> crate::Error {
+		self.error_at(self.position(), message)
+	}
+
+	fn position(&self) -> (usize, usize) {
+		(self.line, self.column)
+	}
+
+	fn peek(&mut self) -> Option<char> {
+		self.src.peek().copied()
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let chr = self.src.next()?;
+
+		if chr == '\n' {
+			self.line += 1;
+			self.column = 1;
+		} else {
+			self.column += 1;
+		}
+
+		Some(chr)
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+			self.next();
+		}
+	}
+
+	fn expect(&mut self, expected: char) -> crate::Result<()> {
+		match self.peek() {
+			Some(chr) if chr == expected => { self.next(); Ok(()) },
+			Some(chr) => Err(self.error(format!("expected `{}`, found `{}`", expected, chr))),
+			None => Err(self.error(format!("expected `{}`, found end of input", expected)))
+		}
+	}
+
+	/// Parses the entire input, which must be exactly one value.
+	fn parse(mut self) -> crate::Result<Object> {
+		let value = self.value(0)?;
+		self.skip_whitespace();
+
+		match self.peek() {
+			None => Ok(value),
+			Some(chr) => Err(self.error(format!("unexpected `{}` after the value", chr)))
+		}
+	}
+
+	fn value(&mut self, depth: usize) -> crate::Result<Object> {
+		if depth > MAX_DEPTH {
+			return Err(self.error("too deeply nested"));
+		}
+
+		self.skip_whitespace();
+
+		match self.peek() {
+			Some('n') => self.word("null", Null.into()),
+			Some('t') => self.word("true", true.into()),
+			Some('f') => self.word("false", false.into()),
+			Some('"') => self.string().map(Object::from),
+			Some('[') => self.array(depth),
+			Some('{') => self.object(depth),
+			Some('-') | Some('0'..='9') => self.number(),
+			Some(chr) => Err(self.error(format!("unexpected `{}`", chr))),
+			None => Err(self.error("expected a value, found end of input"))
+		}
+	}
+
+	fn word(&mut self, word: &str, value: Object) -> crate::Result<Object> {
+		let start = self.position();
+
+		for expected in word.chars() {
+			if self.next() != Some(expected) {
+				return Err(self.error_at(start, format!("expected `{}`", word)));
+			}
+		}
+
+		Ok(value)
+	}
+
+	fn digits(&mut self, number: &mut String) -> crate::Result<()> {
+		if !matches!(self.peek(), Some('0'..='9')) {
+			return Err(self.error("expected a digit"));
+		}
+
+		while let Some(chr @ '0'..='9') = self.peek() {
+			number.push(chr);
+			self.next();
+		}
+
+		Ok(())
+	}
+
+	fn number(&mut self) -> crate::Result<Object> {
+		let start = self.position();
+		let mut number = String::new();
+
+		if self.peek() == Some('-') {
+			number.push('-');
+			self.next();
+		}
+
+		if self.peek() == Some('0') {
+			number.push('0');
+			self.next();
+
+			if let Some('0'..='9') = self.peek() {
+				return Err(self.error("numbers can't have leading zeros"));
+			}
+		} else {
+			self.digits(&mut number)?;
+		}
+
+		if self.peek() == Some('.') {
+			number.push('.');
+			self.next();
+			self.digits(&mut number)?;
+		}
+
+		if let Some(chr @ 'e') | Some(chr @ 'E') = self.peek() {
+			number.push(chr);
+			self.next();
+
+			if let Some(sign @ '+') | Some(sign @ '-') = self.peek() {
+				number.push(sign);
+				self.next();
+			}
+
+			self.digits(&mut number)?;
+		}
+
+		Number::try_from(number.as_str())
+			.map(Object::from)
+			.map_err(|err| self.error_at(start, format!("bad number: {:?}", err)))
+	}
+
+	fn hex_escape(&mut self) -> crate::Result<u32> {
+		let mut value = 0;
+
+		for _ in 0..4 {
+			match self.peek().and_then(|chr| chr.to_digit(16)) {
+				Some(digit) => { self.next(); value = value * 16 + digit; },
+				None => return Err(self.error("expected four hex digits"))
+			}
+		}
+
+		Ok(value)
+	}
+
+	fn string(&mut self) -> crate::Result<String> {
+		let start = self.position();
+		let mut string = String::new();
+		self.expect('"')?;
+
+		loop {
+			let position = self.position();
+
+			match self.next() {
+				Some('"') => return Ok(string),
+				Some('\\') => match self.next() {
+					Some(chr @ '"') | Some(chr @ '\\') | Some(chr @ '/') => string.push(chr),
+					Some('b') => string.push('\x08'),
+					Some('f') => string.push('\x0c'),
+					Some('n') => string.push('\n'),
+					Some('r') => string.push('\r'),
+					Some('t') => string.push('\t'),
+					Some('u') => {
+						let mut code = self.hex_escape()?;
+
+						if (0xd800..=0xdbff).contains(&code) {
+							if self.next() != Some('\\') || self.next() != Some('u') {
+								return Err(self.error_at(position, "unpaired surrogate"));
+							}
+
+							let low = self.hex_escape()?;
+
+							if !(0xdc00..=0xdfff).contains(&low) {
+								return Err(self.error_at(position, "unpaired surrogate"));
+							}
+
+							code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+						}
+
+						match std::char::from_u32(code) {
+							Some(chr) => string.push(chr),
+							None => return Err(self.error_at(position, "unpaired surrogate"))
+						}
+					},
+					Some(chr) => return Err(self.error_at(position, format!("bad escape `\\{}`", chr))),
+					None => return Err(self.error_at(start, "unterminated string"))
+				},
+				Some(chr) if (chr as u32) < 0x20 =>
+					return Err(self.error_at(position, "control characters must be escaped")),
+				Some(chr) => string.push(chr),
+				None => return Err(self.error_at(start, "unterminated string"))
+			}
+		}
+	}
+
+	/// Calls `each` for each comma-separated element until `close`.
+	fn elements(&mut self, close: char, mut each: impl FnMut(&mut Self) -> crate::Result<()>)
+		-> crate::Result<()>
+	{
+		self.next();
+		self.skip_whitespace();
+
+		if self.peek() == Some(close) {
+			self.next();
+			return Ok(());
+		}
+
+		loop {
+			each(self)?;
+			self.skip_whitespace();
+
+			match self.peek() {
+				Some(',') => { self.next(); },
+				Some(chr) if chr == close => { self.next(); return Ok(()); },
+				Some(chr) => return Err(self.error(format!("expected `,` or `{}`, found `{}`", close, chr))),
+				None => return Err(self.error(format!("expected `,` or `{}`, found end of input", close)))
+			}
+		}
+	}
+
+	fn array(&mut self, depth: usize) -> crate::Result<Object> {
+		let mut list = Vec::new();
+
+		self.elements(']', |this| {
+			list.push(this.value(depth + 1)?);
+			Ok(())
+		})?;
+
+		Ok(list.into())
+	}
+
+	fn object(&mut self, depth: usize) -> crate::Result<Object> {
+		let mut map = Map::new();
+
+		self.elements('}', |this| {
+			this.skip_whitespace();
+
+			if this.peek() != Some('"') {
+				return Err(this.error("expected a string key"));
+			}
+
+			let key = this.string()?;
+			this.skip_whitespace();
+			this.expect(':')?;
+			let value = this.value(depth + 1)?;
+
+			map.insert(key.into(), value).map(drop)
+		})?;
+
+		Ok(map.into())
+	}
+}
+
+/// Writes objects out as JSON.
+struct Stringifier<'a> {
+	indent: Option<&'a str>,
+	out: String,
+	/// The ids of the objects currently being written, to catch cycles.
+	path: Vec<usize>
+}
+
+fn escape_into(out: &mut String, text: &str) {
+	out.push('"');
+
+	for chr in text.chars() {
+		match chr {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			chr if (chr as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", chr as u32)),
+			chr => out.push(chr)
+		}
+	}
+
+	out.push('"');
+}
+
+/// Checks to see if the attribute `key` of a scope shouldn't be written.
+fn is_internal(key: &str) -> bool {
+	key.len() > 4 && key.starts_with("__") && key.ends_with("__")
+}
+
+impl Stringifier<'_> {
+	fn newline(&mut self, depth: usize) {
+		if let Some(indent) = self.indent {
+			self.out.push('\n');
+
+			for _ in 0..depth {
+				self.out.push_str(indent);
+			}
+		}
+	}
+
+	/// Writes `entries` between `open` and `close`, calling `each` to write each one.
+	fn entries<T>(&mut self, (open, close): (char, char), entries: Vec<T>, depth: usize,
+		mut each: impl FnMut(&mut Self, T) -> crate::Result<()>) -> crate::Result<()>
+	{
+		self.out.push(open);
+
+		if !entries.is_empty() {
+			for (idx, entry) in entries.into_iter().enumerate() {
+				if idx != 0 {
+					self.out.push(',');
+				}
+
+				self.newline(depth + 1);
+				each(self, entry)?;
+			}
+
+			self.newline(depth);
+		}
+
+		self.out.push(close);
+		Ok(())
+	}
+
+	fn key_value(&mut self, key: &str, value: &Object, depth: usize) -> crate::Result<()> {
+		escape_into(&mut self.out, key);
+		self.out.push_str(if self.indent.is_some() { ": " } else { ":" });
+		self.write(value, depth + 1)
+	}
+
+	fn write(&mut self, obj: &Object, depth: usize) -> crate::Result<()> {
+		if self.path.contains(&obj.id()) {
+			return Err(ValueError::Messaged("can't convert a cyclic structure to JSON".into()).into());
+		} else if depth > MAX_DEPTH {
+			return Err(ValueError::Messaged("too deeply nested to convert to JSON".into()).into());
+		}
+
+		self.path.push(obj.id());
+		let result = self.write_inner(obj, depth);
+		self.path.pop();
+		result
+	}
+
+	fn write_inner(&mut self, obj: &Object, depth: usize) -> crate::Result<()> {
+		if obj.has_attr_lit("@json")? {
+			return self.write(&obj.call_attr_lit("@json", &[])?, depth);
+		}
+
+		if obj.is_a::<Null>() {
+			self.out.push_str("null");
+		} else if let Some(boolean) = obj.downcast::<Boolean>() {
+			self.out.push_str(if boolean.into_inner() { "true" } else { "false" });
+		} else if let Some(number) = obj.downcast::<Number>() {
+			if !number.is_integer() && !f64::from(number.clone()).is_finite() {
+				return Err(ValueError::Messaged(format!("can't convert {} to JSON", *number)).into());
+			}
+
+			self.out.push_str(&number.to_string());
+		} else if let Some(text) = obj.downcast::<Text>() {
+			escape_into(&mut self.out, text.as_ref());
+		} else if obj.is_a::<List>() {
+			let elements = obj.downcast::<List>().unwrap().iter().cloned().collect::<Vec<_>>();
+
+			self.entries(('[', ']'), elements, depth, |this, element| this.write(&element, depth + 1))?;
+		} else if obj.is_a::<Map>() {
+			let entries = obj.downcast::<Map>().unwrap()
+				.iter()
+				.map(|(key, value)| (key.clone(), value.clone()))
+				.collect::<Vec<_>>();
+
+			self.entries(('{', '}'), entries, depth, |this, (key, value)| {
+				let key = key.call_downcast::<Text>()?.to_string();
+				this.key_value(&key, &value, depth)
+			})?;
+		} else if obj.is_descendant_of(Scope::mapping())? {
+			let keys = obj.call_attr_lit(&Literal::__KEYS__, &[&false.into()])?;
+			let mut keys = keys.call_downcast::<List>()?
+				.iter()
+				.map(|key| Ok((key.call_downcast::<Text>()?.to_string(), key.clone())))
+				.collect::<crate::Result<Vec<_>>>()?;
+
+			// attributes aren't kept in any order, so they're sorted to keep the output consistent.
+			keys.retain(|(name, _)| !is_internal(name));
+			keys.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+			self.entries(('{', '}'), keys, depth, |this, (name, key)| {
+				this.key_value(&name, &obj.get_attr(&key)?, depth)
+			})?;
+		} else {
+			return Err(TypeError::Messaged(format!("can't convert a {} to JSON", obj.typename())).into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Json {
+	/// Parses `src` as JSON.
+	pub fn parse(src: &str) -> crate::Result<Object> {
+		Parser::new(src).parse()
+	}
+
+	/// Converts `obj` to JSON, putting each element on its own line if an `indent` is given.
+	pub fn stringify(obj: &Object, indent: Option<&str>) -> crate::Result<String> {
+		let mut stringifier = Stringifier { indent, out: String::new(), path: Vec::new() };
+		stringifier.write(obj, 0)?;
+		Ok(stringifier.out)
+	}
+}
+
+/// Quest methods
+impl Json {
+	/// Parses JSON text.
+	///
+	/// Invalid JSON raises a `ValueError` that says where the problem is.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The JSON to parse.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// config = Json::parse('{"name": "quest", "tags": [1, 2.5, null]}');
+	///
+	/// tags = config.get("tags");
+	///
+	/// assert(config.get("name") == "quest");
+	/// assert(tags == [1, 2.5, null]);
+	/// ```
+	#[instrument(name="Json::parse", level="trace", skip(args), fields(?args))]
+	pub fn qs_parse(args: Args) -> crate::Result<Object> {
+		let src = args.try_arg(0)?.call_downcast::<Text>()?.clone();
+
+		Self::parse(src.as_ref())
+	}
+
+	/// Converts an object to JSON text.
+	///
+	/// `null`, `Boolean`s, `Number`s, `Text`s, `List`s and `Map`s are converted to their JSON
+	/// equivalents, and map keys are converted with `@text`. Objects created with `object` are
+	/// written as JSON objects of their attributes, leaving out internal ones such as `__parents__`
+	/// and `__id__`.
+	///
+	/// Any object with a `@json` attribute is replaced with what `@json` returns, which is then
+	/// converted as normal.
+	///
+	/// # Arguments
+	/// 1. (required) The object to convert.
+	/// 2. (optional, `@num` or `@text`) The amount of spaces, or the text, to indent each level
+	///    with. Without one, everything is put on a single line.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Json::stringify([1, "two", Map::new(["x", true])]) == '[1,"two",{"x":true}]');
+	///
+	/// point = object() { x = 1; y = 2; @json = self -> { [self.x, self.y] }; };
+	/// assert(Json::stringify(point) == "[1,2]");
+	/// ```
+	#[instrument(name="Json::stringify", level="trace", skip(args), fields(?args))]
+	pub fn qs_stringify(args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+		let indent =
+			match args.arg(1) {
+				None => None,
+				Some(indent) if indent.is_a::<Null>() => None,
+				Some(indent) if indent.is_a::<Number>() => {
+					let width = indent.downcast::<Number>().unwrap().clone();
+					Some(" ".repeat(usize::try_from(width)?))
+				},
+				Some(indent) => Some(indent.call_downcast::<Text>()?.to_string())
+			};
+
+		Self::stringify(obj, indent.as_deref()).map(Object::from)
+	}
+}
+
+impl_object_type!{
+for Json [(parents super::Basic)]:
+	"parse" => function Self::qs_parse,
+	"stringify" => function Self::qs_stringify,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn bad_json(src: &str) -> (usize, usize) {
+		match Json::parse(src).unwrap_err() {
+			crate::Error::ValueError(ValueError::BadJson { line, column, .. }) => (line, column),
+			other => panic!("expected bad json from {:?}, got {:?}", src, other)
+		}
+	}
+
+	fn round_trip(src: &str) -> String {
+		Json::stringify(&Json::parse(src).unwrap(), None).unwrap()
+	}
+
+	#[test]
+	fn parse() {
+		crate::init();
+
+		let parsed = Json::parse(r#" {"a": [1, -2.5e1, true, false, null], "b\u00e9": "\ud83d\ude00\n"} "#).unwrap();
+		let map = parsed.downcast::<Map>().unwrap();
+
+		assert_eq!(map.len(), 2);
+		assert!(map.get(&"a".into()).unwrap().unwrap()
+			.eq_obj(&vec![1.into(), (-25).into(), true.into(), false.into(), Null.into()].into()).unwrap());
+		assert_eq!(*map.get(&"bé".into()).unwrap().unwrap().downcast::<Text>().unwrap(), *"😀\n");
+
+		assert!(Json::parse("123456789012345678901234567890").unwrap().downcast::<Number>().unwrap().is_big());
+	}
+
+	#[test]
+	fn parse_errors() {
+		crate::init();
+
+		assert_eq!(bad_json(""), (1, 1));
+		assert_eq!(bad_json("[1,\n  2,]"), (2, 5));
+		assert_eq!(bad_json("{\"a\" 1}"), (1, 6));
+		assert_eq!(bad_json("01"), (1, 2));
+		assert_eq!(bad_json("nul"), (1, 1));
+		assert_eq!(bad_json("\"a\\qb\""), (1, 3));
+		assert_eq!(bad_json("[1] 2"), (1, 5));
+		assert_eq!(bad_json("\"\\ud800\""), (1, 2));
+		assert_eq!(bad_json(&"[".repeat(MAX_DEPTH + 2)), (1, MAX_DEPTH + 2));
+	}
+
+	#[test]
+	fn stringify() {
+		crate::init();
+
+		assert_eq!(round_trip(r#" [1, 2.5, "a\"\u0001", {"x": {}, "y": []}, null] "#),
+			r#"[1,2.5,"a\"\u0001",{"x":{},"y":[]},null]"#);
+
+		let nested = Json::parse(r#"{"a": [1, {"b": true}], "c": []}"#).unwrap();
+		assert_eq!(Json::stringify(&nested, Some("  ")).unwrap(),
+			"{\n  \"a\": [\n    1,\n    {\n      \"b\": true\n    }\n  ],\n  \"c\": []\n}");
+	}
+
+	#[test]
+	fn stringify_errors() {
+		crate::init();
+
+		assert_matches!(Json::stringify(&Number::NAN.into(), None).unwrap_err(), crate::Error::ValueError(..));
+		assert_matches!(Json::stringify(&Object::from(Json), None).unwrap_err(), crate::Error::TypeError(..));
+
+		let list = Object::from(List::default());
+		list.downcast_mut::<List>().unwrap().push(list.clone());
+		assert_matches!(Json::stringify(&list, None).unwrap_err(), crate::Error::ValueError(..));
+	}
+}
@@ -1,10 +1,48 @@
 use crate::{Args, Object, Error, Literal, Binding};
-use crate::types::{Boolean, Text, Null, Number};
+use crate::types::{Boolean, Text, Null, Number, Map};
+use crate::types::exception::Kind;
+use crate::sandbox::{self, Capability};
+use std::cell::RefCell;
+use std::time::Duration;
 use tracing::instrument;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Kernel;
 
+thread_local! {
+	static CURRENT: RefCell<Option<Object>> = const { RefCell::new(None) };
+}
+
+impl Kernel {
+	/// Gets the kernel that new scopes on the current thread inherit from.
+	///
+	/// This is [`Kernel`]'s mapping, unless a different kernel has been [entered](Kernel::enter).
+	pub fn current() -> Object {
+		CURRENT.with(|current| current.borrow().clone())
+			.unwrap_or_else(|| <Self as crate::types::ObjectType>::mapping().clone())
+	}
+
+	/// Makes new scopes on the current thread inherit from `kernel`, instead of the current one.
+	///
+	/// The previous kernel is restored when the returned guard is dropped.
+	#[must_use = "the kernel is exited when the guard is dropped"]
+	pub fn enter(kernel: Object) -> KernelGuard {
+		KernelGuard(CURRENT.with(|current| current.borrow_mut().replace(kernel)))
+	}
+}
+
+/// Restores the previous kernel when dropped; created via [`Kernel::enter`].
+#[derive(Debug)]
+pub struct KernelGuard(Option<Object>);
+
+impl Drop for KernelGuard {
+	fn drop(&mut self) {
+		let previous = self.0.take();
+
+		CURRENT.with(|current| *current.borrow_mut() = previous);
+	}
+}
+
 fn display(args: &[&Object], newline: bool) -> crate::Result<()> {
 	print!("{}",
 		args.iter()
@@ -30,11 +68,43 @@ fn is_object_truthy(object: &Object) -> crate::Result<bool> {
 	object.call_downcast::<Boolean>().map(|b| b.into_inner())
 }
 
+/// Sleeps for `duration`, but not past the current sandbox's time limit.
+fn sleep(duration: Duration) -> crate::Result<()> {
+	match sandbox::remaining_time() {
+		Some(remaining) if remaining < duration => {
+			std::thread::sleep(remaining);
+			sandbox::step()
+		},
+		_ => {
+			std::thread::sleep(duration);
+			Ok(())
+		}
+	}
+}
+
 #[inline]
 fn object_to_string(object: &Object) -> crate::Result<String> {
 	object.call_downcast::<Text>().map(|t| t.to_string())
 }
 
+fn catch_exception(exception: Object, handlers: &Object) -> crate::Result<Object> {
+	let handlers =
+		match handlers.downcast::<Map>() {
+			Some(handlers) => handlers.iter()
+				.map(|(class, handler)| (class.clone(), handler.clone()))
+				.collect::<Vec<_>>(),
+			None => return handlers.call_attr_lit(&Literal::CALL, &[&exception])
+		};
+
+	for (class, handler) in handlers {
+		if exception.is_descendant_of(&class)? {
+			return handler.call_attr_lit(&Literal::CALL, &[&exception]);
+		}
+	}
+
+	Err(Error::Thrown(exception))
+}
+
 impl Kernel {
 	/// Checks the first attribute
 	#[instrument(name="Kernel::if", level="trace")]
@@ -148,9 +218,11 @@ impl Kernel {
 	pub fn qs_quit(args: Args) -> crate::Result<Object> {
 		use std::convert::TryFrom;
 
+		sandbox::require(Capability::EXIT, "Kernel::quit")?;
+
 		let code = 
 			if let Some(code) = args.arg(0) {
-				i32::try_from(*code.call_downcast::<Number>()?)?
+				i32::try_from(code.call_downcast::<Number>()?.clone())?
 			} else {
 				0
 			};
@@ -159,11 +231,13 @@ impl Kernel {
 			display(&[msg], true)?;
 		}
 
-		std::process::exit(code as i32)
+		super::Env::exit(code)
 	}
 
 	#[instrument(name="Kernel::abort", level="trace")]
 	pub fn qs_abort(args: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::EXIT, "Kernel::abort")?;
+
 		if let Some(message) = args.arg(0) {
 			eprintln!("{}", message.call_downcast::<Text>()?.as_ref());
 		};
@@ -171,9 +245,12 @@ impl Kernel {
 		std::process::exit(1);
 	}
 
-	#[instrument(name="Kernel::system", level="trace")]
-	pub fn qs_system(cmd: &Object, args: Args) -> crate::Result<Object> {
+	/// Runs `cmd` with `args`, returning what it wrote to stdout.
+	fn run_system(cmd: &Object, args: Args) -> crate::Result<Vec<u8>> {
 		use std::process::Command;
+
+		sandbox::require(Capability::PROCESS, "Kernel::system")?;
+
 		let cmd = object_to_string(cmd)?;
 		let mut command = Command::new(cmd);
 
@@ -183,7 +260,19 @@ impl Kernel {
 
 		command.output()
 			.map_err(|err| Error::Messaged(format!("couldnt spawn proc: {}", err)))
-			.map(|output| String::from_utf8_lossy(&output.stdout).to_string().into())
+			.map(|output| output.stdout)
+	}
+
+	#[instrument(name="Kernel::system", level="trace")]
+	pub fn qs_system(cmd: &Object, args: Args) -> crate::Result<Object> {
+		Self::run_system(cmd, args)
+			.map(|stdout| String::from_utf8_lossy(&stdout).to_string().into())
+	}
+
+	/// Like `system`, except stdout is returned as [`Bytes`](super::Bytes) instead of text.
+	#[instrument(name="Kernel::system_bytes", level="trace")]
+	pub fn qs_system_bytes(cmd: &Object, args: Args) -> crate::Result<Object> {
+		Self::run_system(cmd, args).map(|stdout| super::Bytes::from(stdout).into())
 	}
 
 	#[instrument(name="Kernel::rand", level="trace")]
@@ -194,10 +283,10 @@ impl Kernel {
 		let mut end: FloatType = 1.0;
 
 		if let Some(start_num) = args.arg(0) {
-			start = (*start_num.call_downcast::<Number>()?).into();
+			start = start_num.call_downcast::<Number>()?.clone().into();
 
 			if let Some(end_num) = args.arg(1) {
-				end = (*end_num.call_downcast::<Number>()?).into();
+				end = end_num.call_downcast::<Number>()?.clone().into();
 			} else {
 				end = start;
 				start = 0.0;
@@ -245,6 +334,49 @@ impl Kernel {
 		Err(Error::Return { to, obj })
 	}
 
+	/// Throws `obj`, unwinding the stack until it's caught by a `try`.
+	#[instrument(name="Kernel::throw", level="trace")]
+	pub fn qs_throw(args: Args) -> crate::Result<Object> {
+		Err(Error::Thrown(args.try_arg(0)?.clone()))
+	}
+
+	/// Calls `body`, catching any exceptions that are thrown.
+	///
+	/// `handlers` is either a block, which is called with any exception, or a [`Map`] of classes
+	/// to blocks, in which case the first class the exception descends from has its block called.
+	/// If no handler matches, the exception keeps propagating. Either way, `ensure` is always
+	/// called before returning.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// result = try({ throw(KeyError("oops")) }, Map::new(
+	/// 	[TypeError, err -> { "type" }],
+	/// 	[Exception, err -> { err.message() }]
+	/// ), { disp("done") });
+	///
+	/// assert(result == "oops");
+	/// ```
+	#[instrument(name="Kernel::try", level="trace")]
+	pub fn qs_try(body: &Object, args: Args) -> crate::Result<Object> {
+		let handlers = args.arg(0).filter(|handlers| !handlers.is_a::<Null>());
+		let ensure = args.arg(1);
+
+		let result =
+			match (body.call_attr_lit(&Literal::CALL, &[]), handlers) {
+				(Err(err), Some(handlers)) => match err.into_exception() {
+					Ok(exception) => catch_exception(exception, handlers),
+					Err(err) => Err(err)
+				},
+				(result, _) => result
+			};
+
+		if let Some(ensure) = ensure {
+			ensure.call_attr_lit(&Literal::CALL, &[])?;
+		}
+
+		result
+	}
+
 	#[instrument(name="Kernel::assert", level="trace")]
 	pub fn qs_assert(cond: &Object, args: Args) -> crate::Result<Object> {
 		if is_object_truthy(cond)? {
@@ -259,20 +391,32 @@ impl Kernel {
 	#[instrument(name="Kernel::sleep", level="trace")]
 	pub fn qs_sleep(args: Args) -> crate::Result<Object> {
 		if let Some(arg) = args.arg(0) {
-			let dur: f64 = (*arg.call_downcast::<Number>()?).into();
-			std::thread::sleep(std::time::Duration::from_secs_f64(dur));
+			let dur: f64 = arg.call_downcast::<Number>()?.clone().into();
+			sleep(Duration::from_secs_f64(dur))?;
 		} else {
 			// technically doesn't sleep forever lol.
-			std::thread::sleep(std::time::Duration::from_secs_f64(f64::MAX));
+			sleep(Duration::from_secs_f64(f64::MAX))?;
 		}
 
 		Ok(Object::default())
 	}
 
-	#[instrument(name="Kernel::open", level="trace")]
-	pub fn qs_open(_: &Object, _args: Args) -> crate::Result<Object> {
-		// let filename = args.try_arg(0)?.downcast_call::<types::Text>();
-		todo!("open")
+	/// Opens a file, optionally passing it to a block that it's closed after.
+	///
+	/// This is the same as `Io::File.open`.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The file to open.
+	/// 2. (optional, `@text`) The mode to open it with, such as `"r"` or `"wt"`.
+	/// 3. (optional, `()`) A block to call with the file, which is closed once the block returns.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// open("log.txt", "at", f -> { f.write("started\n") });
+	/// ```
+	#[instrument(name="Kernel::open", level="trace", skip(args), fields(?args))]
+	pub fn qs_open(args: Args) -> crate::Result<Object> {
+		super::io::File::open_block(args, "Kernel::open")
 	}
 }
 
@@ -283,7 +427,19 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 	"null" => const Null::new(),
 
 	"Io" => const super::Io::mapping().clone(),
-	"Tcp" => const super::Tcp::mapping().clone(),
+	"TcpListener" => const super::TcpListener::mapping().clone(),
+	"TcpStream" => const super::TcpStream::mapping().clone(),
+	"UdpSocket" => const super::UdpSocket::mapping().clone(),
+	"Http" => const super::Http::mapping().clone(),
+	"Time" => const super::Time::mapping().clone(),
+	"Duration" => const super::Duration::mapping().clone(),
+	"Instant" => const super::Instant::mapping().clone(),
+	"Json" => const super::Json::mapping().clone(),
+	"Process" => const super::Process::mapping().clone(),
+	"Env" => const super::Env::mapping().clone(),
+	"OptParse" => const super::OptParse::mapping().clone(),
+	"Fs" => const super::Fs::mapping().clone(),
+	"Path" => const super::Path::mapping().clone(),
 	"Basic" => const super::Basic::mapping().clone(),
 	"Boolean" => const super::Boolean::mapping().clone(),
 	"BoundFunction" => const super::BoundFunction::mapping().clone(),
@@ -291,12 +447,28 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 	"RustClosure" => const super::RustClosure::mapping().clone(),
 	"Kernel" => const Kernel::mapping().clone(),
 	"List" => const super::List::mapping().clone(),
+	"Map" => const super::Map::mapping().clone(),
+	"Exception" => const super::Exception::mapping().clone(),
+	"Frame" => const super::Frame::mapping().clone(),
+	"RuntimeError" => const Kind::RuntimeError.class().clone(),
+	"IoError" => const Kind::IoError.class().clone(),
+	"KeyError" => const Kind::KeyError.class().clone(),
+	"ArgumentError" => const Kind::ArgumentError.class().clone(),
+	"TypeError" => const Kind::TypeError.class().clone(),
+	"ValueError" => const Kind::ValueError.class().clone(),
+	"AssertionError" => const Kind::AssertionError.class().clone(),
+	"SandboxError" => const Kind::SandboxError.class().clone(),
 	"Null" => const super::Null::mapping().clone(),
 	"Number" => const super::Number::mapping().clone(),
+	"Rational" => const super::Rational::mapping().clone(),
 	"Pristine" => const super::Pristine::mapping().clone(),
 	"RustFn" => const super::RustFn::mapping().clone(),
 	"Scope" => const super::Scope::mapping().clone(),
 	"Text" => const super::Text::mapping().clone(),
+	"Bytes" => const super::Bytes::mapping().clone(),
+	"Thread" => const super::Thread::mapping().clone(),
+	"Channel" => const super::Channel::mapping().clone(),
+	"Mutex" => const super::Mutex::mapping().clone(),
 	"Regex" => const super::Regex::mapping().clone(),
 	"Comparable" => const super::Comparable::mapping().clone(),
 	"Iterable" => const super::Iterable::mapping().clone(),
@@ -313,15 +485,18 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 	"quit" => function Self::qs_quit,
 	"abort" => function Self::qs_abort,
 	"system" => method Self::qs_system,
+	"system_bytes" => method Self::qs_system_bytes,
 	"rand" => function Self::qs_rand,
 	"prompt" => function Self::qs_prompt,
 	"while" => method Self::qs_while,
 	"until" => method Self::qs_until,
 	"loop" => method Self::qs_loop,
 	"sleep" => function Self::qs_sleep,
-	"open" => method Self::qs_open,
+	"open" => function Self::qs_open,
 	"return" => function Self::qs_return,
 	"assert" => method Self::qs_assert,
+	"throw" => function Self::qs_throw,
+	"try" => method Self::qs_try,
 
 	"__set_stack__" => method |stack, _| {
 		let stack = stack.call_downcast::<crate::types::List>()?;
@@ -346,28 +521,7 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 		object.call_attr_lit("instance_exec", &[body]).and(Ok(object))
 	},
 
-	"spawn" => method |block, _| {
-		use std::thread::{self, JoinHandle};
-		use std::sync::Arc;
-		use parking_lot::Mutex;
-		#[derive(Debug, Clone)]
-		struct Thread(Arc<Mutex<Option<JoinHandle<crate::Result<Object>>>>>);
-
-		impl_object_type! { for Thread [(parents super::Basic)]:
-			"join" => method |this, _| this.try_downcast::<Thread>().and_then(|this| {
-				this.0.lock().take().expect("no join handle?")
-					.join()
-					.unwrap()
-			})
-		}
-
-		Thread::initialize().unwrap();
-
-		let block = block.clone();
-		Ok(Thread(Arc::new(Mutex::new(Some(thread::spawn(move ||
-			block.call_attr_lit(&Literal::CALL, &[&block])
-		))))).into())
-	},
+	"spawn" => method super::Thread::qs_spawn,
 }
 
 
@@ -427,8 +581,12 @@ mod tests {
 
 		assert_mapping_eq!(
 			"Basic" Basic, /*"Block" Block,*/ "Boolean" Boolean, "Function" Function,
-			"Kernel" Kernel, "List" List, "Null" Null, "Number" Number,
-			"Pristine" Pristine, "RustFn" RustFn, "Text" Text
+			"Kernel" Kernel, "List" List, "Map" Map, "Exception" Exception, "Frame" Frame,
+			"Null" Null, "Number" Number, "Pristine" Pristine, "Rational" Rational, "RustFn" RustFn,
+			"Text" Text, "Bytes" Bytes, "Thread" Thread, "Channel" Channel, "Mutex" Mutex,
+			"TcpListener" TcpListener, "TcpStream" TcpStream, "UdpSocket" UdpSocket,
+			"Http" Http, "Process" Process, "Env" Env, "OptParse" OptParse, "Time" Time, "Duration" Duration,
+			"Instant" Instant, "Json" Json, "Fs" Fs, "Path" Path
 		);
 	}
 
@@ -469,6 +627,21 @@ mod tests {
 	fn sleep() { todo!() }
 
 	#[test]
-	#[ignore]
-	fn open() { todo!() }
+	fn open() {
+		crate::init();
+
+		let path = std::env::temp_dir().join(format!("quest-kernel-open-{}", std::process::id()));
+		std::fs::write(&path, "hello\nworld\n").unwrap();
+		let path_obj = Object::from(path.display().to_string());
+
+		let file = Kernel::qs_open(args!(path_obj.clone())).unwrap();
+		assert_eq!(file.call_attr_lit("read_line", &[]).unwrap().downcast::<Text>().unwrap().as_ref(), "hello\n");
+
+		let block = crate::types::RustFn::method("block", |file, _| file.call_attr_lit("read_line", &[]));
+		let line = Kernel::qs_open(args!(path_obj.clone(), "r", Object::from(block))).unwrap();
+		assert_eq!(line.downcast::<Text>().unwrap().as_ref(), "hello\n");
+
+		std::fs::remove_file(&path).unwrap();
+		assert!(Kernel::qs_open(args!(path_obj)).is_err());
+	}
 }
@@ -1,5 +1,5 @@
 use crate::{Object, Args, Literal, error::KeyError};
-use crate::utils::{correct_index, IndexError};
+use crate::utils::{correct_index, IndexError, SliceIndex};
 use crate::types::{Convertible, Text, Boolean, Number};
 use std::convert::{TryFrom, TryInto};
 use std::iter::FromIterator;
@@ -88,19 +88,7 @@ impl List {
 
 	/// Get either a single element or a range of elements.
 	pub fn get_rng(&self, start: isize, stop: isize) -> Option<&[Object]> {
-		let start = correct_index(start, self.len()).ok()?;
-		let stop = 
-			match correct_index(stop, self.len()) {
-				Ok(stop) => stop + 1,
-				Err(IndexError::TooPositive) => self.len(), // saturate to our biggest value.
-				Err(IndexError::TooNegative) => return None
-			};
-
-		if stop < start {
-			None
-		} else {
-			Some(&self.0[start..stop])
-		}
+		SliceIndex::get(start..=stop, self.0.as_slice())
 	}
 
 	/// Sets a single element in a list
@@ -609,11 +597,11 @@ impl List {
 	/// ```
 	#[instrument(name="List::get", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
-		let start: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+		let start: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 
 		let stop = 
 			args.arg(1)
-			.map(|n| n.call_downcast::<Number>().map(|n| *n))
+			.map(|n| n.call_downcast::<Number>().map(|n| n.clone()))
 			.transpose()?
 			.map(isize::try_from)
 			.transpose()?;
@@ -665,7 +653,7 @@ impl List {
 	/// ```
 	#[instrument(name="List::set", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_set(this: &Object, args: Args) -> crate::Result<Object> {
-		let pos: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+		let pos: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 
 		if args.len() == 2 {
 			let ele = args.arg(1).unwrap().clone();
@@ -677,7 +665,7 @@ impl List {
 				Ok(ele)
 			}
 		} else {
-			let end: isize = args.try_arg(1)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+			let end: isize = args.try_arg(1)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 			let ele = args.try_arg(2)?.call_downcast::<Self>()?.clone();
 			let mut this = this.try_downcast_mut::<Self>()?;
 
@@ -693,7 +681,7 @@ impl List {
 
 	#[instrument(name="List::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_delete(this: &Object, args: Args) -> crate::Result<Object> {
-		let idx: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+		let idx: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 
 		let mut this = this.try_downcast_mut::<Self>()?;
 
@@ -723,14 +711,14 @@ impl List {
 	#[instrument(name="List::*", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
-		let amnt = usize::try_from(*args.try_arg(0)?.call_downcast::<Number>()?)?;
+		let amnt = usize::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
 
 		Ok((&*this * amnt).into())
 	}
 
 	#[instrument(name="List::*=", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_mul_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let amnt = usize::try_from(*args.try_arg(0)?.call_downcast::<Number>()?)?;
+		let amnt = usize::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
 
 		*this.try_downcast_mut::<Self>()? *= amnt;
 
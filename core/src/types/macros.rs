@@ -147,7 +147,7 @@ macro_rules! impl_object_type {
 		vec![$(<$init_parent as $crate::types::ObjectType>::mapping().clone()),+]
 	};
 	(@SET_PARENT $class:ident (parents $parent:path) $($_rest:tt)*) => {
-		impl_object_type!(@SET_PARENT $class (init_parent $parent));
+		impl_object_type!(@SET_PARENT $class (init_parent $parent))
 	};
 
 	(@SET_PARENT $class:ident $_b:tt $($rest:tt)*) => {
@@ -0,0 +1,585 @@
+use crate::{Object, Args, Literal};
+use crate::types::{Text, Boolean, Number, List};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Formatter};
+use tracing::instrument;
+
+/// A Map in Quest.
+///
+/// Maps associate keys with values. Keys can be any object that has a `hash` and a `==` attribute,
+/// and entries are kept in the order they were first inserted.
+#[derive(Clone, Default)]
+pub struct Map {
+	entries: Vec<(Object, Object)>,
+	indices: HashMap<u64, Vec<usize>>
+}
+
+impl Debug for Map {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.debug_tuple("Map").field(&self.entries).finish()
+		} else {
+			f.debug_map().entries(self.iter()).finish()
+		}
+	}
+}
+
+/// Hashes `key` by calling its `hash` attribute.
+fn hash_key(key: &Object) -> crate::Result<u64> {
+	let hash = key.call_attr_lit(&Literal::HASH, &[])?;
+	let hash = hash.call_downcast::<Number>()?;
+
+	Ok(crate::utils::hash(&*hash))
+}
+
+/// Rust-centric map methods
+impl Map {
+	/// Create a new, empty map.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Get the amount of entries in the map.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Checks if the map is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Get an [`Iterator`](std::iter::Iterator) over the entries in this map, in insertion order.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item=(&Object, &Object)> {
+		self.entries.iter().map(|(key, value)| (key, value))
+	}
+
+	/// Get an [`Iterator`](std::iter::Iterator) over the keys in this map.
+	#[inline]
+	pub fn keys(&self) -> impl Iterator<Item=&Object> {
+		self.entries.iter().map(|(key, _)| key)
+	}
+
+	/// Get an [`Iterator`](std::iter::Iterator) over the values in this map.
+	#[inline]
+	pub fn values(&self) -> impl Iterator<Item=&Object> {
+		self.entries.iter().map(|(_, value)| value)
+	}
+
+	/// Remove all entries from the map.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.entries.clear();
+		self.indices.clear();
+	}
+
+	fn position(&self, hash: u64, key: &Object) -> crate::Result<Option<usize>> {
+		if let Some(bucket) = self.indices.get(&hash) {
+			for &idx in bucket {
+				if self.entries[idx].0.eq_obj(key)? {
+					return Ok(Some(idx));
+				}
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Get the value associated with `key`, if it exists.
+	pub fn get(&self, key: &Object) -> crate::Result<Option<&Object>> {
+		let hash = hash_key(key)?;
+
+		Ok(self.position(hash, key)?.map(|idx| &self.entries[idx].1))
+	}
+
+	/// Checks to see if `key` is in the map.
+	pub fn contains_key(&self, key: &Object) -> crate::Result<bool> {
+		self.get(key).map(|value| value.is_some())
+	}
+
+	/// Associates `value` with `key`, returning the previous value if there was one.
+	pub fn insert(&mut self, key: Object, value: Object) -> crate::Result<Option<Object>> {
+		let hash = hash_key(&key)?;
+		let position = self.position(hash, &key)?;
+
+		Ok(self.insert_at(hash, position, key, value))
+	}
+
+	fn insert_at(&mut self, hash: u64, position: Option<usize>, key: Object, value: Object) -> Option<Object> {
+		if let Some(idx) = position {
+			return Some(std::mem::replace(&mut self.entries[idx].1, value));
+		}
+
+		self.indices.entry(hash).or_default().push(self.entries.len());
+		self.entries.push((key, value));
+
+		None
+	}
+
+	/// Removes `key` from the map, returning its value if it existed.
+	pub fn remove(&mut self, key: &Object) -> crate::Result<Option<Object>> {
+		let hash = hash_key(key)?;
+
+		Ok(self.position(hash, key)?.map(|idx| self.remove_at(idx)))
+	}
+
+	fn remove_at(&mut self, idx: usize) -> Object {
+		let (_, value) = self.entries.remove(idx);
+
+		for bucket in self.indices.values_mut() {
+			bucket.retain(|&i| i != idx);
+
+			for i in bucket.iter_mut() {
+				if *i > idx {
+					*i -= 1;
+				}
+			}
+		}
+
+		self.indices.retain(|_, bucket| !bucket.is_empty());
+
+		value
+	}
+
+	/// Checks to see if the entries with the given `hash` are still `candidates`.
+	fn bucket_unchanged(&self, hash: u64, candidates: &[(usize, Object)]) -> bool {
+		let bucket = self.indices.get(&hash).map_or(&[][..], Vec::as_slice);
+
+		bucket.len() == candidates.len()
+			&& bucket.iter().zip(candidates).all(|(&idx, (candidate_idx, candidate))|
+				idx == *candidate_idx && self.entries[idx].0.is_identical(candidate))
+	}
+
+	/// Finds `key` in the map stored in `this`, then calls `func` with the map locked via `lock`.
+	///
+	/// A key's `hash` and `==` can run arbitrary Quest code, which might use the map itself, so
+	/// they're called without `this` locked, on a snapshot of the keys with the same hash. If the
+	/// map changed in the meantime, the search is done again.
+	fn locate<G, T>(
+		this: &Object,
+		key: &Object,
+		lock: impl Fn() -> crate::Result<G>,
+		func: impl FnOnce(G, u64, Option<usize>) -> T
+	) -> crate::Result<T>
+	where
+		G: std::ops::Deref<Target=Self>
+	{
+		let hash = hash_key(key)?;
+
+		loop {
+			let candidates = {
+				let map = this.try_downcast::<Self>()?;
+
+				map.indices.get(&hash)
+					.map(|bucket| bucket.iter().map(|&idx| (idx, map.entries[idx].0.clone())).collect())
+					.unwrap_or_else(Vec::new)
+			};
+
+			let mut position = None;
+
+			for (idx, candidate) in &candidates {
+				if candidate.eq_obj(key)? {
+					position = Some(*idx);
+					break;
+				}
+			}
+
+			let map = lock()?;
+
+			if map.bucket_unchanged(hash, &candidates) {
+				return Ok(func(map, hash, position));
+			}
+		}
+	}
+
+	/// Check to see if two maps have the same keys, and each key has an equal value.
+	pub fn eql(&self, rhs: &Self) -> crate::Result<bool> {
+		if self.len() != rhs.len() {
+			return Ok(false);
+		}
+
+		for (key, value) in self.iter() {
+			match rhs.get(key)? {
+				Some(rhs_value) if value.eq_obj(rhs_value)? => {},
+				_ => return Ok(false)
+			}
+		}
+
+		Ok(true)
+	}
+}
+
+impl TryFrom<&Map> for Text {
+	type Error = crate::Error;
+
+	fn try_from(map: &Map) -> crate::Result<Self> {
+		fn inspect(obj: &Object) -> crate::Result<String> {
+			Ok(obj.call_attr_lit(&Literal::INSPECT, &[])?.call_downcast::<Text>()?.to_string())
+		}
+
+		let mut t = Vec::with_capacity(map.len());
+
+		for (key, value) in map.iter() {
+			t.push(format!("{}: {}", inspect(key)?, inspect(value)?));
+		}
+
+		Ok(format!("{{{}}}", t.join(", ")).into())
+	}
+}
+
+impl From<&Map> for Boolean {
+	#[inline]
+	fn from(map: &Map) -> Self {
+		(!map.is_empty()).into()
+	}
+}
+
+/// Quest methods
+impl Map {
+	/// Creates a new map from a list of `[key, value]` pairs.
+	///
+	/// # Arguments
+	///
+	/// Each argument is a (`@list`) pair of `[key, value]`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// map = Map::new(["a", 1], [true, 2]);
+	///
+	/// assert(map["a"] == 1);
+	/// assert(map[true] == 2);
+	/// assert(Map::new().len() == 0);
+	/// ```
+	#[instrument(name="Map::new", level="trace", skip(args), fields(?args))]
+	pub fn qs_new(args: Args) -> crate::Result<Object> {
+		let mut map = Self::new();
+
+		for pair in args.as_ref() {
+			let pair = pair.call_downcast::<List>()?;
+			let key = pair.get(0).cloned().unwrap_or_default();
+			let value = pair.get(1).cloned().unwrap_or_default();
+
+			map.insert(key, value)?;
+		}
+
+		Ok(map.into())
+	}
+
+	/// Simply returns the map.
+	#[instrument(name="Map::@map", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_map(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Get an [`Iter`](crate::types::Iter) over `[key, value]` pairs.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// map = Map::new(["a", 1], ["b", 2]);
+	///
+	/// assert(map.@list() == [["a", 1], ["b", 2]]);
+	/// ```
+	#[instrument(name="Map::@iter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
+		let pairs = this.try_downcast::<Self>()?
+			.iter()
+			.map(|(key, value)| Object::from(vec![key.clone(), value.clone()]))
+			.collect::<Vec<_>>();
+
+		Ok(crate::types::Iter::new(pairs.into_iter().map(Ok)).into())
+	}
+
+	/// Attempts to convert this into a [`Text`].
+	///
+	/// This calls `inspect` on each key and value.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Map::new(["a", 1]).@text() == '{"a": 1}');
+	/// assert(Map::new().@text() == '{}');
+	/// ```
+	#[instrument(name="Map::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Text::try_from(&*this)?.into())
+	}
+
+	/// Attempts to get an internal representation of the map.
+	#[instrument(name="Map::inspect", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_inspect(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_at_text(this, args)
+	}
+
+	/// Converts this into a [`Boolean`].
+	///
+	/// A map is considered to be `false` when it is empty.
+	#[instrument(name="Map::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Boolean::from(&*this).into())
+	}
+
+	#[instrument(name="Map::empty?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_empty_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_empty().into())
+	}
+
+	/// Get the amount of entries in the map.
+	#[instrument(name="Map::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Gets the value associated with a key, or [`Null`](crate::types::Null) if it doesn't exist.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The key to look up.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// map = Map::new([1, "one"]);
+	///
+	/// assert(map[1] == "one");
+	/// assert(map[2] == null);
+	/// ```
+	#[instrument(name="Map::get", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		Self::locate(this, key, || this.try_downcast::<Self>(), |map, _, position|
+			position.map(|idx| map.entries[idx].1.clone()).unwrap_or_default())
+	}
+
+	/// Associates a value with a key, returning the value.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The key.
+	/// 2. (required) The value.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// map = Map::new();
+	///
+	/// map["a"] = 1;
+	/// map.set("b", 2);
+	/// assert(map == Map::new(["a", 1], ["b", 2]));
+	/// ```
+	#[instrument(name="Map::set", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_set(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?.clone();
+		let value = args.try_arg(1)?.clone();
+
+		Self::locate(this, &key.clone(), || this.try_downcast_mut::<Self>(), |mut map, hash, position|
+			map.insert_at(hash, position, key, value.clone()))?;
+
+		Ok(value)
+	}
+
+	/// Removes a key from the map, returning its value or [`Null`](crate::types::Null).
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// map = Map::new(["a", 1]);
+	///
+	/// assert(map.delete("a") == 1);
+	/// assert(map.delete("a") == null);
+	/// assert(!map);
+	/// ```
+	#[instrument(name="Map::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_delete(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		Self::locate(this, key, || this.try_downcast_mut::<Self>(), |mut map, _, position|
+			position.map(|idx| map.remove_at(idx)).unwrap_or_default())
+	}
+
+	/// Checks to see if the map contains a key.
+	#[instrument(name="Map::has?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_has_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		Self::locate(this, key, || this.try_downcast::<Self>(), |_, _, position|
+			position.is_some().into())
+	}
+
+	/// Get a [`List`] of the map's keys, in insertion order.
+	#[instrument(name="Map::keys", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_keys(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.keys().cloned().collect::<List>().into())
+	}
+
+	/// Get a [`List`] of the map's values, in insertion order.
+	#[instrument(name="Map::values", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_values(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.values().cloned().collect::<List>().into())
+	}
+
+	/// Remove all entries from the map and returns the map.
+	#[instrument(name="Map::clear", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_clear(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast_mut::<Self>()?.clear();
+
+		Ok(this.clone())
+	}
+
+	/// Compares two [`Map`]s.
+	///
+	/// Two maps are equal if they have the same keys, and each key's values are equal. The order
+	/// entries were inserted in doesn't matter.
+	#[instrument(name="Map::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+
+		if this.is_identical(rhs) {
+			return Ok(true.into());
+		}
+
+		let this = this.try_downcast::<Self>()?;
+
+		match rhs.downcast::<Self>() {
+			Some(rhs) => this.eql(&rhs).map(Object::from),
+			None => Ok(false.into())
+		}
+	}
+}
+
+impl_object_type!{
+for Map [(init_parent super::Basic super::Iterable) (parents super::Basic) (convert "@map")]:
+	"new" => function Self::qs_new,
+
+	"inspect" => method Self::qs_inspect,
+	"@text" => method Self::qs_at_text,
+	"@bool" => method Self::qs_at_bool,
+	"@map" => method Self::qs_at_map,
+	"@iter" => method Self::qs_at_iter,
+
+	"empty?" => method Self::qs_empty_q,
+	"len" => method Self::qs_len,
+	"has?" => method Self::qs_has_q,
+	"keys" => method Self::qs_keys,
+	"values" => method Self::qs_values,
+	"clear" => method Self::qs_clear,
+
+	"get" => method Self::qs_get,
+	"set" => method Self::qs_set,
+	"[]" => method Self::qs_get,
+	"[]=" => method |this, args| {
+		let key = args.try_arg(0)?.downcast::<List>().expect("`[]=` called without List.")
+			.get(0).cloned().unwrap_or_default();
+
+		Self::qs_set(this, Args::new(vec![&key, args.try_arg(1)?]))
+	},
+	"delete" => method Self::qs_delete,
+
+	"==" => method Self::qs_eql,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn map(pairs: Vec<(Object, Object)>) -> Map {
+		crate::init();
+
+		let mut map = Map::new();
+		for (key, value) in pairs {
+			map.insert(key, value).unwrap();
+		}
+		map
+	}
+
+	#[test]
+	fn insert_and_get() {
+		let mut map = map(vec![("a".into(), 1.into()), (true.into(), 2.into())]);
+
+		assert_eq!(map.len(), 2);
+		assert_eq!(*map.get(&"a".into()).unwrap().unwrap().downcast::<Number>().unwrap(), 1);
+		assert_eq!(*map.get(&true.into()).unwrap().unwrap().downcast::<Number>().unwrap(), 2);
+		assert!(map.get(&"b".into()).unwrap().is_none());
+
+		let old = map.insert("a".into(), 3.into()).unwrap().unwrap();
+		assert_eq!(*old.downcast::<Number>().unwrap(), 1);
+		assert_eq!(map.len(), 2);
+		assert_eq!(*map.get(&"a".into()).unwrap().unwrap().downcast::<Number>().unwrap(), 3);
+	}
+
+	#[test]
+	fn remove() {
+		let mut map = map(vec![
+			("a".into(), 1.into()), ("b".into(), 2.into()), ("c".into(), 3.into())
+		]);
+
+		assert_eq!(*map.remove(&"a".into()).unwrap().unwrap().downcast::<Number>().unwrap(), 1);
+		assert!(map.remove(&"a".into()).unwrap().is_none());
+		assert_eq!(map.len(), 2);
+
+		// make sure the indices were shifted over correctly.
+		assert_eq!(*map.get(&"b".into()).unwrap().unwrap().downcast::<Number>().unwrap(), 2);
+		assert_eq!(*map.get(&"c".into()).unwrap().unwrap().downcast::<Number>().unwrap(), 3);
+	}
+
+	#[test]
+	fn eql() {
+		let lhs = map(vec![("a".into(), 1.into()), ("b".into(), 2.into())]);
+		let rhs = map(vec![("b".into(), 2.into()), ("a".into(), 1.into())]);
+
+		assert!(lhs.eql(&rhs).unwrap());
+		assert!(!lhs.eql(&map(vec![("a".into(), 1.into())])).unwrap());
+		assert!(!lhs.eql(&map(vec![("a".into(), 1.into()), ("b".into(), 3.into())])).unwrap());
+	}
+
+	#[test]
+	fn at_text() {
+		assert_eq!(Text::try_from(&map(vec![])).unwrap(), *"{}");
+		assert_eq!(
+			Text::try_from(&map(vec![("a".into(), 1.into()), (2.into(), true.into())])).unwrap(),
+			*r#"{"a": 1, 2: true}"#
+		);
+	}
+
+	#[test]
+	fn keys_can_use_the_map() {
+		crate::init();
+
+		/// A key whose `hash` and `==` look at the map it's stored in.
+		#[derive(Debug, Clone)]
+		struct Nosy(Object);
+
+		impl_object_type! { for Nosy [(parents crate::types::Basic)]:
+			"hash" => method |this, _| {
+				let map = this.try_downcast::<Nosy>()?.0.clone();
+				let _ = map.try_downcast::<Map>()?.len();
+
+				Ok(0.into())
+			},
+			"==" => method |this, args| {
+				let map = this.try_downcast::<Nosy>()?.0.clone();
+				let _ = map.try_downcast::<Map>()?.len();
+
+				Ok(this.is_identical(args.try_arg(0)?).into())
+			}
+		}
+
+		let map = Object::from(Map::new());
+		let first = Object::from(Nosy(map.clone()));
+		let second = Object::from(Nosy(map.clone()));
+
+		Map::qs_set(&map, args!(first.clone(), 1)).unwrap();
+		Map::qs_set(&map, args!(second.clone(), 2)).unwrap();
+		assert_eq!(map.downcast::<Map>().unwrap().len(), 2);
+
+		assert_eq!(*Map::qs_get(&map, args!(second.clone())).unwrap().downcast::<Number>().unwrap(), 2);
+		assert_eq!(*Map::qs_delete(&map, args!(first.clone())).unwrap().downcast::<Number>().unwrap(), 1);
+		assert!(!Map::qs_has_q(&map, args!(first)).unwrap().downcast::<Boolean>().unwrap().into_inner());
+		assert_eq!(map.downcast::<Map>().unwrap().len(), 1);
+	}
+}
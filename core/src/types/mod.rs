@@ -38,13 +38,28 @@ pub mod rustfn;
 mod null;
 mod class;
 mod text;
+pub mod bytes;
 pub mod boolean;
 pub mod number;
+pub mod rational;
 pub mod regex;
 pub mod io;
+pub mod fs;
+mod path;
 mod list;
-
-mod tcp;
+mod map;
+pub mod exception;
+pub mod frame;
+
+pub mod net;
+pub mod http;
+pub mod process;
+pub mod env;
+pub mod time;
+mod json;
+mod thread;
+mod channel;
+mod mutex;
 
 pub use function::BoundRustFn;
 
@@ -52,7 +67,37 @@ pub use function::BoundRustFn;
 pub use io::Io;
 
 #[doc(inline)]
-pub use tcp::Tcp;
+pub use fs::Fs;
+
+#[doc(inline)]
+pub use path::Path;
+
+#[doc(inline)]
+pub use net::{TcpListener, TcpStream, UdpSocket};
+
+#[doc(inline)]
+pub use http::Http;
+
+#[doc(inline)]
+pub use process::Process;
+
+#[doc(inline)]
+pub use env::{Env, OptParse};
+
+#[doc(inline)]
+pub use time::{Time, Duration, Instant};
+
+#[doc(inline)]
+pub use json::Json;
+
+#[doc(inline)]
+pub use thread::Thread;
+
+#[doc(inline)]
+pub use channel::Channel;
+
+#[doc(inline)]
+pub use mutex::Mutex;
 
 #[doc(inline)]
 pub use class::Class;
@@ -67,7 +112,7 @@ pub use comparable::Comparable;
 pub use pristine::Pristine;
 
 #[doc(inline)]
-pub use kernel::Kernel;
+pub use kernel::{Kernel, KernelGuard};
 
 #[doc(inline)]
 pub use basic::Basic;
@@ -96,12 +141,27 @@ pub use boolean::Boolean;
 #[doc(inline)]
 pub use number::Number;
 
+#[doc(inline)]
+pub use rational::Rational;
+
 #[doc(inline)]
 pub use text::Text;
 
+#[doc(inline)]
+pub use bytes::Bytes;
+
 #[doc(inline)]
 pub use list::List;
 
+#[doc(inline)]
+pub use map::Map;
+
+#[doc(inline)]
+pub use exception::Exception;
+
+#[doc(inline)]
+pub use frame::{Frame, SourceLocation};
+
 #[doc(inline)]
 pub use self::regex::Regex;
 
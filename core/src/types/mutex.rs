@@ -0,0 +1,117 @@
+//! The [`Mutex`] type in Quest.
+
+use crate::{Object, Args, Literal};
+use parking_lot::ReentrantMutex;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// A lock that ensures only one [`Thread`](crate::types::Thread) runs a section of code at once.
+///
+/// The lock is reentrant, so a thread that already holds it can `synchronize` again without
+/// deadlocking.
+#[derive(Clone, Default)]
+pub struct Mutex(Arc<ReentrantMutex<()>>);
+
+impl Debug for Mutex {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_struct("Mutex")
+			.field("locked", &self.is_locked())
+			.finish()
+	}
+}
+
+impl Mutex {
+	/// Runs `func` while holding the lock, releasing it afterwards even if `func` fails.
+	pub fn synchronize<T>(&self, func: impl FnOnce() -> T) -> T {
+		let _guard = self.0.lock();
+
+		func()
+	}
+
+	/// Checks to see if any thread is holding the lock.
+	pub fn is_locked(&self) -> bool {
+		self.0.is_locked()
+	}
+}
+
+/// Quest methods
+impl Mutex {
+	/// Creates a new, unlocked mutex.
+	#[instrument(name="Mutex::new", level="trace")]
+	pub fn qs_new(_: Args) -> crate::Result<Object> {
+		Ok(Self::default().into())
+	}
+
+	/// Calls the block while holding the lock, returning the block's result.
+	///
+	/// # Arguments
+	/// 1. (required, `()`) The block to call.
+	/// 2. (optional, rest) Arguments to pass to the block.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// lock = Mutex::new();
+	/// count = 0;
+	///
+	/// threads = 1.upto(9).map({ spawn({ lock.synchronize({ count += 1 }) }) });
+	/// threads.each({ _0.join() });
+	///
+	/// assert(count == 9);
+	/// ```
+	#[instrument(name="Mutex::synchronize", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_synchronize(this: &Object, args: Args) -> crate::Result<Object> {
+		let block = args.try_arg(0)?;
+		let rest = args.args(1..).unwrap_or_default();
+		let mutex = this.try_downcast::<Self>()?.clone();
+
+		mutex.synchronize(|| block.call_attr_lit(&Literal::CALL, rest))
+	}
+
+	/// Checks to see if any thread is holding the lock.
+	#[instrument(name="Mutex::locked?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_locked_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_locked().into())
+	}
+
+	#[instrument(name="Mutex::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("{:?}", *this.try_downcast::<Self>()?).into())
+	}
+}
+
+impl_object_type!{
+for Mutex [(parents super::Basic)]:
+	"new" => function Self::qs_new,
+	"synchronize" => method Self::qs_synchronize,
+	"locked?" => method Self::qs_locked_q,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	#[test]
+	fn synchronize_is_exclusive_and_reentrant() {
+		let mutex = Mutex::default();
+		let count = Arc::new(AtomicUsize::new(0));
+
+		let threads = (0..8).map(|_| {
+			let (mutex, count) = (mutex.clone(), count.clone());
+
+			std::thread::spawn(move || mutex.synchronize(|| {
+				let before = count.load(Ordering::SeqCst);
+				std::thread::yield_now();
+				count.store(before + 1, Ordering::SeqCst);
+			}))
+		}).collect::<Vec<_>>();
+
+		threads.into_iter().for_each(|thread| thread.join().unwrap());
+		assert_eq!(count.load(Ordering::SeqCst), 8);
+
+		assert!(mutex.synchronize(|| mutex.synchronize(|| mutex.is_locked())));
+		assert!(!mutex.is_locked());
+	}
+}
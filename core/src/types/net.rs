@@ -0,0 +1,74 @@
+//! Networking over TCP and UDP.
+//!
+//! Data is received as [`Text`], with anything that isn't valid UTF-8 replaced with `U+FFFD`, unless
+//! one of the `_bytes` methods is used to get [`Bytes`](crate::types::Bytes) instead. Both can be sent.
+
+mod tcp_listener;
+mod tcp_stream;
+mod udp_socket;
+
+pub use tcp_listener::TcpListener;
+pub use tcp_stream::TcpStream;
+pub use udp_socket::UdpSocket;
+
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Number, Null};
+use std::convert::TryFrom;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+/// Gets the socket addresses given by the arguments starting at `start`.
+///
+/// Addresses can either be given as a single `"host:port"`, or as a host followed by a port.
+fn socket_addrs(args: &Args, start: usize) -> crate::Result<Vec<SocketAddr>> {
+	let host = args.try_arg(start)?.call_downcast::<Text>()?.clone();
+
+	let addrs =
+		if let Some(port) = args.arg(start + 1) {
+			let port = port.call_downcast::<Number>()?.clone();
+			let port = i64::try_from(port.clone()).ok()
+				.and_then(|port| u16::try_from(port).ok())
+				.ok_or_else(|| ValueError::Messaged(format!("invalid port: {}", port)))?;
+
+			(host.as_ref(), port).to_socket_addrs()?.collect()
+		} else {
+			host.as_ref().to_socket_addrs()?.collect()
+		};
+
+	Ok(addrs)
+}
+
+/// Converts an amount of bytes to read, which must be a nonnegative integer.
+pub(crate) fn byte_count(arg: &Object) -> crate::Result<usize> {
+	let amnt = arg.call_downcast::<Number>()?.clone();
+
+	i64::try_from(amnt.clone()).ok()
+		.and_then(|amnt| usize::try_from(amnt).ok())
+		.ok_or_else(|| ValueError::Messaged(format!("invalid byte count: {}", amnt)).into())
+}
+
+/// Converts a timeout argument, in seconds, to a [`Duration`]; `null` means no timeout.
+pub(crate) fn timeout(arg: &Object) -> crate::Result<Option<Duration>> {
+	if arg.is_a::<Null>() {
+		return Ok(None);
+	}
+
+	let secs = f64::from(arg.call_downcast::<Number>()?.clone());
+
+	if secs.is_finite() && secs > 0.0 {
+		Ok(Some(Duration::from_secs_f64(secs)))
+	} else {
+		Err(ValueError::Messaged(format!("invalid timeout: {}", secs)).into())
+	}
+}
+
+/// Converts a socket address to a `"host:port"` [`Text`].
+fn addr_to_object(addr: SocketAddr) -> Object {
+	Text::from(addr.to_string()).into()
+}
+
+/// Converts received bytes to a [`Text`].
+fn bytes_to_object(bytes: &[u8]) -> Object {
+	Text::from(String::from_utf8_lossy(bytes).into_owned()).into()
+}
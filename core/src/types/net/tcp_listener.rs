@@ -0,0 +1,104 @@
+use crate::{Object, Args};
+use super::TcpStream;
+use std::convert::TryFrom;
+use std::io;
+use std::net::{self, SocketAddr};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// A socket that listens for incoming [`TcpStream`] connections.
+#[derive(Debug, Clone)]
+pub struct TcpListener(Arc<net::TcpListener>);
+
+impl TcpListener {
+	/// Listens on the first of `addrs` that can be bound to.
+	pub fn bind(addrs: &[SocketAddr]) -> io::Result<Self> {
+		net::TcpListener::bind(addrs).map(Arc::new).map(Self)
+	}
+
+	/// Waits for a new connection.
+	pub fn accept(&self) -> io::Result<TcpStream> {
+		TcpStream::try_from(self.0.accept()?.0)
+	}
+
+	/// The address that's being listened on.
+	pub fn local_addr(&self) -> io::Result<SocketAddr> {
+		self.0.local_addr()
+	}
+}
+
+/// Quest methods
+impl TcpListener {
+	/// Starts listening on an address.
+	///
+	/// Use a port of `0` to have the operating system pick an unused one.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) Either `"host:port"`, or just the host if the port is given.
+	/// 2. (optional, `@num`) The port.
+	#[instrument(name="TcpListener::bind", level="trace", skip(args), fields(?args))]
+	pub fn qs_bind(args: Args) -> crate::Result<Object> {
+		crate::sandbox::require(crate::sandbox::Capability::NETWORK, "TcpListener::bind")?;
+
+		let addrs = super::socket_addrs(&args, 0)?;
+
+		Ok(Self::bind(&addrs)?.into())
+	}
+
+	/// Waits for a new connection, returning a `TcpStream` for it.
+	#[instrument(name="TcpListener::accept", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_accept(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.accept()?.into())
+	}
+
+	/// Gets the `"host:port"` that's being listened on.
+	#[instrument(name="TcpListener::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(super::addr_to_object(this.try_downcast::<Self>()?.local_addr()?))
+	}
+
+	#[instrument(name="TcpListener::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let addr = this.try_downcast::<Self>()?.local_addr()?;
+
+		Ok(format!("TcpListener({})", addr).into())
+	}
+}
+
+impl_object_type!{
+for TcpListener [(parents crate::types::Basic)]:
+	"bind" => function Self::qs_bind,
+	"accept" => method Self::qs_accept,
+	"local_addr" => method Self::qs_local_addr,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn accepts_connections() {
+		let listener = TcpListener::bind(&["127.0.0.1:0".parse().unwrap()]).unwrap();
+		let addr = listener.local_addr().unwrap();
+
+		let client = std::thread::spawn(move || TcpStream::connect(&[addr]).unwrap().write_all(b"hi\n"));
+		let server = listener.accept().unwrap();
+
+		client.join().unwrap().unwrap();
+		assert_eq!(server.read_line().unwrap().unwrap(), b"hi\n");
+	}
+
+	#[test]
+	fn bind_errors_are_io_errors() {
+		crate::init();
+
+		let listener = TcpListener::bind(&["127.0.0.1:0".parse().unwrap()]).unwrap();
+		let addr = listener.local_addr().unwrap().to_string();
+
+		assert_matches!(TcpListener::qs_bind(args!(addr)).unwrap_err(), crate::Error::IoError(..));
+		assert_matches!(TcpListener::qs_bind(args!("127.0.0.1", 70000)).unwrap_err(), crate::Error::ValueError(..));
+	}
+}
@@ -0,0 +1,366 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Bytes};
+use parking_lot::Mutex;
+use std::convert::TryFrom;
+use std::io::{self, Read, Write, BufRead, BufReader};
+use std::net::{self, SocketAddr, Shutdown};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+
+/// The most bytes a single call to [`TcpStream::read_up_to`] reads, so the buffer doesn't have to
+/// be as large as the amount asked for.
+const MAX_READ_SIZE: usize = 64 * 1024;
+
+#[derive(Debug)]
+struct Inner {
+	stream: net::TcpStream,
+	// reads are buffered so `read_line` doesn't lose data; writes go directly to `stream`.
+	reader: Mutex<BufReader<net::TcpStream>>
+}
+
+/// A connection to a remote socket.
+///
+/// Reading and writing can happen from separate threads at the same time.
+#[derive(Debug, Clone)]
+pub struct TcpStream(Arc<Inner>);
+
+impl TryFrom<net::TcpStream> for TcpStream {
+	type Error = io::Error;
+
+	fn try_from(stream: net::TcpStream) -> io::Result<Self> {
+		let reader = Mutex::new(BufReader::new(stream.try_clone()?));
+
+		Ok(Self(Arc::new(Inner { stream, reader })))
+	}
+}
+
+impl TcpStream {
+	/// Connects to the first of `addrs` that accepts the connection.
+	pub fn connect(addrs: &[SocketAddr]) -> io::Result<Self> {
+		Self::try_from(net::TcpStream::connect(addrs)?)
+	}
+
+	/// Reads everything until the remote end shuts down its writing half.
+	pub fn read_to_end(&self) -> io::Result<Vec<u8>> {
+		let mut buf = Vec::new();
+		self.0.reader.lock().read_to_end(&mut buf)?;
+		Ok(buf)
+	}
+
+	/// Reads at most `amnt` bytes, returning `None` at end of stream.
+	///
+	/// Like [`Read::read`], fewer bytes may be read, even if more are coming.
+	pub fn read_up_to(&self, amnt: usize) -> io::Result<Option<Vec<u8>>> {
+		let mut buf = vec![0; amnt.min(MAX_READ_SIZE)];
+		let read = self.0.reader.lock().read(&mut buf)?;
+
+		if read == 0 && amnt != 0 {
+			Ok(None)
+		} else {
+			buf.truncate(read);
+			Ok(Some(buf))
+		}
+	}
+
+	/// Reads exactly `amnt` bytes, returning an error if the stream ends first.
+	pub fn read_exact(&self, amnt: usize) -> io::Result<Vec<u8>> {
+		let mut buf = Vec::new();
+
+		// the buffer only grows as data arrives, instead of being allocated up front.
+		(&mut *self.0.reader.lock()).take(amnt as u64).read_to_end(&mut buf)?;
+
+		if buf.len() == amnt {
+			Ok(buf)
+		} else {
+			Err(io::Error::new(io::ErrorKind::UnexpectedEof, "stream ended before enough bytes were read"))
+		}
+	}
+
+	/// Reads up to and including the next `\n`, returning `None` at end of stream.
+	pub fn read_line(&self) -> io::Result<Option<Vec<u8>>> {
+		let mut buf = Vec::new();
+
+		if self.0.reader.lock().read_until(b'\n', &mut buf)? == 0 {
+			Ok(None)
+		} else {
+			Ok(Some(buf))
+		}
+	}
+
+	/// Writes all of `data` to the stream.
+	pub fn write_all(&self, data: &[u8]) -> io::Result<()> {
+		(&self.0.stream).write_all(data)
+	}
+
+	/// Flushes any buffered output.
+	pub fn flush(&self) -> io::Result<()> {
+		(&self.0.stream).flush()
+	}
+
+	/// Shuts down the reading half, writing half, or both halves of the connection.
+	pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+		self.0.stream.shutdown(how)
+	}
+
+	/// Sets how long reads and writes wait before failing, or `None` to wait forever.
+	pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+		self.0.stream.set_read_timeout(timeout)?;
+		self.0.stream.set_write_timeout(timeout)
+	}
+
+	/// The address of the remote end.
+	pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+		self.0.stream.peer_addr()
+	}
+
+	/// The address of the local end.
+	pub fn local_addr(&self) -> io::Result<SocketAddr> {
+		self.0.stream.local_addr()
+	}
+}
+
+/// Quest methods
+impl TcpStream {
+	/// Connects to a remote socket.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) Either `"host:port"`, or just the host if the port is given.
+	/// 2. (optional, `@num`) The port.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// listener = TcpListener::bind("127.0.0.1", 0);
+	/// client = TcpStream::connect(listener.local_addr());
+	/// server = listener.accept();
+	///
+	/// client.write("hello\n");
+	/// assert(server.read_line() == "hello\n");
+	/// ```
+	#[instrument(name="TcpStream::connect", level="trace", skip(args), fields(?args))]
+	pub fn qs_connect(args: Args) -> crate::Result<Object> {
+		crate::sandbox::require(crate::sandbox::Capability::NETWORK, "TcpStream::connect")?;
+
+		let addrs = super::socket_addrs(&args, 0)?;
+
+		Ok(Self::connect(&addrs)?.into())
+	}
+
+	/// Reads from the stream.
+	///
+	/// If no amount is given, everything until the end of the stream is read. Otherwise, at most
+	/// that many bytes are read, and `null` is returned at the end of the stream.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The maximum amount of bytes to read.
+	#[instrument(name="TcpStream::read", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_read(this: &Object, args: Args) -> crate::Result<Object> {
+		let amnt = args.arg(0).map(super::byte_count).transpose()?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		match amnt {
+			None => Ok(super::bytes_to_object(&this.read_to_end()?)),
+			Some(amnt) => Ok(this.read_up_to(amnt)?
+				.map(|bytes| super::bytes_to_object(&bytes))
+				.unwrap_or_default())
+		}
+	}
+
+	/// Like `read`, except the data is returned as [`Bytes`] instead of text.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The maximum amount of bytes to read.
+	#[instrument(name="TcpStream::read_bytes", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_read_bytes(this: &Object, args: Args) -> crate::Result<Object> {
+		let amnt = args.arg(0).map(super::byte_count).transpose()?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		match amnt {
+			None => Ok(Bytes::from(this.read_to_end()?).into()),
+			Some(amnt) => Ok(this.read_up_to(amnt)?.map(|bytes| Bytes::from(bytes).into()).unwrap_or_default())
+		}
+	}
+
+	/// Reads the next line, including the trailing `\n`, or returns `null` at the end of the stream.
+	#[instrument(name="TcpStream::read_line", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_read_line(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.read_line()?.map(|bytes| super::bytes_to_object(&bytes)).unwrap_or_default())
+	}
+
+	/// Reads exactly the given amount of bytes, raising an `IoError` if the stream ends first.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The amount of bytes to read.
+	#[instrument(name="TcpStream::read_exact", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_read_exact(this: &Object, args: Args) -> crate::Result<Object> {
+		let amnt = super::byte_count(args.try_arg(0)?)?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(super::bytes_to_object(&this.read_exact(amnt)?))
+	}
+
+	/// Writes the first argument to the stream, returning the amount of bytes written.
+	///
+	/// # Arguments
+	/// 1. (required, `@text` or `Bytes`) The data to write.
+	#[instrument(name="TcpStream::write", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_write(this: &Object, args: Args) -> crate::Result<Object> {
+		let data = Bytes::data_of(args.try_arg(0)?)?;
+
+		this.try_downcast::<Self>()?.clone().write_all(&data)?;
+		Ok(data.len().into())
+	}
+
+	/// Flushes any buffered output, returning `this`.
+	#[instrument(name="TcpStream::flush", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_flush(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast::<Self>()?.flush()?;
+		Ok(this.clone())
+	}
+
+	/// Shuts down part of the connection, returning `this`.
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) Which half to shut down: `"read"`, `"write"`, or `"both"` (the default).
+	#[instrument(name="TcpStream::shutdown", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_shutdown(this: &Object, args: Args) -> crate::Result<Object> {
+		let how =
+			match args.arg(0).map(|how| how.call_downcast::<Text>().map(|how| how.clone())).transpose()? {
+				None => Shutdown::Both,
+				Some(how) => match how.as_ref() {
+					"read" => Shutdown::Read,
+					"write" => Shutdown::Write,
+					"both" => Shutdown::Both,
+					other => return Err(ValueError::Messaged(format!("unknown shutdown kind: {:?}", other)).into())
+				}
+			};
+
+		this.try_downcast::<Self>()?.shutdown(how)?;
+		Ok(this.clone())
+	}
+
+	/// Sets how long reads and writes wait before raising an `IoError`; `this` is returned.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or `null`) The timeout in seconds, or `null` to wait forever.
+	#[instrument(name="TcpStream::set_timeout", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_set_timeout(this: &Object, args: Args) -> crate::Result<Object> {
+		let timeout = super::timeout(args.try_arg(0)?)?;
+
+		this.try_downcast::<Self>()?.set_timeout(timeout)?;
+		Ok(this.clone())
+	}
+
+	/// Gets the `"host:port"` of the remote end.
+	#[instrument(name="TcpStream::peer_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_peer_addr(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(super::addr_to_object(this.try_downcast::<Self>()?.peer_addr()?))
+	}
+
+	/// Gets the `"host:port"` of the local end.
+	#[instrument(name="TcpStream::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(super::addr_to_object(this.try_downcast::<Self>()?.local_addr()?))
+	}
+
+	#[instrument(name="TcpStream::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(match this.peer_addr() {
+			Ok(addr) => format!("TcpStream({})", addr),
+			Err(_) => "TcpStream(<disconnected>)".to_string()
+		}.into())
+	}
+}
+
+impl_object_type!{
+for TcpStream [(parents crate::types::Basic)]:
+	"connect" => function Self::qs_connect,
+
+	"read" => method Self::qs_read,
+	"read_bytes" => method Self::qs_read_bytes,
+	"read_line" => method Self::qs_read_line,
+	"read_exact" => method Self::qs_read_exact,
+	"write" => method Self::qs_write,
+	"flush" => method Self::qs_flush,
+	"shutdown" => method Self::qs_shutdown,
+	"set_timeout" => method Self::qs_set_timeout,
+	"peer_addr" => method Self::qs_peer_addr,
+	"local_addr" => method Self::qs_local_addr,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn pair() -> (TcpStream, TcpStream) {
+		let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let client = TcpStream::connect(&[listener.local_addr().unwrap()]).unwrap();
+		let server = TcpStream::try_from(listener.accept().unwrap().0).unwrap();
+
+		(client, server)
+	}
+
+	#[test]
+	fn reads_and_writes() {
+		let (client, server) = pair();
+
+		client.write_all(b"first\nsecond\nthird").unwrap();
+		client.shutdown(Shutdown::Write).unwrap();
+
+		assert_eq!(server.read_line().unwrap().unwrap(), b"first\n");
+		assert_eq!(server.read_exact(3).unwrap(), b"sec");
+		assert_eq!(server.read_line().unwrap().unwrap(), b"ond\n");
+		assert_eq!(server.read_to_end().unwrap(), b"third");
+		assert_eq!(server.read_line().unwrap(), None);
+		assert_eq!(server.read_up_to(10).unwrap(), None);
+		assert_eq!(server.read_exact(1).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn huge_reads_are_bounded() {
+		let (client, server) = pair();
+
+		client.write_all(b"data").unwrap();
+		client.shutdown(Shutdown::Write).unwrap();
+
+		// neither of these should try to allocate `usize::MAX` bytes.
+		assert_eq!(server.read_up_to(usize::MAX).unwrap().unwrap(), b"data");
+		assert_eq!(server.read_exact(usize::MAX).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn addresses() {
+		let (client, server) = pair();
+
+		assert_eq!(client.peer_addr().unwrap(), server.local_addr().unwrap());
+		assert_eq!(client.local_addr().unwrap(), server.peer_addr().unwrap());
+	}
+
+	#[test]
+	fn timeouts() {
+		let (client, _server) = pair();
+
+		client.set_timeout(Some(Duration::from_millis(10))).unwrap();
+		let err = client.read_up_to(1).unwrap_err();
+		assert!(matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut));
+	}
+
+	#[test]
+	fn errors_are_io_errors() {
+		crate::init();
+
+		// nothing should be listening on a port we just released.
+		let addr = net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+		assert_matches!(
+			TcpStream::qs_connect(args!(addr.to_string())).unwrap_err(),
+			crate::Error::IoError(..)
+		);
+	}
+}
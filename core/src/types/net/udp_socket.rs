@@ -0,0 +1,172 @@
+use crate::{Object, Args};
+use crate::types::{List, Bytes};
+use std::io;
+use std::net::{self, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+
+/// The largest payload a UDP datagram can carry.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// A socket for sending and receiving UDP datagrams.
+#[derive(Debug, Clone)]
+pub struct UdpSocket(Arc<net::UdpSocket>);
+
+impl UdpSocket {
+	/// Binds to the first of `addrs` that can be bound to.
+	pub fn bind(addrs: &[SocketAddr]) -> io::Result<Self> {
+		net::UdpSocket::bind(addrs).map(Arc::new).map(Self)
+	}
+
+	/// Sends `data` to the first of `addrs`, returning the amount of bytes sent.
+	pub fn send_to(&self, data: &[u8], addrs: &[SocketAddr]) -> io::Result<usize> {
+		self.0.send_to(data, addrs)
+	}
+
+	/// Waits for a datagram of at most `max` bytes, returning it and who sent it.
+	///
+	/// Since datagrams can't be larger than [`MAX_DATAGRAM_SIZE`], larger values of `max` are
+	/// treated as it.
+	pub fn recv_from(&self, max: usize) -> io::Result<(Vec<u8>, SocketAddr)> {
+		let mut buf = vec![0; max.min(MAX_DATAGRAM_SIZE)];
+		let (amnt, addr) = self.0.recv_from(&mut buf)?;
+		buf.truncate(amnt);
+
+		Ok((buf, addr))
+	}
+
+	/// Sets how long receiving and sending wait before failing, or `None` to wait forever.
+	pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+		self.0.set_read_timeout(timeout)?;
+		self.0.set_write_timeout(timeout)
+	}
+
+	/// The address that's bound to.
+	pub fn local_addr(&self) -> io::Result<SocketAddr> {
+		self.0.local_addr()
+	}
+}
+
+/// Quest methods
+impl UdpSocket {
+	/// Binds a socket to an address.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) Either `"host:port"`, or just the host if the port is given.
+	/// 2. (optional, `@num`) The port.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// server = UdpSocket::bind("127.0.0.1", 0);
+	/// client = UdpSocket::bind("127.0.0.1:0");
+	///
+	/// client.send_to("ping", server.local_addr());
+	/// message = server.recv_from();
+	/// assert(message.get(0) == "ping");
+	/// assert(message.get(1) == client.local_addr());
+	/// ```
+	#[instrument(name="UdpSocket::bind", level="trace", skip(args), fields(?args))]
+	pub fn qs_bind(args: Args) -> crate::Result<Object> {
+		crate::sandbox::require(crate::sandbox::Capability::NETWORK, "UdpSocket::bind")?;
+
+		let addrs = super::socket_addrs(&args, 0)?;
+
+		Ok(Self::bind(&addrs)?.into())
+	}
+
+	/// Sends a datagram, returning the amount of bytes sent.
+	///
+	/// # Arguments
+	/// 1. (required, `@text` or `Bytes`) The data to send.
+	/// 2. (required, `@text`) Either `"host:port"`, or just the host if the port is given.
+	/// 3. (optional, `@num`) The port.
+	#[instrument(name="UdpSocket::send_to", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_send_to(this: &Object, args: Args) -> crate::Result<Object> {
+		let data = Bytes::data_of(args.try_arg(0)?)?;
+		let addrs = super::socket_addrs(&args, 1)?;
+
+		Ok(this.try_downcast::<Self>()?.send_to(&data, &addrs)?.into())
+	}
+
+	/// Waits for a datagram, returning a list of its contents and the `"host:port"` that sent it.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The maximum size of the datagram; any extra is discarded.
+	#[instrument(name="UdpSocket::recv_from", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_recv_from(this: &Object, args: Args) -> crate::Result<Object> {
+		let max = args.arg(0).map(super::byte_count).transpose()?.unwrap_or(MAX_DATAGRAM_SIZE);
+		let this = this.try_downcast::<Self>()?.clone();
+		let (data, addr) = this.recv_from(max)?;
+
+		Ok(List::from(vec![super::bytes_to_object(&data), super::addr_to_object(addr)]).into())
+	}
+
+	/// Sets how long receiving and sending wait before raising an `IoError`; `this` is returned.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or `null`) The timeout in seconds, or `null` to wait forever.
+	#[instrument(name="UdpSocket::set_timeout", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_set_timeout(this: &Object, args: Args) -> crate::Result<Object> {
+		let timeout = super::timeout(args.try_arg(0)?)?;
+
+		this.try_downcast::<Self>()?.set_timeout(timeout)?;
+		Ok(this.clone())
+	}
+
+	/// Gets the `"host:port"` that's bound to.
+	#[instrument(name="UdpSocket::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(super::addr_to_object(this.try_downcast::<Self>()?.local_addr()?))
+	}
+
+	#[instrument(name="UdpSocket::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let addr = this.try_downcast::<Self>()?.local_addr()?;
+
+		Ok(format!("UdpSocket({})", addr).into())
+	}
+}
+
+impl_object_type!{
+for UdpSocket [(parents crate::types::Basic)]:
+	"bind" => function Self::qs_bind,
+	"send_to" => method Self::qs_send_to,
+	"recv_from" => method Self::qs_recv_from,
+	"set_timeout" => method Self::qs_set_timeout,
+	"local_addr" => method Self::qs_local_addr,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn send_and_recv() {
+		let server = UdpSocket::bind(&["127.0.0.1:0".parse().unwrap()]).unwrap();
+		let client = UdpSocket::bind(&["127.0.0.1:0".parse().unwrap()]).unwrap();
+
+		assert_eq!(client.send_to(b"ping", &[server.local_addr().unwrap()]).unwrap(), 4);
+		let (data, from) = server.recv_from(MAX_DATAGRAM_SIZE).unwrap();
+		assert_eq!(data, b"ping");
+		assert_eq!(from, client.local_addr().unwrap());
+
+		// datagrams larger than the buffer are truncated.
+		server.send_to(b"pong!", &[from]).unwrap();
+		assert_eq!(client.recv_from(4).unwrap().0, b"pong");
+
+		// and larger buffers are capped at the largest possible datagram.
+		client.send_to(b"ping", &[server.local_addr().unwrap()]).unwrap();
+		assert_eq!(server.recv_from(usize::MAX).unwrap().0, b"ping");
+	}
+
+	#[test]
+	fn recv_times_out() {
+		let socket = UdpSocket::bind(&["127.0.0.1:0".parse().unwrap()]).unwrap();
+		socket.set_timeout(Some(Duration::from_millis(10))).unwrap();
+
+		let err = socket.recv_from(1).unwrap_err();
+		assert!(matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut));
+	}
+}
@@ -5,14 +5,20 @@ use std::fmt::{self, Debug, Display, Formatter};
 use std::cmp::Ordering;
 use std::ops;
 use crate::{Object, Args};
-use crate::types::{Text, Boolean, Convertible};
+use crate::types::{Text, Boolean, Convertible, Rational};
+use crate::types::rational::BinaryOp;
 use std::hash::{Hash, Hasher};
 use crate::error::{TypeError, ValueError};
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Signed, Zero};
 use tracing::instrument;
 
 /// The type used by [`Number`] to keep track of integers.
 pub type IntegerType = i64;
 
+/// The type used by [`Number`] to keep track of integers that don't fit within [`IntegerType`].
+pub type BigIntType = BigInt;
+
 /// The type used by [`Number`] to keep track of floats.
 pub type FloatType = f64;
 
@@ -21,16 +27,22 @@ pub type FloatType = f64;
 /// There's only one struct because there's no distinction between integers and floats within Quest.
 /// Because of this, most functions that require integers will [truncate](#floor) floating point
 /// numbers, but the bitwise operations will raise [`NotAnInteger`] if performed with non-integers.
-#[derive(Clone, Copy)]
+///
+/// Integer arithmetic never overflows: results that don't fit within an [`IntegerType`] are
+/// promoted to a [`BigIntType`], and are demoted back once they fit again.
+///
+/// Arithmetic and comparisons with a [`Rational`] are exact, unless `self` is a float.
+#[derive(Clone)]
 pub struct Number(Inner);
 
 // note: to ensure consistancy, there won't ever be a `Float` that has an integer within it;
-// all integer `FloatType`s (eg `2.0`) are converted to `IntegerType` first.
-#[derive(Clone, Copy)]
+// all integer `FloatType`s (eg `2.0`) are converted to `IntegerType` first. Likewise, a `BigInt`
+// will never contain a value that would fit within an `IntegerType`.
+#[derive(Clone)]
 enum Inner {
 	Integer(IntegerType),
+	BigInt(BigIntType),
 	Float(FloatType),
-	// (In the future, I intend to add in a "large number" variant)
 	// should we add a "not a number" variant here?
 }
 
@@ -38,8 +50,9 @@ impl Eq for Number {}
 
 impl PartialEq for Number {
 	fn eq(&self, rhs: &Self) -> bool {
-		match (self.0, rhs.0) {
+		match (&self.0, &rhs.0) {
 			(Inner::Integer(l), Inner::Integer(r)) => l == r,
+			(Inner::BigInt(l), Inner::BigInt(r)) => l == r,
 			(Inner::Float(l), Inner::Float(r)) => l == r,
 			_ => false
 		}
@@ -50,7 +63,8 @@ impl Hash for Number {
 	fn hash<H: Hasher>(&self, h: &mut H) {
 		match self.0 {
 			Inner::Integer(i) => { 0i8.hash(h); i.hash(h) },
-			Inner::Float(f) => { 1i8.hash(h); f.to_bits().hash(h) }
+			Inner::Float(f) => { 1i8.hash(h); f.to_bits().hash(h) },
+			Inner::BigInt(ref b) => { 2i8.hash(h); b.hash(h) }
 		}
 	}
 }
@@ -68,6 +82,7 @@ impl Debug for Number {
 		if f.alternate() {
 			match self.0 {
 				Inner::Integer(n) => f.debug_tuple("Number").field(&n).finish(),
+				Inner::BigInt(ref n) => f.debug_tuple("Number").field(n).finish(),
 				Inner::Float(n) => f.debug_tuple("Number").field(&n).finish(),
 			}
 		} else {
@@ -80,6 +95,7 @@ impl Display for Number {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self.0 {
 			Inner::Integer(n) => Display::fmt(&n, f),
+			Inner::BigInt(ref n) => Display::fmt(n, f),
 			Inner::Float(n) => Display::fmt(&n, f),
 		}
 	}
@@ -110,19 +126,40 @@ impl Number {
 		num.into()
 	}
 
+	/// Checks to see if `self` is a whole number.
+	#[inline]
+	pub fn is_integer(&self) -> bool {
+		!matches!(self.0, Inner::Float(_))
+	}
+
+	/// Checks to see if `self` is too large to fit within an [`IntegerType`].
+	#[inline]
+	pub fn is_big(&self) -> bool {
+		matches!(self.0, Inner::BigInt(_))
+	}
+
+	/// Converts `self` into a [`BigIntType`], returning [`NotAnInteger`] if it's not an integer.
+	pub fn try_to_big(&self) -> Result<BigIntType, NotAnInteger> {
+		match self.0 {
+			Inner::Integer(i) => Ok(i.into()),
+			Inner::BigInt(ref b) => Ok(b.clone()),
+			Inner::Float(f) => Err(NotAnInteger(f))
+		}
+	}
+
 	/// Rounds `self` to the next highest integer (nothing's done if `self` is an integer).
 	pub fn ceil(&self) -> Self {
 		match self.0 {
-			Inner::Integer(..) => *self,
-			Inner::Float(f) => f.ceil().into()
+			Inner::Float(f) => f.ceil().into(),
+			_ => self.clone()
 		}
 	}
 
 	/// Rounds `self` to the next lowest integer (nothing's done if `self` is an integer).
 	pub fn floor(&self) -> Self {
 		match self.0 {
-			Inner::Integer(..) => *self,
-			Inner::Float(f) => f.floor().into()
+			Inner::Float(f) => f.floor().into(),
+			_ => self.clone()
 		}
 	}
 
@@ -133,28 +170,40 @@ impl Number {
 	/// Rounds `self` to the nearest integer (nothing's done if `self` is an integer).
 	pub fn round(&self) -> Self {
 		match self.0 {
-			Inner::Integer(..) => *self,
-			Inner::Float(f) => f.round().into()
+			Inner::Float(f) => f.round().into(),
+			_ => self.clone()
 		}
 	}
 
 	/// Returns the absolute value of `self`.
 	pub fn abs(&self) -> Self {
 		match self.0 {
-			Inner::Integer(i) => i.abs().into(),
+			Inner::Integer(i) => i.checked_abs()
+				.map(Self::from)
+				.unwrap_or_else(|| BigIntType::from(i).abs().into()),
+			Inner::BigInt(ref b) => b.abs().into(),
 			Inner::Float(f) => f.abs().into()
 		}
 	}
 
 	/// Try to parse a [`Number`] from the input with the given radix.
 	pub fn from_str_radix(inp: &str, radix: u32) -> Result<Self, FromStrError> {
+		use std::num::IntErrorKind;
+
 		if radix < 2 || radix > 36 {
 			return Err(FromStrError::BadRadix(radix))
 		}
 
-		IntegerType::from_str_radix(inp.trim(), radix)
-			.map(Self::from)
-			.map_err(FromStrError::BadInteger)
+		let inp = inp.trim();
+
+		match IntegerType::from_str_radix(inp, radix) {
+			Ok(int) => Ok(int.into()),
+			Err(err) if matches!(err.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) =>
+				BigIntType::parse_bytes(inp.as_bytes(), radix)
+					.map(Self::from)
+					.ok_or(FromStrError::BadInteger(err)),
+			Err(err) => Err(FromStrError::BadInteger(err))
+		}
 	}
 
 	/// Converts a [`Number`] into a string with the given radix.
@@ -163,14 +212,14 @@ impl Number {
 			return Err(ToStringRadixError::InvalidRadix(radix))
 		}
 
-		let this = IntegerType::try_from(*self).map_err(ToStringRadixError::NotAnInteger)?;
-
-		match radix {
-			2 => Ok(format!("{:b}", this)),
-			8 => Ok(format!("{:o}", this)),
-			16 => Ok(format!("{:x}", this)),
-			10 => Ok(format!("{}", this)),
-			other => todo!("unsupported radix {}", other),
+		match (&self.0, radix) {
+			(Inner::Integer(this), 2) => Ok(format!("{:b}", this)),
+			(Inner::Integer(this), 8) => Ok(format!("{:o}", this)),
+			(Inner::Integer(this), 16) => Ok(format!("{:x}", this)),
+			(Inner::Integer(this), 10) => Ok(format!("{}", this)),
+			(Inner::Integer(this), _) => Ok(BigIntType::from(*this).to_str_radix(radix)),
+			(Inner::BigInt(this), _) => Ok(this.to_str_radix(radix)),
+			(Inner::Float(f), _) => Err(ToStringRadixError::NotAnInteger(NotAnInteger(*f)))
 		}
 	}
 
@@ -178,17 +227,20 @@ impl Number {
 	///
 	/// Since Rust doesn't have a "power of" trait, this is is the replacement for it.
 	pub fn pow(self, rhs: Self) -> Self {
+		use num_traits::Pow;
+
 		if self == Self::ONE || rhs == Self::ZERO {
 			return Self::ONE;
 		}
 
 		match (self.0, rhs.0) {
-			(Inner::Integer(l), Inner::Integer(r)) if 0 <= r && r <= (u32::MAX as IntegerType)
-				=> l.wrapping_pow(r as u32).into(),
-			(Inner::Integer(l), Inner::Integer(r)) => (l as FloatType).powf(r as FloatType).into(),
-			(Inner::Integer(l), Inner::Float(r)) => (l as FloatType).powf(r).into(),
-			(Inner::Float(l), Inner::Integer(r)) => l.powf(r as FloatType).into(),
-			(Inner::Float(l), Inner::Float(r)) => l.powf(r).into()
+			(Inner::Integer(l), Inner::Integer(r)) if 0 <= r && r <= (u32::MAX as IntegerType) =>
+				l.checked_pow(r as u32)
+					.map(Self::from)
+					.unwrap_or_else(|| BigIntType::from(l).pow(r as u32).into()),
+			(Inner::BigInt(l), Inner::Integer(r)) if 0 <= r && r <= (u32::MAX as IntegerType)
+				=> l.pow(r as u32).into(),
+			(l, r) => FloatType::from(Self(l)).powf(FloatType::from(Self(r))).into()
 		}
 	}
 
@@ -197,14 +249,56 @@ impl Number {
 	/// Since Rust doesn't have a "power of assign" trait, this is is the replacement for it.
 	#[inline]
 	pub fn pow_assign(&mut self, rhs: Self) {
-		*self = self.pow(rhs);
+		*self = std::mem::take(self).pow(rhs);
+	}
+
+	/// How many bits `self` needs, ignoring its sign; floats are considered to need none.
+	fn bits(&self) -> u64 {
+		match self.0 {
+			Inner::Integer(i) => (IntegerType::BITS - i.unsigned_abs().leading_zeros()).into(),
+			Inner::BigInt(ref b) => b.bits(),
+			Inner::Float(_) => 0
+		}
+	}
+
+	/// Gets `self` as a positive number of bits to scale by, saturating if it's too large.
+	fn positive_amount(&self) -> Option<u64> {
+		match self.0 {
+			Inner::Integer(i) if i > 0 => Some(i as u64),
+			Inner::BigInt(ref b) if b.is_positive() => Some(u64::MAX),
+			_ => None
+		}
+	}
+
+	/// Ensures the current sandbox allows an integer as large as `self ** rhs`.
+	fn check_pow_size(&self, rhs: &Self) -> crate::Result<()> {
+		match rhs.positive_amount() {
+			// `self` is at least `2 ** (bits - 1)`, so the result needs at least this many bits.
+			Some(exponent) if self.bits() > 1 => crate::sandbox::check_integer_bits(
+				(self.bits() - 1).saturating_mul(exponent).saturating_add(1)),
+			_ => Ok(())
+		}
+	}
+
+	/// Ensures the current sandbox allows an integer as large as `self << rhs`.
+	fn check_shl_size(&self, rhs: &Self) -> crate::Result<()> {
+		match rhs.positive_amount() {
+			Some(amnt) if self.bits() != 0 =>
+				crate::sandbox::check_integer_bits(self.bits().saturating_add(amnt)),
+			_ => Ok(())
+		}
+	}
+
+	/// Ensures the current sandbox allows an integer as large as `self * rhs`.
+	fn check_mul_size(&self, rhs: &Self) -> crate::Result<()> {
+		crate::sandbox::check_integer_bits(self.bits().saturating_add(rhs.bits()))
 	}
 
 	#[inline]
 	pub fn is_nan(&self) -> bool {
 		match self.0 {
-			Inner::Integer(..) => false,
-			Inner::Float(f) => f.is_nan()
+			Inner::Float(f) => f.is_nan(),
+			_ => false
 		}
 	}
 }
@@ -221,11 +315,14 @@ impl Ord for Number {
 	fn cmp(&self, rhs: &Self) -> Ordering {
 		use Inner::*;
 		// TODO: somehow make an ordering and account for NaN
-		match (self.0, rhs.0) {
-			(Integer(l), Integer(r)) => l.cmp(&r),
-			(Integer(l), Float(r)) => (l as FloatType).partial_cmp(&r).expect("bad cmp (i/f)"),
-			(Float(l), Integer(r)) => l.partial_cmp(&(r as FloatType)).expect("bad cmp (f/i)"),
-			(Float(l), Float(r)) => l.partial_cmp(&r).expect("bad cmp (f/f)"),
+		match (&self.0, &rhs.0) {
+			(Integer(l), Integer(r)) => l.cmp(r),
+			(BigInt(l), BigInt(r)) => l.cmp(r),
+			(Integer(l), BigInt(r)) => BigIntType::from(*l).cmp(r),
+			(BigInt(l), Integer(r)) => l.cmp(&BigIntType::from(*r)),
+			(Float(l), Float(r)) => l.partial_cmp(r).expect("bad cmp (f/f)"),
+			(Float(l), _) => l.partial_cmp(&FloatType::from(rhs.clone())).expect("bad cmp (f/i)"),
+			(_, Float(r)) => FloatType::from(self.clone()).partial_cmp(r).expect("bad cmp (i/f)"),
 		}
 	}
 }
@@ -255,6 +352,7 @@ impl TryFrom<&str> for Number {
 
 		IntegerType::from_str(inp)
 			.map(Self::from)
+			.or_else(|_| BigIntType::from_str(inp).map(Self::from))
 			.or_else(|_| FloatType::from_str(inp).map(Self::from))
 			.map_err(FromStrError::BadFloat)
 	}
@@ -338,21 +436,110 @@ impl From<NotAnInteger> for crate::Error {
 	}
 }
 
+/// The error that could occur when trying to convert a number to a primitive integer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegerConversionError {
+	/// It's not an integer.
+	NotAnInteger(NotAnInteger),
+
+	/// It's an integer, but it doesn't fit in the primitive type.
+	OutOfRange {
+		/// The integer that didn't fit.
+		number: BigIntType,
+		/// The name of the primitive type.
+		ty: &'static str
+	}
+}
+
+impl From<NotAnInteger> for IntegerConversionError {
+	#[inline]
+	fn from(err: NotAnInteger) -> Self {
+		Self::NotAnInteger(err)
+	}
+}
+
+impl Display for IntegerConversionError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::NotAnInteger(err) => Display::fmt(err, f),
+			Self::OutOfRange { number, ty } => write!(f, "{} is out of range for {}", number, ty)
+		}
+	}
+}
+
+impl std::error::Error for IntegerConversionError {}
+
+impl From<IntegerConversionError> for crate::Error {
+	fn from(err: IntegerConversionError) -> Self {
+		ValueError::Messaged(err.to_string()).into()
+	}
+}
+
+/// The error that could occur when shifting a number.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShiftError {
+	/// Either the number or the amount isn't an integer.
+	NotAnInteger(NotAnInteger),
+
+	/// The amount to shift by was negative.
+	NegativeAmount,
+
+	/// Shifting left would produce an integer larger than [`Number::MAX_SHL_BITS`].
+	TooLarge {
+		/// How many bits the result would have needed.
+		bits: u64
+	}
+}
+
+impl From<NotAnInteger> for ShiftError {
+	#[inline]
+	fn from(err: NotAnInteger) -> Self {
+		Self::NotAnInteger(err)
+	}
+}
+
+impl Display for ShiftError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::NotAnInteger(err) => Display::fmt(err, f),
+			Self::NegativeAmount => write!(f, "negative shift amount"),
+			Self::TooLarge { bits } =>
+				write!(f, "shifting would produce a {}-bit integer (the maximum is {})",
+					bits, Number::MAX_SHL_BITS)
+		}
+	}
+}
+
+impl std::error::Error for ShiftError {}
+
+impl From<ShiftError> for crate::Error {
+	fn from(err: ShiftError) -> Self {
+		ValueError::Messaged(err.to_string()).into()
+	}
+}
+
+// Unlike `as` casts, integers that don't fit in the destination type are an error.
 macro_rules! impl_try_from_eq {
 	($($int:ty)*; $($float:ty)*) => {
 		$(
 			impl TryFrom<Number> for $int {
-				type Error = NotAnInteger;
+				type Error = IntegerConversionError;
 				fn try_from(num: Number) -> Result<Self, Self::Error> {
+					let out_of_range = |number: BigIntType| IntegerConversionError::OutOfRange {
+						number, ty: stringify!($int)
+					};
+
 					match num.0 {
-						Inner::Integer(n) => Ok(n as Self),
-						Inner::Float(f) => Err(NotAnInteger(f))
+						Inner::Integer(n) => Self::try_from(n).map_err(|_| out_of_range(n.into())),
+						Inner::BigInt(n) => Self::try_from(&n).map_err(|_| out_of_range(n)),
+						Inner::Float(f) => Err(NotAnInteger(f).into())
 					}
 				}
 			}
 
 			impl PartialEq<$int> for Number {
 				#[inline]
+				#[allow(clippy::cmp_owned)] // big ints mean numbers aren't `Copy` anymore.
 				fn eq(&self, rhs: &$int) -> bool {
 					*self == Self::from(*rhs)
 				}
@@ -362,6 +549,7 @@ macro_rules! impl_try_from_eq {
 		$(
 			impl PartialEq<$float> for Number {
 				#[inline]
+				#[allow(clippy::cmp_owned)] // big ints mean numbers aren't `Copy` anymore.
 				fn eq(&self, rhs: &$float) -> bool {
 					*self == Self::from(*rhs)
 				}
@@ -393,6 +581,16 @@ impl From<IntegerType> for Number {
 	}
 }
 
+impl From<BigIntType> for Number {
+	/// Converts a [`BigIntType`] to a number, demoting it to an [`IntegerType`] if it fits.
+	fn from(n: BigIntType) -> Self {
+		match n.to_i64() {
+			Some(n) => Self(Inner::Integer(n)),
+			None => Self(Inner::BigInt(n))
+		}
+	}
+}
+
 impl From<FloatType> for Object {
 	#[inline]
 	fn from(f: FloatType) -> Self {
@@ -407,10 +605,18 @@ impl From<IntegerType> for Object {
 	}
 }
 
+impl From<BigIntType> for Object {
+	#[inline]
+	fn from(n: BigIntType) -> Self {
+		Number::from(n).into()
+	}
+}
+
 impl From<Number> for FloatType {
 	fn from(n: Number) -> Self {
 		match n.0 {
 			Inner::Integer(n) => n as _,
+			Inner::BigInt(n) => n.to_f64().unwrap_or(FloatType::NAN),
 			Inner::Float(n) => n,
 		}
 	}
@@ -421,8 +627,13 @@ macro_rules! impl_from {
 		$(
 			impl From<$int> for Number {
 				#[inline]
+				#[allow(irrefutable_let_patterns)]
 				fn from(num: $int) -> Self {
-					Self::from(num as IntegerType)
+					if let Ok(num) = IntegerType::try_from(num) {
+						Self::from(num)
+					} else {
+						Self::from(BigIntType::from(num))
+					}
 				}
 			}
 
@@ -458,7 +669,7 @@ impl_from!{
 }
 
 macro_rules! impl_math_ops {
-	($($trait:ident $trait_assign:ident $fn:ident $wrapping_fn:ident $fn_assign:ident)*) => {
+	($($trait:ident $trait_assign:ident $fn:ident $checked_fn:ident $fn_assign:ident)*) => {
 		$(
 			impl ops::$trait for Number {
 				type Output = Self;
@@ -466,11 +677,13 @@ macro_rules! impl_math_ops {
 				fn $fn(self, rhs: Self) -> Self {
 					use Inner::*;
 					match (self.0, rhs.0) {
-						// todo: make use of a big integer crate.
-						(Integer(l), Integer(r)) => Self::from(l.$wrapping_fn(r)),
-						(Integer(l), Float(r)) => Self::from((l as FloatType).$fn(r)),
-						(Float(l), Integer(r)) => Self::from(l.$fn(r as FloatType)),
-						(Float(l), Float(r)) => Self::from(l.$fn(r))
+						(Integer(l), Integer(r)) => l.$checked_fn(r)
+							.map(Self::from)
+							.unwrap_or_else(|| BigIntType::from(l).$fn(r).into()),
+						(BigInt(l), BigInt(r)) => Self::from(l.$fn(r)),
+						(BigInt(l), Integer(r)) => Self::from(l.$fn(r)),
+						(Integer(l), BigInt(r)) => Self::from(BigIntType::from(l).$fn(r)),
+						(l, r) => Self::from(FloatType::from(Self(l)).$fn(FloatType::from(Self(r))))
 					}
 				}
 			}
@@ -479,7 +692,7 @@ macro_rules! impl_math_ops {
 				#[inline]
 				fn $fn_assign(&mut self, rhs: Self) {
 					use ops::$trait;
-					*self = (*self).$fn(rhs);
+					*self = std::mem::take(self).$fn(rhs);
 				}
 			}
 		)*
@@ -487,9 +700,9 @@ macro_rules! impl_math_ops {
 }
 
 impl_math_ops! {
-	Add AddAssign add wrapping_add add_assign
-	Sub SubAssign sub wrapping_sub sub_assign
-	Mul MulAssign mul wrapping_mul mul_assign
+	Add AddAssign add checked_add add_assign
+	Sub SubAssign sub checked_sub sub_assign
+	Mul MulAssign mul checked_mul mul_assign
 }
 
 impl ops::Div for Number {
@@ -500,15 +713,25 @@ impl ops::Div for Number {
 	/// sign of `self`.
 	fn div(self, divisor: Self) -> Self {
 		if divisor == Self::ZERO {
-			match self.cmp(&Self::ZERO) {
+			return match self.cmp(&Self::ZERO) {
 				Ordering::Less => -Self::INF,
 				Ordering::Equal => Self::NAN,
 				Ordering::Greater => Self::INF,
+			};
+		}
+
+		// large integers would lose precision if converted to floats, so divide them directly if
+		// there's no remainder.
+		if self.is_big() || divisor.is_big() {
+			if let (Ok(l), Ok(r)) = (self.try_to_big(), divisor.try_to_big()) {
+				if (&l % &r).is_zero() {
+					return Self::from(l / r);
+				}
 			}
-		} else {
-			// convert to a float because we want to allow for `1/2 = 0.5`
-			Self::from(FloatType::from(self) / FloatType::from(divisor))
 		}
+
+		// convert to a float because we want to allow for `1/2 = 0.5`
+		Self::from(FloatType::from(self) / FloatType::from(divisor))
 	}
 }
 
@@ -518,7 +741,7 @@ impl ops::DivAssign for Number {
 	/// See (Number::div)[#div] for more details on a divisor of [zero](Number::ZERO).
 	#[inline]
 	fn div_assign(&mut self, divisor: Self) {
-		*self = *self / divisor;
+		*self = std::mem::take(self) / divisor;
 	}
 }
 
@@ -540,9 +763,10 @@ impl ops::Rem for Number {
 			use Inner::*;
 			match (self.0, divisor.0) {
 				(Integer(l), Integer(r)) => Self::from(l.wrapping_rem(r)),
-				(Integer(l), Float(r)) => Self::from(l as FloatType % r),
-				(Float(l), Integer(r)) => Self::from(l % r as FloatType),
-				(Float(l), Float(r)) => Self::from(l % r)
+				(BigInt(l), BigInt(r)) => Self::from(l % r),
+				(BigInt(l), Integer(r)) => Self::from(l % r),
+				(Integer(l), BigInt(r)) => Self::from(BigIntType::from(l) % r),
+				(l, r) => Self::from(FloatType::from(Self(l)) % FloatType::from(Self(r)))
 			}
 		}
 	}
@@ -555,84 +779,122 @@ impl ops::RemAssign for Number {
 	/// of `self`.
 	#[inline]
 	fn rem_assign(&mut self, divisor: Self) {
-		*self = *self % divisor;
+		*self = std::mem::take(self) % divisor;
 	}
 }
 
+macro_rules! impl_bitwise_ops {
+	($($fn:ident $fn_assign:ident $op:tt $opname:literal;)*) => {
+		impl Number {
+			$(
+				#[doc = "If both numbers are integers, simply `"]
+				#[doc = $opname]
+				#[doc = "` them. If either isn't an integer, [`NotAnInteger`] is returned."]
+				pub fn $fn(self, rhs: Self) -> Result<Self, NotAnInteger> {
+					match (&self.0, &rhs.0) {
+						(Inner::Integer(l), Inner::Integer(r)) => Ok(Self::from(l $op r)),
+						_ => Ok(Self::from(self.try_to_big()? $op rhs.try_to_big()?))
+					}
+				}
+
+				#[doc = "If both numbers are integers, replace `self` with `"]
+				#[doc = $opname]
+				#[doc = "`'s result. If either isn't an integer, [`NotAnInteger`] is returned."]
+				#[inline]
+				pub fn $fn_assign(&mut self, rhs: Self) -> Result<(), NotAnInteger> {
+					*self = std::mem::take(self).$fn(rhs)?;
+					Ok(())
+				}
+			)*
+		}
+	};
+}
+
+impl_bitwise_ops! {
+	try_bitand try_bitand_assign & "&";
+	try_bitor try_bitor_assign | "|";
+	try_bitxor try_bitxor_assign ^ "^";
+}
+
 impl Number {
-	/// If both numbers are integers, simply `&` them. If either isn't an integer, [`NotAnInteger`]
-	/// is returned.
-	pub fn try_bitand(self, rhs: Self) -> Result<Self, NotAnInteger> {
-		Ok(Self::from(IntegerType::try_from(self)? & IntegerType::try_from(rhs)?))
-	}
+	/// The most bits that [`try_shl`](Self::try_shl) will produce, so that a single shift can't
+	/// exhaust memory.
+	pub const MAX_SHL_BITS: u64 = 1 << 28;
 
-	/// If both numbers are integers, replace `self` with [`try_bitand`]'s result. If either isn't an
-	/// integer, [`NotAnInteger`] is returned.
-	#[inline]
-	pub fn try_bitand_assign(&mut self, rhs: Self) -> Result<(), NotAnInteger> {
-		*self = self.try_bitand(rhs)?;
-		Ok(())
+	/// Gets `self` as an amount to shift by, saturating if it's too large.
+	fn shift_amount(&self) -> Result<u64, ShiftError> {
+		match self.0 {
+			Inner::Integer(i) => u64::try_from(i).map_err(|_| ShiftError::NegativeAmount),
+			Inner::BigInt(ref b) if b.is_negative() => Err(ShiftError::NegativeAmount),
+			Inner::BigInt(_) => Ok(u64::MAX),
+			Inner::Float(f) => Err(NotAnInteger(f).into())
+		}
 	}
 
-	/// If both numbers are integers, simply `|` them. If either isn't an integer, [`NotAnInteger`]
-	/// is returned.
-	pub fn try_bitor(self, rhs: Self) -> Result<Self, NotAnInteger> {
-		Ok(Self::from(IntegerType::try_from(self)? | IntegerType::try_from(rhs)?))
-	}
+	/// If both numbers are integers, simply `<<` them.
+	///
+	/// If either isn't an integer, `rhs` is negative, or the result would need more than
+	/// [`MAX_SHL_BITS`](Self::MAX_SHL_BITS) bits, a [`ShiftError`] is returned.
+	pub fn try_shl(self, rhs: Self) -> Result<Self, ShiftError> {
+		let amnt = rhs.shift_amount()?;
+
+		if let Inner::Integer(l) = self.0 {
+			if amnt < IntegerType::BITS.into() && (l << amnt) >> amnt == l {
+				return Ok(Self::from(l << amnt));
+			}
+		}
 
-	/// If both numbers are integers, replace `self` with [`try_bitor`]'s result. If either isn't an
-	/// integer, [`NotAnInteger`] is returned.
-	#[inline]
-	pub fn try_bitor_assign(&mut self, rhs: Self) -> Result<(), NotAnInteger> {
-		*self = self.try_bitor(rhs)?;
-		Ok(())
-	}
+		let big = self.try_to_big()?;
 
-	/// If both numbers are integers, simply `^` them. If either isn't an integer, [`NotAnInteger`]
-	/// is returned.
-	pub fn try_bitxor(self, rhs: Self) -> Result<Self, NotAnInteger> {
-		Ok(Self::from(IntegerType::try_from(self)? ^ IntegerType::try_from(rhs)?))
+		match big.bits() {
+			0 => Ok(Self::ZERO),
+			bits if bits.saturating_add(amnt) > Self::MAX_SHL_BITS =>
+				Err(ShiftError::TooLarge { bits: bits.saturating_add(amnt) }),
+			_ => Ok(Self::from(big << amnt as usize))
+		}
 	}
 
-	/// If both numbers are integers, replace `self` with [`try_bitxor`]'s result. If either isn't an
-	/// integer, [`NotAnInteger`] is returned.
+	/// If both numbers are integers, replace `self` with [`try_shl`]'s result. Otherwise, a
+	/// [`ShiftError`] is returned.
 	#[inline]
-	pub fn try_bitxor_assign(&mut self, rhs: Self) -> Result<(), NotAnInteger> {
-		*self = self.try_bitxor(rhs)?;
+	pub fn try_shl_assign(&mut self, rhs: Self) -> Result<(), ShiftError> {
+		*self = std::mem::take(self).try_shl(rhs)?;
 		Ok(())
 	}
 
-	/// If both numbers are integers, simply `<<` them. If either isn't an integer, [`NotAnInteger`]
-	/// is returned.
-	pub fn try_shl(self, rhs: Self) -> Result<Self, NotAnInteger>{
-		Ok(Self::from(IntegerType::try_from(self)?.wrapping_shl(u32::try_from(rhs)?)))
-	}
+	/// If both numbers are integers, simply `>>` them. If either isn't an integer, or `rhs` is
+	/// negative, a [`ShiftError`] is returned.
+	pub fn try_shr(self, rhs: Self) -> Result<Self, ShiftError> {
+		let amnt = rhs.shift_amount()?;
 
-	/// If both numbers are integers, replace `self` with [`try_shl`]'s result. If either isn't an
-	/// integer, [`NotAnInteger`] is returned.
-	#[inline]
-	pub fn try_shl_assign(&mut self, rhs: Self) -> Result<(), NotAnInteger> {
-		*self = self.try_shl(rhs)?;
-		Ok(())
-	}
+		if let Inner::Integer(l) = self.0 {
+			return Ok(Self::from(l >> amnt.min((IntegerType::BITS - 1).into())));
+		}
 
-	/// If both numbers are integers, simply `<<` them. If either isn't an integer, [`NotAnInteger`]
-	/// is returned.
-	pub fn try_shr(self, rhs: Self) -> Result<Self, NotAnInteger> {
-		Ok(Self::from(IntegerType::try_from(self)?.wrapping_shr(u32::try_from(rhs)?)))
+		let big = self.try_to_big()?;
+
+		// every bit is shifted out, leaving only the sign.
+		if amnt >= big.bits() {
+			Ok(Self::from(if big.is_negative() { -1 } else { 0 }))
+		} else {
+			Ok(Self::from(big >> amnt as usize))
+		}
 	}
 
-	/// If both numbers are integers, replace `self` with [`try_shr`]'s result. If either isn't an
-	/// integer, [`NotAnInteger`] is returned.
+	/// If both numbers are integers, replace `self` with [`try_shr`]'s result. Otherwise, a
+	/// [`ShiftError`] is returned.
 	#[inline]
-	pub fn try_shr_assign(&mut self, rhs: Self) -> Result<(), NotAnInteger> {
-		*self = self.try_shr(rhs)?;
+	pub fn try_shr_assign(&mut self, rhs: Self) -> Result<(), ShiftError> {
+		*self = std::mem::take(self).try_shr(rhs)?;
 		Ok(())
 	}
 
 	/// Try to perform `~`, returning [`NotAnInteger`] if `self` isn't an integer
 	pub fn try_not(self) -> Result<Self, NotAnInteger> {
-		Ok(Self::from(!IntegerType::try_from(self)?))
+		match self.0 {
+			Inner::Integer(i) => Ok(Self::from(!i)),
+			_ => Ok(Self::from(!self.try_to_big()?))
+		}
 	}
 }
 
@@ -641,7 +903,10 @@ impl ops::Neg for Number {
 
 	fn neg(self) -> Self {
 		match self.0 {
-			Inner::Integer(i) => Self::from(-i),
+			Inner::Integer(i) => i.checked_neg()
+				.map(Self::from)
+				.unwrap_or_else(|| (-BigIntType::from(i)).into()),
+			Inner::BigInt(b) => Self::from(-b),
 			Inner::Float(f) => Self::from(-f)
 		}
 	}
@@ -696,12 +961,12 @@ impl Number {
 		let this = this.try_downcast::<Self>()?;
 
 		if let Some(radix) = args.arg(0) {
-			this.to_string_radix((*radix.call_downcast::<Self>()?).try_into()?)
+			this.to_string_radix(radix.call_downcast::<Self>()?.clone().try_into()?)
 				.map_err(|err| TypeError::Messaged(err.to_string()))
 				.map_err(crate::Error::from)
 				.map(Object::from)
 		} else {
-			Ok(Text::from(*this).into())
+			Ok(Text::from(this.clone()).into())
 		}
 	}
 
@@ -712,7 +977,7 @@ impl Number {
 	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(Boolean::from(*this).into())
+		Ok(Boolean::from(this.clone()).into())
 	}
 
 	/// Calling a number is simply an alias for [multiplication](#qs_mul).
@@ -721,6 +986,16 @@ impl Number {
 		Self::qs_mul(this, args)
 	}
 
+	/// Converts `this` to an exact [`Rational`].
+	///
+	/// Floats are converted to their exact binary value; `NaN` and infinities can't be converted.
+	#[instrument(name="Number::@rational", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_rational(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(Rational::try_from(this)?.into())
+	}
+
 	/// Hash a number.
 	#[instrument(name="Number::hash", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
@@ -734,7 +1009,7 @@ impl Number {
 	pub fn qs_neg(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		Ok((-*this).into())
+		Ok((-this.clone()).into())
 	}
 
 	/// Get the absolute value of `this`.
@@ -749,10 +1024,14 @@ impl Number {
 	/// 1. (required, `@num`) The addend.
 	#[instrument(name="Number::+", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(addend) = args.try_arg(0)?.downcast::<Rational>() {
+			return Rational::number_op(BinaryOp::Add, &*this.try_downcast::<Self>()?, &addend);
+		}
+
 		let addend = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok((*this + *addend).into())
+		Ok((this.clone() + addend.clone()).into())
 	}
 
 	/// Add `this` and the first argument, in place.
@@ -761,7 +1040,7 @@ impl Number {
 	/// 1. (required, `@num`) The addend.
 	#[instrument(name="Number::+=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_add_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let addend = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let addend = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		*this.try_downcast_mut::<Self>()? += addend;
 		Ok(this.clone())
@@ -773,10 +1052,14 @@ impl Number {
 	/// 1. (required, `@num`) The subtrahend.
 	#[instrument(name="Number::-", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(subtrahend) = args.try_arg(0)?.downcast::<Rational>() {
+			return Rational::number_op(BinaryOp::Sub, &*this.try_downcast::<Self>()?, &subtrahend);
+		}
+
 		let subtrahend = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok((*this - *subtrahend).into())
+		Ok((this.clone() - subtrahend.clone()).into())
 	}
 
 	/// Subtract the the first argument from `this`, in place.
@@ -785,7 +1068,7 @@ impl Number {
 	/// 1. (required, `@num`) The subtrahend.
 	#[instrument(name="Number::-=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_sub_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let subtrahend = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let subtrahend = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		*this.try_downcast_mut::<Self>()? -= subtrahend;
 		Ok(this.clone())
@@ -797,10 +1080,15 @@ impl Number {
 	/// 1. (required, `@num`) The multiplicand.
 	#[instrument(name="Number::*", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(multiplicand) = args.try_arg(0)?.downcast::<Rational>() {
+			return Rational::number_op(BinaryOp::Mul, &*this.try_downcast::<Self>()?, &multiplicand);
+		}
+
 		let multiplicand = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
+		this.check_mul_size(&multiplicand)?;
 
-		Ok((*this * *multiplicand).into())
+		Ok((this.clone() * multiplicand.clone()).into())
 	}
 
 	/// Multiply `this` and the first argument, in place.
@@ -809,7 +1097,8 @@ impl Number {
 	/// 1. (required, `@num`) The multiplicand.
 	#[instrument(name="Number::*=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mul_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let multiplicand = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let multiplicand = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		this.try_downcast::<Self>()?.check_mul_size(&multiplicand)?;
 
 		*this.try_downcast_mut::<Self>()? *= multiplicand;
 		Ok(this.clone())
@@ -824,10 +1113,14 @@ impl Number {
 	/// 1. (required, `@num`) The divisor.
 	#[instrument(name="Number::/", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_div(this: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(divisor) = args.try_arg(0)?.downcast::<Rational>() {
+			return Rational::number_op(BinaryOp::Div, &*this.try_downcast::<Self>()?, &divisor);
+		}
+
 		let divisor = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok((*this / *divisor).into())
+		Ok((this.clone() / divisor.clone()).into())
 	}
 
 	/// Divide `this` by the first argument, in place.
@@ -838,7 +1131,7 @@ impl Number {
 	/// 1. (required, `@num`) The divisor.
 	#[instrument(name="Number::/=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_div_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let divisor = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let divisor = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		*this.try_downcast_mut::<Self>()? /= divisor;
 		Ok(this.clone())
@@ -853,10 +1146,14 @@ impl Number {
 	/// 1. (required, `@num`) The divisor.
 	#[instrument(name="Number::%", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mod(this: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(divisor) = args.try_arg(0)?.downcast::<Rational>() {
+			return Rational::number_op(BinaryOp::Rem, &*this.try_downcast::<Self>()?, &divisor);
+		}
+
 		let divisor = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok((*this % *divisor).into())
+		Ok((this.clone() % divisor.clone()).into())
 	}
 
 	/// Modulo `this` by `divisor`, in place.
@@ -867,7 +1164,7 @@ impl Number {
 	/// 1. (required, `@num`) The divisor.
 	#[instrument(name="Number::%=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mod_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let divisor = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let divisor = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		*this.try_downcast_mut::<Self>()? %= divisor;
 		Ok(this.clone())
@@ -881,8 +1178,9 @@ impl Number {
 	pub fn qs_pow(this: &Object, args: Args) -> crate::Result<Object> {
 		let exponent = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
+		this.check_pow_size(&exponent)?;
 
-		Ok(this.pow(*exponent).into())
+		Ok(this.clone().pow(exponent.clone()).into())
 	}
 
 	/// Raises `this` to the power of `exponent`, in place.
@@ -891,7 +1189,8 @@ impl Number {
 	/// 1. (required, `@num`) The exponent.
 	#[instrument(name="Number::**=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_pow_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let exponent = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let exponent = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		this.try_downcast::<Self>()?.check_pow_size(&exponent)?;
 
 		this.try_downcast_mut::<Self>()?.pow_assign(exponent);
 		Ok(this.clone())
@@ -904,7 +1203,7 @@ impl Number {
 	pub fn qs_bitnot(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(this.try_not()?.into())
+		Ok(this.clone().try_not()?.into())
 	}
 
 	/// Bitwise AND of `this` and `other`.
@@ -918,7 +1217,7 @@ impl Number {
 		let other = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(this.try_bitand(*other)?.into())
+		Ok(this.clone().try_bitand(other.clone())?.into())
 	}
 
 	/// Bitwise AND of `this` and `other`, in place.
@@ -929,7 +1228,7 @@ impl Number {
 	/// 1. (required, `@num`) The other value.
 	#[instrument(name="Number::&=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_bitand_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let other = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let other = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		this.try_downcast_mut::<Self>()?.try_bitand_assign(other)?;
 		Ok(this.clone())
@@ -946,7 +1245,7 @@ impl Number {
 		let other = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(this.try_bitor(*other)?.into())
+		Ok(this.clone().try_bitor(other.clone())?.into())
 	}
 
 	/// Bitwise OR of `this` and `other`, in place.
@@ -957,7 +1256,7 @@ impl Number {
 	/// 1. (required, `@num`) The other value.
 	#[instrument(name="Number::|=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_bitor_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let other = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let other = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		this.try_downcast_mut::<Self>()?.try_bitor_assign(other)?;
 		Ok(this.clone())
@@ -974,7 +1273,7 @@ impl Number {
 		let other = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(this.try_bitxor(*other)?.into())
+		Ok(this.clone().try_bitxor(other.clone())?.into())
 	}
 
 	/// Bitwise XOR of `this` and `other`, in place.
@@ -985,7 +1284,7 @@ impl Number {
 	/// 1. (required, `@num`) The other value.
 	#[instrument(name="Number::^=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_bitxor_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let other = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let other = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		this.try_downcast_mut::<Self>()?.try_bitxor_assign(other)?;
 		Ok(this.clone())
@@ -993,7 +1292,9 @@ impl Number {
 
 	/// Shift `this` left by `amnt`.
 	///
-	/// If either `this` or `amnt` aren't a whole number, a [`ValueError`] is raised.
+	/// If either `this` or `amnt` aren't a whole number, or `amnt` is negative, a [`ValueError`] is
+	/// raised. A [`ValueError`] is also raised if the result would need more than
+	/// [`MAX_SHL_BITS`](Self::MAX_SHL_BITS) bits.
 	///
 	/// # Arguments
 	/// 1. (required, `@num`) The value to shift by.
@@ -1001,19 +1302,23 @@ impl Number {
 	pub fn qs_shl(this: &Object, args: Args) -> crate::Result<Object> {
 		let amnt = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
+		this.check_shl_size(&amnt)?;
 
-		Ok(this.try_shl(*amnt)?.into())
+		Ok(this.clone().try_shl(amnt.clone())?.into())
 	}
 
 	/// Shift `this` left by `amnt`, in place.
 	///
-	/// If either `this` or `amnt` aren't a whole number, a [`ValueError`] is raised.
+	/// If either `this` or `amnt` aren't a whole number, or `amnt` is negative, a [`ValueError`] is
+	/// raised. A [`ValueError`] is also raised if the result would need more than
+	/// [`MAX_SHL_BITS`](Self::MAX_SHL_BITS) bits.
 	///
 	/// # Arguments
 	/// 1. (required, `@num`) The value to shift by.
 	#[instrument(name="Number::<<=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_shl_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let amnt = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let amnt = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		this.try_downcast::<Self>()?.check_shl_size(&amnt)?;
 
 		this.try_downcast_mut::<Self>()?.try_shl_assign(amnt)?;
 		Ok(this.clone())
@@ -1021,7 +1326,8 @@ impl Number {
 
 	/// Shift `this` right by `amnt`.
 	///
-	/// If either `this` or `amnt` aren't a whole number, a [`ValueError`] is raised.
+	/// If either `this` or `amnt` aren't a whole number, or `amnt` is negative, a [`ValueError`] is
+	/// raised.
 	///
 	/// # Arguments
 	/// 1. (required, `@num`) The value to shift by.
@@ -1030,18 +1336,19 @@ impl Number {
 		let amnt = args.try_arg(0)?.call_downcast::<Self>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(this.try_shr(*amnt)?.into())
+		Ok(this.clone().try_shr(amnt.clone())?.into())
 	}
 
 	/// Shift `this` right by `amnt`, in place.
 	///
-	/// If either `this` or `amnt` aren't a whole number, a [`ValueError`] is raised.
+	/// If either `this` or `amnt` aren't a whole number, or `amnt` is negative, a [`ValueError`] is
+	/// raised.
 	///
 	/// # Arguments
 	/// 1. (required, `@num`) The value to shift by.
 	#[instrument(name="Number::>>=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_shr_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let amnt = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let amnt = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 
 		this.try_downcast_mut::<Self>()?.try_shr_assign(amnt)?;
 		Ok(this.clone())
@@ -1063,6 +1370,11 @@ impl Number {
 	/// 1. (required) The other object to compare against.
 	#[instrument(name="Number::==", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(rhs) = args.try_arg(0)?.downcast::<Rational>() {
+			let is_eql = Rational::number_cmp(&*this.try_downcast::<Self>()?, &rhs) == Some(Ordering::Equal);
+			return Ok(is_eql.into());
+		}
+
 		let rhs = args.try_arg(0)?.downcast::<Self>();
 		let this = this.try_downcast::<Self>()?;
 
@@ -1076,6 +1388,11 @@ impl Number {
 	#[instrument(name="Number::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
+
+		if let Some(rhs) = args.try_arg(0)?.downcast::<Rational>() {
+			return Ok(Rational::number_cmp(&this, &rhs).map(Object::from).unwrap_or_default());
+		}
+
 		let rhs = args.try_arg(0)?.downcast::<Self>();
 
 		Ok(rhs.map(|rhs| this.cmp(&*rhs).into()).unwrap_or_default())
@@ -1110,16 +1427,16 @@ impl Number {
 	pub fn qs_sqrt(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(FloatType::from(*this).sqrt().into())
+		Ok(FloatType::from(this.clone()).sqrt().into())
 	}
 
 	/// Checks to see if `this` is between the first and second arguments, inclusive.
 	#[instrument(name="Number::between?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_between_q(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
-		let min = *args.try_arg(0)?.call_downcast::<Self>()?;
-		let max = *args.try_arg(1)?.call_downcast::<Self>()?;
+		let min = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let max = args.try_arg(1)?.call_downcast::<Self>()?.clone();
 
 		Ok((min <= this && this <= max).into())
 	}
@@ -1127,7 +1444,7 @@ impl Number {
 	/// Checks to see if `this` is zero.
 	#[instrument(name="Number::zero?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_zero_q(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
 		Ok((this == Self::ZERO).into())
 	}
@@ -1135,7 +1452,7 @@ impl Number {
 	/// Checks to see if `this` is one.
 	#[instrument(name="Number::one?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_one_q(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
 		Ok((this == Self::ONE).into())
 	}
@@ -1143,7 +1460,7 @@ impl Number {
 	/// Checks to see if `this` is one.
 	#[instrument(name="Number::positive?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_positive_q(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
 		Ok((this > Self::ZERO).into())
 	}
@@ -1151,7 +1468,7 @@ impl Number {
 	/// Checks to see if `this` is one.
 	#[instrument(name="Number::negative?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_negative_q(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
 		Ok((this < Self::ZERO).into())
 	}
@@ -1159,7 +1476,7 @@ impl Number {
 	/// Checks to see if `this` is even.
 	#[instrument(name="Number::even?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_even_q(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
 		Ok((IntegerType::try_from(this).map_or(false, |x| x % 2 == 0)).into())
 	}
@@ -1167,7 +1484,7 @@ impl Number {
 	/// Checks to see if `this` is odd.
 	#[instrument(name="Number::odd?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_odd_q(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = *this.try_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?.clone();
 
 		Ok((IntegerType::try_from(this).map_or(false, |x| x % 2 != 0)).into())
 	}
@@ -1175,18 +1492,18 @@ impl Number {
 	/// Returns an array starting at `this` and ending at the first argument, with an optional step.
 	#[instrument(name="Number::upto", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_upto(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut start = *this.try_downcast::<Self>()?;
-		let stop = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let mut start = this.try_downcast::<Self>()?.clone();
+		let stop = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 		let step = args.arg(1)
 			.map(Object::try_downcast::<Self>)
 			.transpose()?
-			.map(|x| *x)
+			.map(|x| x.clone())
 			.unwrap_or(Number::ONE);
 
 		let mut v = Vec::new();
 		while start <= stop {
-			v.push(start.into());
-			start += step;
+			v.push(start.clone().into());
+			start += step.clone();
 		}
 
 		Ok(v.into())
@@ -1195,18 +1512,18 @@ impl Number {
 	/// Returns an array starting at `this` and ending at the first argument, with an optional step.
 	#[instrument(name="Number::downto", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_downto(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut start = *this.try_downcast::<Self>()?;
-		let stop = *args.try_arg(0)?.call_downcast::<Self>()?;
+		let mut start = this.try_downcast::<Self>()?.clone();
+		let stop = args.try_arg(0)?.call_downcast::<Self>()?.clone();
 		let step = args.arg(1)
 			.map(Object::try_downcast::<Self>)
 			.transpose()?
-			.map(|x| *x)
+			.map(|x| x.clone())
 			.unwrap_or(Number::ONE);
 
 		let mut v = Vec::new();
 		while start >= stop {
-			v.push(start.into());
-			start -= step;
+			v.push(start.clone().into());
+			start -= step.clone();
 		}
 
 		Ok(v.into())
@@ -1229,13 +1546,18 @@ impl_object_type!{
 			static ref OBJECTS: RwLock<HashMap<Number, Object>> = RwLock::new(HashMap::new());
 		}
 
+		// big integers are rarely reused, so there's no point in keeping them around forever.
+		if self.is_big() {
+			return Object::new_with_parent(self, vec![Number::mapping()]);
+		}
+
 		if let Some(obj) = OBJECTS.read().get(&self) {
 			return obj.deep_clone();
 		}
 
 		let mut objs = OBJECTS.write();
 
-		objs.entry(self)
+		objs.entry(self.clone())
 			.or_insert_with(|| Object::new_with_parent(self, vec![Number::mapping()]))
 			.deep_clone()
 	}
@@ -1250,6 +1572,7 @@ impl_object_type!{
 	"@text" => method Self::qs_at_text,
 	"inspect" => method Self::qs_inspect,
 	"@num" => method Self::qs_at_num,
+	"@rational" => method Self::qs_at_rational,
 	"@bool" => method Self::qs_at_bool,
 	"hash" => method Self::qs_hash,
 
@@ -1307,6 +1630,31 @@ mod tests {
 		assert_eq!(x, x);
 	}
 
+	#[test]
+	fn try_from_out_of_range() {
+		let big = |n: BigIntType| Number::from(n);
+
+		assert_eq!(u64::try_from(big(u64::MAX.into())), Ok(u64::MAX));
+		assert!(matches!(
+			u64::try_from(big(BigIntType::from(u64::MAX) + 1)),
+			Err(IntegerConversionError::OutOfRange { ty: "u64", .. })));
+
+		assert_eq!(i64::try_from(Number::from(i64::MIN)), Ok(i64::MIN));
+		assert!(matches!(
+			i64::try_from(big(BigIntType::from(i64::MIN) - 1)),
+			Err(IntegerConversionError::OutOfRange { ty: "i64", .. })));
+
+		assert_eq!(usize::try_from(big(usize::MAX.into())), Ok(usize::MAX));
+		assert!(matches!(
+			usize::try_from(big(BigIntType::from(usize::MAX) + 1)),
+			Err(IntegerConversionError::OutOfRange { ty: "usize", .. })));
+
+		assert!(matches!(u8::try_from(Number::from(-1)),
+			Err(IntegerConversionError::OutOfRange { ty: "u8", .. })));
+		assert!(matches!(u8::try_from(Number::from(1.5)),
+			Err(IntegerConversionError::NotAnInteger(_))));
+	}
+
 	#[allow(clippy::float_cmp)]
 	mod qs {
 		use super::*;
@@ -1519,15 +1867,15 @@ mod tests {
 
 				assert_call_eq!(Number::qs_hash(n1) -> Number, hash!(n1));
 				assert_eq!(
-					call_unwrap!(Number::qs_hash(n1) -> Number; |n| *n) == 
-						call_unwrap!(Number::qs_hash(n2) -> Number; |n| *n),
+					call_unwrap!(Number::qs_hash(n1) -> Number; |n| n.clone()) == 
+						call_unwrap!(Number::qs_hash(n2) -> Number; |n| n.clone()),
 					Number::from(n1) == Number::from(n2)
 				);
 
 				assert_call_eq!(Number::qs_hash(f1) -> Number, hash!(f1));
 				assert_eq!(
-					call_unwrap!(Number::qs_hash(f1) -> Number; |n| *n) == 
-						call_unwrap!(Number::qs_hash(f2) -> Number; |n| *n),
+					call_unwrap!(Number::qs_hash(f1) -> Number; |n| n.clone()) == 
+						call_unwrap!(Number::qs_hash(f2) -> Number; |n| n.clone()),
 					Number::from(f1) == Number::from(f2)
 				);
 			}
@@ -1709,13 +2057,13 @@ mod tests {
 				let f1 = random::<FloatType>();
 				let f2 = random::<FloatType>();
 
-				assert_call_eq!(Number::qs_add(n1, n1) -> Number, n1.wrapping_add(n1));
-				assert_call_eq!(Number::qs_add(n1, n2) -> Number, n1.wrapping_add(n2));
+				assert_call_eq!(Number::qs_add(n1, n1) -> Number, Number::from(n1 as i128 + n1 as i128));
+				assert_call_eq!(Number::qs_add(n1, n2) -> Number, Number::from(n1 as i128 + n2 as i128));
 				assert_call_eq!(Number::qs_add(n1, f1) -> Number, n1 as FloatType + f1);
 				assert_call_eq!(Number::qs_add(n1, f2) -> Number, n1 as FloatType + f2);
 
-				assert_call_eq!(Number::qs_add(n2, n1) -> Number, n2.wrapping_add(n1));
-				assert_call_eq!(Number::qs_add(n2, n2) -> Number, n2.wrapping_add(n2));
+				assert_call_eq!(Number::qs_add(n2, n1) -> Number, Number::from(n2 as i128 + n1 as i128));
+				assert_call_eq!(Number::qs_add(n2, n2) -> Number, Number::from(n2 as i128 + n2 as i128));
 				assert_call_eq!(Number::qs_add(n2, f1) -> Number, n2 as FloatType + f1);
 				assert_call_eq!(Number::qs_add(n2, f2) -> Number, n2 as FloatType + f2);
 
@@ -1759,11 +2107,11 @@ mod tests {
 				let f2 = random::<FloatType>();
 
 				assert_call_eq!(Number::qs_sub(n1, n1) -> Number, 0);
-				assert_call_eq!(Number::qs_sub(n1, n2) -> Number, n1.wrapping_sub(n2));
+				assert_call_eq!(Number::qs_sub(n1, n2) -> Number, Number::from(n1 as i128 - n2 as i128));
 				assert_call_eq!(Number::qs_sub(n1, f1) -> Number, n1 as FloatType - f1);
 				assert_call_eq!(Number::qs_sub(n1, f2) -> Number, n1 as FloatType - f2);
 
-				assert_call_eq!(Number::qs_sub(n2, n1) -> Number, n2.wrapping_sub(n1));
+				assert_call_eq!(Number::qs_sub(n2, n1) -> Number, Number::from(n2 as i128 - n1 as i128));
 				assert_call_eq!(Number::qs_sub(n2, n2) -> Number, 0);
 				assert_call_eq!(Number::qs_sub(n2, f1) -> Number, n2 as FloatType - f1);
 				assert_call_eq!(Number::qs_sub(n2, f2) -> Number, n2 as FloatType - f2);
@@ -1807,13 +2155,13 @@ mod tests {
 				let f1 = random::<FloatType>();
 				let f2 = random::<FloatType>();
 
-				assert_call_eq!(Number::qs_mul(n1, n1) -> Number, n1.wrapping_mul(n1));
-				assert_call_eq!(Number::qs_mul(n1, n2) -> Number, n1.wrapping_mul(n2));
+				assert_call_eq!(Number::qs_mul(n1, n1) -> Number, Number::from(n1 as i128 * n1 as i128));
+				assert_call_eq!(Number::qs_mul(n1, n2) -> Number, Number::from(n1 as i128 * n2 as i128));
 				assert_call_eq!(Number::qs_mul(n1, f1) -> Number, n1 as FloatType * f1);
 				assert_call_eq!(Number::qs_mul(n1, f2) -> Number, n1 as FloatType * f2);
 
-				assert_call_eq!(Number::qs_mul(n2, n1) -> Number, n2.wrapping_mul(n1));
-				assert_call_eq!(Number::qs_mul(n2, n2) -> Number, n2.wrapping_mul(n2));
+				assert_call_eq!(Number::qs_mul(n2, n1) -> Number, Number::from(n2 as i128 * n1 as i128));
+				assert_call_eq!(Number::qs_mul(n2, n2) -> Number, Number::from(n2 as i128 * n2 as i128));
 				assert_call_eq!(Number::qs_mul(n2, f1) -> Number, n2 as FloatType * f1);
 				assert_call_eq!(Number::qs_mul(n2, f2) -> Number, n2 as FloatType * f2);
 
@@ -1856,13 +2204,13 @@ mod tests {
 				let f1 = random::<FloatType>();
 				let f2 = random::<FloatType>();
 
-				assert_call_eq!(Number::qs_call(n1, n1) -> Number, n1.wrapping_mul(n1));
-				assert_call_eq!(Number::qs_call(n1, n2) -> Number, n1.wrapping_mul(n2));
+				assert_call_eq!(Number::qs_call(n1, n1) -> Number, Number::from(n1 as i128 * n1 as i128));
+				assert_call_eq!(Number::qs_call(n1, n2) -> Number, Number::from(n1 as i128 * n2 as i128));
 				assert_call_eq!(Number::qs_call(n1, f1) -> Number, n1 as FloatType * f1);
 				assert_call_eq!(Number::qs_call(n1, f2) -> Number, n1 as FloatType * f2);
 
-				assert_call_eq!(Number::qs_call(n2, n1) -> Number, n2.wrapping_mul(n1));
-				assert_call_eq!(Number::qs_call(n2, n2) -> Number, n2.wrapping_mul(n2));
+				assert_call_eq!(Number::qs_call(n2, n1) -> Number, Number::from(n2 as i128 * n1 as i128));
+				assert_call_eq!(Number::qs_call(n2, n2) -> Number, Number::from(n2 as i128 * n2 as i128));
 				assert_call_eq!(Number::qs_call(n2, f1) -> Number, n2 as FloatType * f1);
 				assert_call_eq!(Number::qs_call(n2, f2) -> Number, n2 as FloatType * f2);
 
@@ -2001,7 +2349,7 @@ mod tests {
 
 		#[test]
 		fn pow() {
-			assert_call_eq!(Number::qs_pow(149, 19) -> Number, (149 as IntegerType).wrapping_pow(19));
+			assert_call_eq!(Number::qs_pow(149, 19) -> Number, Number::from(BigIntType::from(149).pow(19u32)));
 			assert_call_eq!(Number::qs_pow(12, -123) -> Number, (12.0 as FloatType).powf(-123.0));
 			assert_call_eq!(Number::qs_pow(0, -123) -> Number, (0.0 as FloatType).powf(-123.0));
 			assert_call_eq!(Number::qs_pow(Number::INF, 123) -> Number, Number::INF);
@@ -2023,7 +2371,7 @@ mod tests {
 
 			for _ in 0..1000 {
 				let n1 = random::<IntegerType>().abs();
-				let n2 = random::<u32>();
+				let n2 = random::<u32>() % 64; // larger exponents take far too long to compute
 				let f1 = random::<FloatType>().abs();
 				let f2 = random::<FloatType>();
 
@@ -2032,7 +2380,7 @@ mod tests {
 					continue;
 				}
 
-				assert_call_eq!(Number::qs_pow(n1, n2) -> Number, n1.wrapping_pow(n2));
+				assert_call_eq!(Number::qs_pow(n1, n2) -> Number, Number::from(BigIntType::from(n1).pow(n2)));
 				assert_call_eq!(Number::qs_pow(n1, f1) -> Number, (n1 as FloatType).powf(f1));
 				assert_call_eq!(Number::qs_pow(n1, f2) -> Number, (n1 as FloatType).powf(f2));
 
@@ -2162,10 +2510,9 @@ mod tests {
 
 		#[test]
 		fn shl() {
-			assert_call_eq!(Number::qs_shl(912, 12) -> Number, (912 as IntegerType).wrapping_shl(12));
-			assert_call_eq!(Number::qs_shl(-512, 4) -> Number, (-512 as IntegerType).wrapping_shl(4));
-			assert_call_eq!(Number::qs_shl(0xff1e24, 10) -> Number,
-				(0xff1e24 as IntegerType).wrapping_shl(10));
+			assert_call_eq!(Number::qs_shl(912, 12) -> Number, 912 << 12);
+			assert_call_eq!(Number::qs_shl(-512, 4) -> Number, -512 << 4);
+			assert_call_eq!(Number::qs_shl(0xff1e24, 10) -> Number, (0xff1e24 as IntegerType) << 10);
 
 			assert_call_err!(Number::qs_shl(12.3, 0xfe), crate::Error::ValueError(..));
 			assert_call_err!(Number::qs_shl(0xed, -12.9), crate::Error::ValueError(..));
@@ -2174,28 +2521,60 @@ mod tests {
 			for _ in 0..1000 {
 				let n1 = random::<IntegerType>();
 				let n2 = random::<u32>() % 100; // to make it more a realistic shift amnt.
-				let n3 = random::<u32>();
+				let n3 = random::<u32>() % 10_000;
 
-				assert_call_eq!(Number::qs_shl(n1, n2) -> Number, n1.wrapping_shl(n2));
-				assert_call_eq!(Number::qs_shl(n1, n3) -> Number, n1.wrapping_shl(n3));
+				assert_call_eq!(Number::qs_shl(n1, n2) -> Number, Number::from(BigIntType::from(n1) << n2 as usize));
+				assert_call_eq!(Number::qs_shl(n1, n3) -> Number, Number::from(BigIntType::from(n1) << n3 as usize));
 			}
 
 			assert_call_missing_parameter!(Number::qs_shl(0), 0);
 			assert_call_idempotent!(Number::qs_shl(12, 14));
 		}
 
+		#[test]
+		fn shl_bounds() {
+			assert_call_err!(Number::qs_shl(1, -1), crate::Error::ValueError(..));
+			assert_call_err!(Number::qs_shl(1, Number::from(BigIntType::from(-1) << 100)), crate::Error::ValueError(..));
+			assert_call_err!(Number::qs_shl_assign(1, -1), crate::Error::ValueError(..));
+
+			let max = Number::MAX_SHL_BITS as IntegerType;
+			assert_call_eq!(Number::qs_shl(1, max - 1) -> Number, Number::from(BigIntType::from(1) << (max - 1) as usize));
+			assert_call_err!(Number::qs_shl(1, max), crate::Error::ValueError(..));
+			assert_call_err!(Number::qs_shl(1, u32::MAX), crate::Error::ValueError(..));
+			assert_call_err!(Number::qs_shl(1, Number::from(BigIntType::from(1) << 100)), crate::Error::ValueError(..));
+			assert_call_eq!(Number::qs_shl(0, Number::from(BigIntType::from(1) << 100)) -> Number, 0);
+		}
+
 		#[test]
 		fn shl_assign() {
 			assert_call_non_idempotent!(Number::qs_shl_assign(0, 1));
 			assert_call_missing_parameter!(Number::qs_shl_assign(0), 0);
 		}
 
+		#[test]
+		fn sandboxed_sizes() {
+			let _guard = crate::sandbox::Sandbox::new().max_integer_bits(64).enter();
+
+			assert_call_eq!(Number::qs_pow(2, 63) -> Number, Number::from(BigIntType::from(2).pow(63u32)));
+			assert_call_eq!(Number::qs_pow(-1, 1_000_000) -> Number, 1);
+			assert_call_err!(Number::qs_pow(2, 64), crate::Error::SandboxError(..));
+			assert_call_err!(Number::qs_pow_assign(2, 30_000_000), crate::Error::SandboxError(..));
+
+			assert_call_eq!(Number::qs_shl(1, 63) -> Number, Number::from(BigIntType::from(1) << 63));
+			assert_call_eq!(Number::qs_shl(0, 1_000) -> Number, 0);
+			assert_call_err!(Number::qs_shl(1, 64), crate::Error::SandboxError(..));
+			assert_call_err!(Number::qs_shl_assign(1, 64), crate::Error::SandboxError(..));
+
+			assert_call_eq!(Number::qs_mul(1i64 << 31, 1i64 << 31) -> Number, 1i64 << 62);
+			assert_call_err!(Number::qs_mul(1i64 << 40, 1i64 << 40), crate::Error::SandboxError(..));
+			assert_call_err!(Number::qs_mul_assign(1i64 << 40, 1i64 << 40), crate::Error::SandboxError(..));
+		}
+
 		#[test]
 		fn shr() {
-			assert_call_eq!(Number::qs_shr(912, 12) -> Number, (912 as IntegerType).wrapping_shr(12));
-			assert_call_eq!(Number::qs_shr(-512, 4) -> Number, (-512 as IntegerType).wrapping_shr(4));
-			assert_call_eq!(Number::qs_shr(0xff1e24, 10) -> Number,
-				(0xff1e24 as IntegerType).wrapping_shr(10));
+			assert_call_eq!(Number::qs_shr(912, 12) -> Number, 912 >> 12);
+			assert_call_eq!(Number::qs_shr(-512, 4) -> Number, -512 >> 4);
+			assert_call_eq!(Number::qs_shr(0xff1e24, 10) -> Number, (0xff1e24 as IntegerType) >> 10);
 
 			assert_call_err!(Number::qs_shr(12.3, 0xfe), crate::Error::ValueError(..));
 			assert_call_err!(Number::qs_shr(0xed, -12.9), crate::Error::ValueError(..));
@@ -2204,16 +2583,28 @@ mod tests {
 			for _ in 0..1000 {
 				let n1 = random::<IntegerType>();
 				let n2 = random::<u32>() % 100; // to make it more a realistic shift amnt.
-				let n3 = random::<u32>();
+				let n3 = random::<u32>() % 10_000;
 
-				assert_call_eq!(Number::qs_shr(n1, n2) -> Number, n1.wrapping_shr(n2));
-				assert_call_eq!(Number::qs_shr(n1, n3) -> Number, n1.wrapping_shr(n3));
+				assert_call_eq!(Number::qs_shr(n1, n2) -> Number, Number::from(BigIntType::from(n1) >> n2 as usize));
+				assert_call_eq!(Number::qs_shr(n1, n3) -> Number, Number::from(BigIntType::from(n1) >> n3 as usize));
 			}
 
 			assert_call_missing_parameter!(Number::qs_shr(0), 0);
 			assert_call_idempotent!(Number::qs_shr(12, 14));
 		}
 
+		#[test]
+		fn shr_bounds() {
+			assert_call_err!(Number::qs_shr(1, -1), crate::Error::ValueError(..));
+			assert_call_err!(Number::qs_shr_assign(1, -1), crate::Error::ValueError(..));
+
+			let big = Number::from(BigIntType::from(1) << 100);
+			assert_call_eq!(Number::qs_shr(big.clone(), 100) -> Number, 1);
+			assert_call_eq!(Number::qs_shr(big.clone(), u64::MAX) -> Number, 0);
+			assert_call_eq!(Number::qs_shr(-big.clone(), 1_000) -> Number, -1);
+			assert_call_eq!(Number::qs_shr(-5, Number::from(BigIntType::from(1) << 100)) -> Number, -1);
+		}
+
 		#[test]
 		fn shr_assign() {
 			assert_call_non_idempotent!(Number::qs_shr_assign(0, 1));
@@ -2275,4 +2666,28 @@ mod tests {
 		assert!(matches!(Number::try_from("").unwrap_err(), FromStrError::BadFloat(..)));
 		assert!(matches!(Number::try_from(" ").unwrap_err(), FromStrError::BadFloat(..)));
 	}
+
+	#[test]
+	fn big_integers() {
+		let max = Number::from(IntegerType::MAX);
+		let big = max.clone() + Number::ONE;
+
+		assert!(big.is_big());
+		assert_eq!(big.to_string(), "9223372036854775808");
+		assert_eq!(big.clone() - Number::ONE, max);
+		assert!(!(big.clone() - Number::ONE).is_big(), "big ints should demote when they fit");
+
+		let two_pow_100 = Number::from(2).pow(100.into());
+		assert_eq!(two_pow_100.to_string_radix(10).unwrap(), "1267650600228229401496703205376");
+		assert_eq!(two_pow_100.to_string_radix(16).unwrap(), format!("1{}", "0".repeat(25)));
+		assert_eq!(Number::from_str_radix("1267650600228229401496703205376", 10).unwrap(), two_pow_100);
+		assert_eq!(Number::try_from("-1267650600228229401496703205376").unwrap(), -two_pow_100.clone());
+		assert_eq!(two_pow_100.clone().try_shr(100.into()).unwrap(), Number::ONE);
+		assert_eq!(two_pow_100.clone().try_bitand(two_pow_100.clone()).unwrap(), two_pow_100);
+		assert_eq!(two_pow_100.clone() / 2.into(), Number::from(2).pow(99.into()));
+
+		use std::collections::hash_map::DefaultHasher;
+		let hash = |n: &Number| { let mut h = DefaultHasher::new(); n.hash(&mut h); h.finish() };
+		assert_eq!(hash(&(two_pow_100.clone() + Number::ONE)), hash(&(Number::ONE + two_pow_100)));
+	}
 }
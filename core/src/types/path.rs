@@ -0,0 +1,276 @@
+//! The [`Path`] type in Quest.
+
+use crate::{Object, Args};
+use crate::sandbox::{self, Capability};
+use crate::types::{Text, List, Boolean, Null};
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+use tracing::instrument;
+
+/// A path on the filesystem.
+///
+/// Creating and manipulating paths never touches the filesystem itself, except for
+/// [`canonicalize`](Path::qs_canonicalize). Anywhere a path is expected, any object with a `@text`
+/// can be given instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Path(PathBuf);
+
+impl Path {
+	/// Creates a new [`Path`].
+	#[inline]
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self(path.into())
+	}
+
+	/// Gets the path as a standard library path.
+	#[inline]
+	pub fn as_path(&self) -> &std::path::Path {
+		&self.0
+	}
+
+	/// Gets the path out of `obj`, which is either a [`Path`] or something with a `@text`.
+	pub fn from_object(obj: &Object) -> crate::Result<PathBuf> {
+		if let Some(path) = obj.downcast::<Self>() {
+			Ok(path.0.clone())
+		} else {
+			Ok(PathBuf::from(obj.call_downcast::<Text>()?.as_ref()))
+		}
+	}
+}
+
+impl From<PathBuf> for Path {
+	#[inline]
+	fn from(path: PathBuf) -> Self {
+		Self(path)
+	}
+}
+
+impl From<Path> for PathBuf {
+	#[inline]
+	fn from(path: Path) -> Self {
+		path.0
+	}
+}
+
+impl Display for Path {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0.display(), f)
+	}
+}
+
+impl From<Path> for Text {
+	#[inline]
+	fn from(path: Path) -> Self {
+		Self::new(path.to_string())
+	}
+}
+
+/// Converts an optional part of a path to text, or `null` if it's missing.
+fn text_or_null(part: Option<&std::ffi::OsStr>) -> Object {
+	part.map_or_else(
+		|| Null.into(),
+		|part| part.to_string_lossy().into_owned().into())
+}
+
+impl Path {
+	/// Creates a new path by joining its arguments together.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The first part of the path.
+	/// 2. (optional, `@text`) Any further parts, which are joined onto it.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("src", "lib.rs").@text() == "src/lib.rs");
+	/// ```
+	#[instrument(name="Path::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let mut path = Self::from_object(args.try_arg(0)?)?;
+
+		for part in args.args(1..).unwrap_or_default().iter() {
+			path.push(Self::from_object(part)?);
+		}
+
+		Ok(Self(path).into())
+	}
+
+	/// Converts the path to [`Text`].
+	#[instrument(name="Path::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.to_string().into())
+	}
+
+	/// Gets a representation of the path, eg `Path("src/lib.rs")`.
+	#[instrument(name="Path::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("Path({:?})", this.to_string()).into())
+	}
+
+	/// Checks to see if two paths are the same, without accessing the filesystem.
+	#[instrument(name="Path::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+
+		if let Some(rhs) = rhs.downcast::<Self>() {
+			Ok((*this.try_downcast::<Self>()? == *rhs).into())
+		} else {
+			Ok(false.into())
+		}
+	}
+
+	/// Joins each argument onto the end of the path, returning a new one.
+	///
+	/// If an argument is an absolute path, it replaces everything before it.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("/usr").join("local", "bin") == Path("/usr/local/bin"));
+	/// assert(Path("/usr").join("/etc") == Path("/etc"));
+	/// ```
+	#[instrument(name="Path::join", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_join(this: &Object, args: Args) -> crate::Result<Object> {
+		let mut path = this.try_downcast::<Self>()?.0.clone();
+
+		for part in args.iter() {
+			path.push(Self::from_object(part)?);
+		}
+
+		Ok(Self(path).into())
+	}
+
+	/// Gets the directory the path is in, or `null` if it doesn't have one.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("src/lib.rs").parent() == Path("src"));
+	/// assert(Path("/").parent() == null);
+	/// ```
+	#[instrument(name="Path::parent", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_parent(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.0.parent().map_or_else(|| Null.into(), |parent| Self::new(parent).into()))
+	}
+
+	/// Gets the last part of the path, or `null` if there isn't one.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("src/lib.rs").basename() == "lib.rs");
+	/// ```
+	#[instrument(name="Path::basename", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_basename(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(text_or_null(this.try_downcast::<Self>()?.0.file_name()))
+	}
+
+	/// Gets the basename without its extension, or `null` if there isn't a basename.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("src/lib.rs").stem() == "lib");
+	/// ```
+	#[instrument(name="Path::stem", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stem(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(text_or_null(this.try_downcast::<Self>()?.0.file_stem()))
+	}
+
+	/// Gets the extension of the path, without the `.`, or `null` if there isn't one.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("archive.tar.gz").extension() == "gz");
+	/// assert(Path("Makefile").extension() == null);
+	/// ```
+	#[instrument(name="Path::extension", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_extension(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(text_or_null(this.try_downcast::<Self>()?.0.extension()))
+	}
+
+	/// Gets each part of the path as a [`List`] of [`Text`]s.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// parts = Path("/usr/bin").components();
+	///
+	/// assert(parts == ["/", "usr", "bin"]);
+	/// ```
+	#[instrument(name="Path::components", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_components(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.0.components()
+			.map(|component| Object::from(component.as_os_str().to_string_lossy().into_owned()))
+			.collect::<List>()
+			.into())
+	}
+
+	/// Checks to see if the path is absolute.
+	#[instrument(name="Path::absolute?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_absolute_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::new(this.try_downcast::<Self>()?.0.is_absolute()).into())
+	}
+
+	/// Gets the absolute path, with all symlinks and `.`/`..`s resolved.
+	///
+	/// This raises an `IoError` if the path doesn't exist.
+	#[instrument(name="Path::canonicalize", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_canonicalize(this: &Object, _: Args) -> crate::Result<Object> {
+		sandbox::require(Capability::FILESYSTEM, "Path::canonicalize")?;
+
+		let path = this.try_downcast::<Self>()?.0.clone();
+
+		Ok(Self(path.canonicalize()?).into())
+	}
+}
+
+impl_object_type!{
+for Path [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"==" => method Self::qs_eql,
+	"join" => method Self::qs_join,
+	"parent" => method Self::qs_parent,
+	"basename" => method Self::qs_basename,
+	"stem" => method Self::qs_stem,
+	"extension" => method Self::qs_extension,
+	"components" => method Self::qs_components,
+	"absolute?" => method Self::qs_absolute_q,
+	"canonicalize" => method Self::qs_canonicalize,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn path(path: &str) -> Object {
+		Path::new(path).into()
+	}
+
+	#[test]
+	fn join_and_parent() {
+		crate::init();
+
+		assert_call_eq!(Path::qs_join(path("/usr"), "local", Path::new("bin")) -> Path, Path::new("/usr/local/bin"));
+		assert_call_eq!(Path::qs_join(path("/usr"), "/etc") -> Path, Path::new("/etc"));
+		assert_call_eq!(Path::qs_parent(path("src/lib.rs")) -> Path, Path::new("src"));
+		assert_call!(Path::qs_parent(path("/")) -> Null; |_| true);
+	}
+
+	#[test]
+	fn parts() {
+		crate::init();
+
+		assert_call_eq!(Path::qs_basename(path("src/lib.rs")) -> Text, *"lib.rs");
+		assert_call_eq!(Path::qs_stem(path("src/lib.rs")) -> Text, *"lib");
+		assert_call_eq!(Path::qs_extension(path("a/archive.tar.gz")) -> Text, *"gz");
+		assert_call!(Path::qs_extension(path("Makefile")) -> Null; |_| true);
+		assert_call!(Path::qs_basename(path("/")) -> Null; |_| true);
+		assert_call_eq!(Path::qs_absolute_q(path("/etc")) -> Boolean, true);
+		assert_call_eq!(Path::qs_absolute_q(path("etc")) -> Boolean, false);
+	}
+}
@@ -0,0 +1,510 @@
+//! Running other programs.
+
+mod child;
+mod output;
+
+pub use child::Child;
+pub use output::Output;
+
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::sandbox::{self, Capability};
+use crate::types::{Text, Bytes, Null};
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use tracing::instrument;
+
+/// What to do with a process's stdout or stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Redirect {
+	/// Collect it into the [`Output`] once the process is waited on.
+	#[default]
+	Capture,
+	/// Share this process's stream.
+	Inherit,
+	/// Leave it to be read from the [`Child`] while it's running. Anything that isn't read by the
+	/// time it's waited on is discarded.
+	Pipe,
+	/// Discard it.
+	Null
+}
+
+impl Redirect {
+	fn stdio(self) -> Stdio {
+		match self {
+			Redirect::Capture | Redirect::Pipe => Stdio::piped(),
+			Redirect::Inherit => Stdio::inherit(),
+			Redirect::Null => Stdio::null()
+		}
+	}
+
+	/// Parses one of `"capture"`, `"inherit"`, `"pipe"` or `"null"`.
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		match obj.call_downcast::<Text>()?.as_ref() {
+			"capture" => Ok(Redirect::Capture),
+			"inherit" => Ok(Redirect::Inherit),
+			"pipe" => Ok(Redirect::Pipe),
+			"null" => Ok(Redirect::Null),
+			other => Err(ValueError::Messaged(format!("unknown redirect: {:?}", other)).into())
+		}
+	}
+}
+
+/// A program to run, along with how to run it.
+///
+/// Processes are built up and then either [`run`](Process::run), which waits for them to finish,
+/// or [`spawn`](Process::spawn)ed, which returns a [`Child`] straight away. Unless they're given
+/// input, run processes read from an empty stdin, and spawned ones from a pipe that can be
+/// written to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Process {
+	program: String,
+	args: Vec<String>,
+	env: Vec<(String, Option<String>)>,
+	cwd: Option<PathBuf>,
+	input: Option<Vec<u8>>,
+	stdout: Redirect,
+	stderr: Redirect,
+	source: Option<Box<Process>>
+}
+
+/// The processes started for a [`Process`] and anything piped into it.
+pub(super) struct Started {
+	child: process::Child,
+	/// Every process piped into `child`, starting with the first one.
+	upstream: Vec<process::Child>
+}
+
+impl Process {
+	/// Creates a new process that'll run `program`.
+	pub fn new(program: impl Into<String>) -> Self {
+		Self { program: program.into(), ..Self::default() }
+	}
+
+	/// Adds an argument.
+	pub fn arg(mut self, arg: impl Into<String>) -> Self {
+		self.args.push(arg.into());
+		self
+	}
+
+	/// Sets the environment variable `key`, or removes it if `value` is `None`.
+	pub fn env(mut self, key: impl Into<String>, value: Option<String>) -> Self {
+		self.env.push((key.into(), value));
+		self
+	}
+
+	/// Sets the directory to run in.
+	pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+		self.cwd = Some(cwd.into());
+		self
+	}
+
+	/// Sets what to write to stdin; it's closed once everything's been written.
+	pub fn input(mut self, input: impl Into<Vec<u8>>) -> Self {
+		self.input = Some(input.into());
+		self
+	}
+
+	/// Sets what to do with stdout.
+	pub fn stdout(mut self, stdout: Redirect) -> Self {
+		self.stdout = stdout;
+		self
+	}
+
+	/// Sets what to do with stderr.
+	pub fn stderr(mut self, stderr: Redirect) -> Self {
+		self.stderr = stderr;
+		self
+	}
+
+	/// Pipes this process's stdout into `into`'s stdin, returning the combined process.
+	///
+	/// Only the last process's stderr is captured; any earlier processes that would have their
+	/// stderr captured inherit it instead.
+	pub fn pipe(self, mut into: Self) -> Self {
+		into.set_first(self);
+		into
+	}
+
+	fn set_first(&mut self, source: Self) {
+		match self.source {
+			Some(ref mut first) => first.set_first(source),
+			None => self.source = Some(Box::new(source))
+		}
+	}
+
+	fn command(&self) -> Command {
+		let mut command = Command::new(&self.program);
+		command.args(&self.args);
+
+		for (key, value) in &self.env {
+			match value {
+				Some(value) => command.env(key, value),
+				None => command.env_remove(key)
+			};
+		}
+
+		if let Some(ref cwd) = self.cwd {
+			command.current_dir(cwd);
+		}
+
+		command
+	}
+
+	/// Starts this process and everything piped into it; the first process's stdin is `stdin`
+	/// unless it has input, and `piped` is whether this process's stdout is piped into another.
+	fn start(&self, stdin: fn() -> Stdio, piped: bool) -> crate::Result<Started> {
+		let mut command = self.command();
+		let mut upstream = Vec::new();
+
+		match (&self.source, &self.input) {
+			(Some(_), Some(_)) =>
+				return Err(ValueError::Messaged(format!("{} is piped into, so it can't be given input", self)).into()),
+			(Some(source), None) => {
+				let mut started = source.start(stdin, true)?;
+				let stdout = started.child.stdout.take().expect("stdout is always piped");
+
+				command.stdin(Stdio::from(stdout));
+				upstream = started.upstream;
+				upstream.push(started.child);
+			},
+			(None, Some(_)) => { command.stdin(Stdio::piped()); },
+			(None, None) => { command.stdin(stdin()); }
+		}
+
+		command.stdout(if piped { Stdio::piped() } else { self.stdout.stdio() });
+		command.stderr(match self.stderr {
+			Redirect::Capture | Redirect::Pipe if piped => Stdio::inherit(),
+			other => other.stdio()
+		});
+
+		let mut child =
+			match command.spawn() {
+				Ok(child) => child,
+				Err(err) => {
+					for mut process in upstream {
+						let _ = process.kill();
+						let _ = process.wait();
+					}
+
+					return Err(io::Error::new(err.kind(), format!("couldn't run {:?}: {}", self.program, err)).into());
+				}
+			};
+
+		if let Some(input) = self.input.clone() {
+			let mut stdin = child.stdin.take().expect("stdin is piped when there's input");
+
+			// write from another thread, so programs that write a lot before reading everything
+			// don't deadlock. if the program exits without reading it all, that's its business.
+			std::thread::spawn(move || { let _ = stdin.write_all(&input); });
+		}
+
+		Ok(Started { child, upstream })
+	}
+
+	/// Runs the process, waiting for it (and anything piped into it) to finish.
+	pub fn run(&self) -> crate::Result<Output> {
+		sandbox::require(Capability::PROCESS, "Process::run")?;
+
+		let child = Child::new(self.start(Stdio::null, false)?, self.stdout, self.stderr);
+
+		Ok(child.wait()?)
+	}
+
+	/// Starts the process without waiting for it to finish.
+	pub fn spawn(&self) -> crate::Result<Child> {
+		sandbox::require(Capability::PROCESS, "Process::spawn")?;
+
+		Ok(Child::new(self.start(Stdio::piped, false)?, self.stdout, self.stderr))
+	}
+}
+
+impl Display for Process {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let Some(ref source) = self.source {
+			write!(f, "{} | ", source)?;
+		}
+
+		write!(f, "Process({:?}", self.program)?;
+
+		for arg in &self.args {
+			write!(f, ", {:?}", arg)?;
+		}
+
+		write!(f, ")")
+	}
+}
+
+fn text_arg(args: &Args, idx: usize) -> crate::Result<String> {
+	Ok(args.try_arg(idx)?.call_downcast::<Text>()?.to_string())
+}
+
+/// Quest methods
+impl Process {
+	/// Creates a new process.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The program to run.
+	/// 2. (optional, `@text`) Any arguments to pass to it.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// output = Process("echo", "hello").run();
+	///
+	/// assert(output.stdout() == "hello\n");
+	/// ```
+	#[instrument(name="Process::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let mut process = Self::new(text_arg(&args, 0)?);
+
+		for idx in 1..args.len() {
+			process = process.arg(text_arg(&args, idx)?);
+		}
+
+		Ok(process.into())
+	}
+
+	/// Adds each argument to the end of the process's arguments, returning the process.
+	#[instrument(name="Process::args", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_args(this: &Object, args: Args) -> crate::Result<Object> {
+		for idx in 0..args.len() {
+			let arg = text_arg(&args, idx)?;
+			this.try_downcast_mut::<Self>()?.args.push(arg);
+		}
+
+		Ok(this.clone())
+	}
+
+	/// Sets an environment variable, returning the process.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The variable's name.
+	/// 2. (required, `@text`) Its value; if it's `null`, the variable is removed instead.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// output = Process("sh", "-c", "echo $GREETING").env("GREETING", "hi").run();
+	///
+	/// assert(output.stdout() == "hi\n");
+	/// ```
+	#[instrument(name="Process::env", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_env(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = text_arg(&args, 0)?;
+		let value = args.try_arg(1)?;
+		let value =
+			if value.is_a::<Null>() {
+				None
+			} else {
+				Some(value.call_downcast::<Text>()?.to_string())
+			};
+
+		this.try_downcast_mut::<Self>()?.env.push((key, value));
+
+		Ok(this.clone())
+	}
+
+	/// Sets the directory to run in, returning the process.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The directory.
+	#[instrument(name="Process::cwd", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cwd(this: &Object, args: Args) -> crate::Result<Object> {
+		let cwd = crate::types::Path::from_object(args.try_arg(0)?)?;
+
+		this.try_downcast_mut::<Self>()?.cwd = Some(cwd);
+
+		Ok(this.clone())
+	}
+
+	/// Sets the input written to the process's stdin, returning the process.
+	///
+	/// # Arguments
+	/// 1. (required, `@text` or `Bytes`) The input.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// output = Process("tr", "a-z", "A-Z").stdin("shout").run();
+	///
+	/// assert(output.stdout() == "SHOUT");
+	/// ```
+	#[instrument(name="Process::stdin", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_stdin(this: &Object, args: Args) -> crate::Result<Object> {
+		let input = Bytes::data_of(args.try_arg(0)?)?;
+
+		this.try_downcast_mut::<Self>()?.input = Some(input);
+
+		Ok(this.clone())
+	}
+
+	/// Sets what to do with stdout, returning the process.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) One of `"capture"` (the default) to collect it into the output,
+	///    `"inherit"` to share this program's stdout, `"pipe"` to read it from the spawned
+	///    process while it's running, or `"null"` to discard it.
+	#[instrument(name="Process::stdout", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_stdout(this: &Object, args: Args) -> crate::Result<Object> {
+		let redirect = Redirect::from_object(args.try_arg(0)?)?;
+
+		this.try_downcast_mut::<Self>()?.stdout = redirect;
+
+		Ok(this.clone())
+	}
+
+	/// Sets what to do with stderr, returning the process; see `stdout` for the options.
+	#[instrument(name="Process::stderr", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_stderr(this: &Object, args: Args) -> crate::Result<Object> {
+		let redirect = Redirect::from_object(args.try_arg(0)?)?;
+
+		this.try_downcast_mut::<Self>()?.stderr = redirect;
+
+		Ok(this.clone())
+	}
+
+	/// Pipes this process's stdout into another's stdin, returning the combined process.
+	///
+	/// Only the last process's stderr is captured.
+	///
+	/// # Arguments
+	/// 1. (required, `Process`) The process to pipe into.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// output = Process("printf", "b\na\n").pipe(Process("sort")).run();
+	///
+	/// assert(output.stdout() == "a\nb\n");
+	/// ```
+	#[instrument(name="Process::pipe", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_pipe(this: &Object, args: Args) -> crate::Result<Object> {
+		let into = args.try_arg(0)?.try_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.pipe(into).into())
+	}
+
+	/// Runs the process, waiting for it to finish and returning its [`Output`].
+	#[instrument(name="Process::run", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_run(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.run()?.into())
+	}
+
+	/// Starts the process without waiting for it, returning a [`Child`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// child = Process("cat").stdout("pipe").spawn();
+	/// child.write("hello\n");
+	///
+	/// assert(child.read_line() == "hello\n");
+	/// assert(child.wait().success?());
+	/// ```
+	#[instrument(name="Process::spawn", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_spawn(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.spawn()?.into())
+	}
+
+	#[instrument(name="Process::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+}
+
+impl_object_type!{
+for Process [(parents super::Basic)]:
+	"Child" => const Child::mapping().clone(),
+	"Output" => const Output::mapping().clone(),
+	"()" => method Self::qs_call,
+	"args" => method Self::qs_args,
+	"env" => method Self::qs_env,
+	"cwd" => method Self::qs_cwd,
+	"stdin" => method Self::qs_stdin,
+	"stdout" => method Self::qs_stdout,
+	"stderr" => method Self::qs_stderr,
+	"pipe" => method Self::qs_pipe,
+	"|" => method Self::qs_pipe,
+	"run" => method Self::qs_run,
+	"spawn" => method Self::qs_spawn,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+
+	fn sh(script: &str) -> Process {
+		Process::new("sh").arg("-c").arg(script)
+	}
+
+	#[test]
+	fn running() {
+		let output = sh("echo out; echo err >&2; exit 3").run().unwrap();
+		assert_eq!(output.status(), Some(3));
+		assert!(!output.is_success());
+		assert_eq!(output.stdout(), b"out\n");
+		assert_eq!(output.stderr(), b"err\n");
+
+		let output = sh("echo $GREETING; pwd").env("GREETING", Some("hi".into())).cwd("/").run().unwrap();
+		assert_eq!(output.stdout(), b"hi\n/\n");
+
+		let output = Process::new("cat").input("some input").stderr(Redirect::Null).run().unwrap();
+		assert!(output.is_success());
+		assert_eq!(output.stdout(), b"some input");
+
+		assert!(Process::new("/does/not/exist").run().is_err());
+	}
+
+	#[test]
+	fn piping() {
+		let output = Process::new("printf").arg("c\\na\\nb\\n")
+			.pipe(Process::new("sort"))
+			.pipe(Process::new("head").arg("-n").arg("2"))
+			.run().unwrap();
+
+		assert_eq!(output.stdout(), b"a\nb\n");
+
+		let piped = Process::new("cat").pipe(Process::new("cat").input("nope"));
+		assert!(piped.run().is_err());
+	}
+
+	#[test]
+	fn spawning() {
+		let child = sh("read line; echo \"got $line\"; echo err >&2").stdout(Redirect::Pipe).spawn().unwrap();
+		child.write(b"hello\n").unwrap();
+		assert_eq!(child.read_line(false).unwrap().as_deref(), Some(&b"got hello\n"[..]));
+
+		// piped output is read from the child, rather than being collected.
+		let output = child.wait().unwrap();
+		assert!(output.is_success());
+		assert!(output.stdout().is_empty());
+		assert_eq!(output.stderr(), b"err\n");
+		assert!(child.read_line(false).is_err());
+
+		let child = Process::new("sleep").arg("10").spawn().unwrap();
+		assert!(child.is_running().unwrap());
+		child.kill().unwrap();
+		let output = child.wait().unwrap();
+		assert!(!output.is_success());
+		assert_eq!(output.status(), None);
+		assert!(!child.is_running().unwrap());
+	}
+
+	#[test]
+	fn quest_methods() {
+		crate::init();
+
+		let process = Process::qs_call(&Object::default(), args!("echo", "a")).unwrap();
+		Process::qs_args(&process, args!("b")).unwrap();
+		assert_call_eq!(Process::qs_inspect(process.clone()) -> Text, *r#"Process("echo", "a", "b")"#);
+		assert!(Process::qs_stdout(&process, args!("sideways")).is_err());
+
+		let output = Process::qs_run(&process, args!()).unwrap();
+		assert_call_eq!(Output::qs_stdout(output.clone()) -> Text, *"a b\n");
+		assert_call_eq!(Output::qs_success_q(output.clone()) -> crate::types::Boolean, true);
+	}
+}
@@ -0,0 +1,305 @@
+use super::{Started, Output, Redirect};
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Bytes, Text};
+use parking_lot::Mutex;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{self, ChildStdin, ChildStdout, ChildStderr, ExitStatus};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+
+/// How often to check whether a process has exited.
+///
+/// Processes are polled so that they can be killed while they're being waited on.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Debug)]
+struct Inner {
+	pid: u32,
+	child: Mutex<process::Child>,
+	upstream: Vec<Mutex<process::Child>>,
+	stdin: Mutex<Option<ChildStdin>>,
+	stdout: Mutex<Option<BufReader<ChildStdout>>>,
+	stderr: Mutex<Option<BufReader<ChildStderr>>>,
+	redirects: (Redirect, Redirect),
+	output: Mutex<Option<Output>>
+}
+
+/// A process that's been spawned, along with anything piped into it.
+///
+/// Writing, reading and killing can all happen from separate threads, even while waiting.
+#[derive(Debug, Clone)]
+pub struct Child(Arc<Inner>);
+
+/// Waits for `child` to exit, without holding its lock in the meantime.
+fn wait_for(child: &Mutex<process::Child>) -> io::Result<ExitStatus> {
+	loop {
+		if let Some(status) = child.lock().try_wait()? {
+			return Ok(status);
+		}
+
+		std::thread::sleep(POLL_INTERVAL);
+	}
+}
+
+fn not_piped(name: &str) -> crate::Error {
+	ValueError::Messaged(format!("{} isn't piped, or has already been closed", name)).into()
+}
+
+impl Child {
+	pub(super) fn new(started: Started, stdout: Redirect, stderr: Redirect) -> Self {
+		let Started { mut child, mut upstream } = started;
+
+		// stdin belongs to the first process in the pipeline.
+		let stdin = upstream.first_mut().map_or(child.stdin.take(), |first| first.stdin.take());
+
+		Self(Arc::new(Inner {
+			pid: child.id(),
+			stdin: Mutex::new(stdin),
+			stdout: Mutex::new(child.stdout.take().map(BufReader::new)),
+			stderr: Mutex::new(child.stderr.take().map(BufReader::new)),
+			child: Mutex::new(child),
+			upstream: upstream.into_iter().map(Mutex::new).collect(),
+			redirects: (stdout, stderr),
+			output: Mutex::new(None)
+		}))
+	}
+
+	/// The OS's id for the (last) process.
+	pub fn pid(&self) -> u32 {
+		self.0.pid
+	}
+
+	/// Writes all of `data` to stdin.
+	pub fn write(&self, data: &[u8]) -> crate::Result<()> {
+		match *self.0.stdin.lock() {
+			Some(ref mut stdin) => Ok(stdin.write_all(data)?),
+			None => Err(not_piped("stdin"))
+		}
+	}
+
+	/// Closes stdin, so the process knows there's nothing left to read.
+	pub fn close_stdin(&self) {
+		self.0.stdin.lock().take();
+	}
+
+	fn with_stream<T>(&self, stderr: bool, func: impl FnOnce(&mut dyn BufRead) -> io::Result<T>) -> crate::Result<T> {
+		if stderr {
+			match *self.0.stderr.lock() {
+				Some(ref mut stderr) => Ok(func(stderr)?),
+				None => Err(not_piped("stderr"))
+			}
+		} else {
+			match *self.0.stdout.lock() {
+				Some(ref mut stdout) => Ok(func(stdout)?),
+				None => Err(not_piped("stdout"))
+			}
+		}
+	}
+
+	/// Reads the next line from stdout (or stderr), including the trailing `\n`, or `None` once
+	/// the process has closed it.
+	pub fn read_line(&self, stderr: bool) -> crate::Result<Option<Vec<u8>>> {
+		self.with_stream(stderr, |stream| {
+			let mut line = Vec::new();
+
+			if stream.read_until(b'\n', &mut line)? == 0 {
+				Ok(None)
+			} else {
+				Ok(Some(line))
+			}
+		})
+	}
+
+	/// Reads everything from stdout (or stderr) until the process closes it.
+	pub fn read_to_end(&self, stderr: bool) -> crate::Result<Vec<u8>> {
+		self.with_stream(stderr, |stream| {
+			let mut buf = Vec::new();
+			stream.read_to_end(&mut buf)?;
+			Ok(buf)
+		})
+	}
+
+	/// Kills the process, along with anything piped into it.
+	pub fn kill(&self) -> io::Result<()> {
+		for process in &self.0.upstream {
+			let _ = process.lock().kill();
+		}
+
+		self.0.child.lock().kill()
+	}
+
+	/// Checks to see if the (last) process is still running.
+	pub fn is_running(&self) -> io::Result<bool> {
+		if self.0.output.try_lock().is_some_and(|output| output.is_some()) {
+			return Ok(false);
+		}
+
+		Ok(self.0.child.lock().try_wait()?.is_none())
+	}
+
+	/// Waits for every process to exit, returning the last one's [`Output`].
+	///
+	/// Stdin is closed first, captured output is collected, and anything left in pipes is
+	/// discarded. Waiting more than once returns the same output.
+	pub fn wait(&self) -> io::Result<Output> {
+		let mut output = self.0.output.lock();
+
+		if let Some(ref output) = *output {
+			return Ok(output.clone());
+		}
+
+		self.close_stdin();
+
+		let (stdout_redirect, stderr_redirect) = self.0.redirects;
+		let stdout = self.0.stdout.lock().take().filter(|_| stdout_redirect == Redirect::Capture);
+		let stderr = self.0.stderr.lock().take().filter(|_| stderr_redirect == Redirect::Capture);
+
+		// stderr's read from another thread, so neither pipe can fill up and block the process.
+		let stderr = stderr.map(|mut stderr| std::thread::spawn(move || {
+			let mut buf = Vec::new();
+			stderr.read_to_end(&mut buf).map(|_| buf)
+		}));
+
+		let mut stdout_buf = Vec::new();
+		if let Some(mut stdout) = stdout {
+			stdout.read_to_end(&mut stdout_buf)?;
+		}
+
+		let stderr_buf =
+			match stderr {
+				Some(handle) => handle.join().expect("reading stderr panicked")?,
+				None => Vec::new()
+			};
+
+		let status = wait_for(&self.0.child)?;
+
+		for process in &self.0.upstream {
+			wait_for(process)?;
+		}
+
+		let result = Output::new(status, stdout_buf, stderr_buf);
+		*output = Some(result.clone());
+
+		Ok(result)
+	}
+}
+
+/// Converts the optional `"stdout"` or `"stderr"` argument to whether it's stderr.
+fn stream_arg(args: &Args) -> crate::Result<bool> {
+	match args.arg(0) {
+		None => Ok(false),
+		Some(stream) => match stream.call_downcast::<Text>()?.as_ref() {
+			"stdout" => Ok(false),
+			"stderr" => Ok(true),
+			other => Err(ValueError::Messaged(format!("unknown stream: {:?}", other)).into())
+		}
+	}
+}
+
+/// Quest methods
+impl Child {
+	/// Gets the process's id.
+	#[instrument(name="Process::Child::pid", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_pid(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.pid().into())
+	}
+
+	/// Writes to the process's stdin, returning the child.
+	///
+	/// # Arguments
+	/// 1. (required, `@text` or `Bytes`) The data to write.
+	#[instrument(name="Process::Child::write", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_write(this: &Object, args: Args) -> crate::Result<Object> {
+		let data = Bytes::data_of(args.try_arg(0)?)?;
+
+		this.try_downcast::<Self>()?.clone().write(&data)?;
+
+		Ok(this.clone())
+	}
+
+	/// Closes the process's stdin, returning the child.
+	#[instrument(name="Process::Child::close_stdin", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_close_stdin(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast::<Self>()?.close_stdin();
+
+		Ok(this.clone())
+	}
+
+	/// Reads the next line, including the trailing `\n`, returning `null` once the process has
+	/// closed the stream.
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) Which stream to read from: `"stdout"` (the default) or `"stderr"`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// child = Process("ls").stdout("pipe").spawn();
+	///
+	/// while({ line = child.read_line() }, {
+	/// 	disp("found", line);
+	/// });
+	/// ```
+	#[instrument(name="Process::Child::read_line", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_read_line(this: &Object, args: Args) -> crate::Result<Object> {
+		let stderr = stream_arg(&args)?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.read_line(stderr)?
+			.map(|line| String::from_utf8_lossy(&line).into_owned().into())
+			.unwrap_or_default())
+	}
+
+	/// Reads everything until the process closes the stream.
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) Which stream to read from: `"stdout"` (the default) or `"stderr"`.
+	#[instrument(name="Process::Child::read", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_read(this: &Object, args: Args) -> crate::Result<Object> {
+		let stderr = stream_arg(&args)?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(String::from_utf8_lossy(&this.read_to_end(stderr)?).into_owned().into())
+	}
+
+	/// Waits for the process to exit, returning its [`Output`].
+	#[instrument(name="Process::Child::wait", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_wait(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.wait()?.into())
+	}
+
+	/// Kills the process, returning the child.
+	#[instrument(name="Process::Child::kill", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_kill(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast::<Self>()?.kill()?;
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if the process is still running.
+	#[instrument(name="Process::Child::running?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_running_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_running()?.into())
+	}
+
+	#[instrument(name="Process::Child::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("Child(pid={})", this.try_downcast::<Self>()?.pid()).into())
+	}
+}
+
+impl_object_type!{
+for Child [(parents crate::types::Basic)]:
+	"pid" => method Self::qs_pid,
+	"write" => method Self::qs_write,
+	"close_stdin" => method Self::qs_close_stdin,
+	"read_line" => method Self::qs_read_line,
+	"read" => method Self::qs_read,
+	"wait" => method Self::qs_wait,
+	"kill" => method Self::qs_kill,
+	"running?" => method Self::qs_running_q,
+	"inspect" => method Self::qs_inspect,
+}
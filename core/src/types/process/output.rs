@@ -0,0 +1,100 @@
+use crate::{Object, Args};
+use crate::types::{Boolean, Bytes, Number};
+use std::process::ExitStatus;
+use tracing::instrument;
+
+/// What a process finished with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+	status: Option<i32>,
+	stdout: Vec<u8>,
+	stderr: Vec<u8>
+}
+
+impl Output {
+	pub(super) fn new(status: ExitStatus, stdout: Vec<u8>, stderr: Vec<u8>) -> Self {
+		Self { status: status.code(), stdout, stderr }
+	}
+
+	/// The exit code, or `None` if the process was killed by a signal.
+	pub fn status(&self) -> Option<i32> {
+		self.status
+	}
+
+	/// Checks to see if the process exited with a code of `0`.
+	pub fn is_success(&self) -> bool {
+		self.status == Some(0)
+	}
+
+	/// Everything the process wrote to stdout, if it was captured.
+	pub fn stdout(&self) -> &[u8] {
+		&self.stdout
+	}
+
+	/// Everything the process wrote to stderr, if it was captured.
+	pub fn stderr(&self) -> &[u8] {
+		&self.stderr
+	}
+}
+
+/// Quest methods
+impl Output {
+	/// Gets the exit code, or `null` if the process was killed by a signal.
+	#[instrument(name="Process::Output::status", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_status(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.status()
+			.map(|code| Number::from(code).into())
+			.unwrap_or_default())
+	}
+
+	/// Checks to see if the process exited with a code of `0`.
+	#[instrument(name="Process::Output::success?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_success_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::new(this.try_downcast::<Self>()?.is_success()).into())
+	}
+
+	/// Gets the captured stdout as text; anything that isn't valid UTF-8 is replaced with `U+FFFD`.
+	#[instrument(name="Process::Output::stdout", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stdout(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(String::from_utf8_lossy(this.try_downcast::<Self>()?.stdout()).into_owned().into())
+	}
+
+	/// Gets the captured stderr as text; anything that isn't valid UTF-8 is replaced with `U+FFFD`.
+	#[instrument(name="Process::Output::stderr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stderr(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(String::from_utf8_lossy(this.try_downcast::<Self>()?.stderr()).into_owned().into())
+	}
+
+	/// Gets the captured stdout as [`Bytes`].
+	#[instrument(name="Process::Output::stdout_bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stdout_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Bytes::from(this.try_downcast::<Self>()?.stdout().to_vec()).into())
+	}
+
+	/// Gets the captured stderr as [`Bytes`].
+	#[instrument(name="Process::Output::stderr_bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stderr_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Bytes::from(this.try_downcast::<Self>()?.stderr().to_vec()).into())
+	}
+
+	#[instrument(name="Process::Output::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		match this.status {
+			Some(code) => Ok(format!("Output(status={})", code).into()),
+			None => Ok("Output(killed)".into())
+		}
+	}
+}
+
+impl_object_type!{
+for Output [(parents crate::types::Basic)]:
+	"status" => method Self::qs_status,
+	"success?" => method Self::qs_success_q,
+	"stdout" => method Self::qs_stdout,
+	"stderr" => method Self::qs_stderr,
+	"stdout_bytes" => method Self::qs_stdout_bytes,
+	"stderr_bytes" => method Self::qs_stderr_bytes,
+	"inspect" => method Self::qs_inspect,
+}
@@ -0,0 +1,901 @@
+//! The [`Rational`] type in Quest.
+
+use crate::{Object, Args};
+use crate::types::{Text, Boolean, Number};
+use crate::types::number::{BigIntType, FloatType, ToStringRadixError};
+use crate::error::{TypeError, ValueError};
+use num_rational::BigRational;
+use num_traits::{Zero, Signed, ToPrimitive};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops;
+use tracing::instrument;
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+///
+/// Arithmetic between rationals and integers is exact, whereas arithmetic involving a float
+/// yields a float [`Number`]. Comparisons are always exact, even against floats. Because in-place
+/// operators can't turn a rational into a float, they raise an error instead.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rational(BigRational);
+
+impl Debug for Rational {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.debug_tuple("Rational").field(&self.0).finish()
+		} else {
+			write!(f, "Rational({})", self)
+		}
+	}
+}
+
+impl Display for Rational {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+impl Default for Rational {
+	fn default() -> Self {
+		Self(BigRational::zero())
+	}
+}
+
+/// An error that's returned when dividing by zero, or creating a rational with a zero denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivideByZero;
+
+impl Display for DivideByZero {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "division by zero")
+	}
+}
+
+impl std::error::Error for DivideByZero {}
+
+impl From<DivideByZero> for crate::Error {
+	fn from(err: DivideByZero) -> Self {
+		ValueError::Messaged(err.to_string()).into()
+	}
+}
+
+/// An error that's returned when trying to convert a `NaN` or infinite [`Number`] to a rational.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NotFinite(pub FloatType);
+
+impl Display for NotFinite {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{} can't be represented as a rational", self.0)
+	}
+}
+
+impl std::error::Error for NotFinite {}
+
+impl From<NotFinite> for crate::Error {
+	fn from(err: NotFinite) -> Self {
+		ValueError::Messaged(err.to_string()).into()
+	}
+}
+
+impl Rational {
+	/// Creates a new rational, reducing it to lowest terms.
+	pub fn new(numer: BigIntType, denom: BigIntType) -> Result<Self, DivideByZero> {
+		if denom.is_zero() {
+			Err(DivideByZero)
+		} else {
+			Ok(Self(BigRational::new(numer, denom)))
+		}
+	}
+
+	/// Creates a new rational with a denominator of one.
+	#[inline]
+	pub fn from_integer(numer: BigIntType) -> Self {
+		Self(BigRational::from_integer(numer))
+	}
+
+	/// The numerator of `self`, which has the same sign as `self`.
+	#[inline]
+	pub fn numer(&self) -> &BigIntType {
+		self.0.numer()
+	}
+
+	/// The denominator of `self`, which is always positive.
+	#[inline]
+	pub fn denom(&self) -> &BigIntType {
+		self.0.denom()
+	}
+
+	/// Checks to see if `self` is a whole number.
+	#[inline]
+	pub fn is_whole(&self) -> bool {
+		self.0.is_integer()
+	}
+
+	/// Converts `self` to a string in the given `radix`, of the form `numer/denom`, or just
+	/// `numer` if it's whole.
+	pub fn to_string_radix(&self, radix: u32) -> Result<String, ToStringRadixError> {
+		if !(2..=36).contains(&radix) {
+			return Err(ToStringRadixError::InvalidRadix(radix));
+		}
+
+		if self.is_whole() {
+			Ok(self.numer().to_str_radix(radix))
+		} else {
+			Ok(format!("{}/{}", self.numer().to_str_radix(radix), self.denom().to_str_radix(radix)))
+		}
+	}
+
+	/// Converts `self` to the closest [`FloatType`].
+	pub fn to_float(&self) -> FloatType {
+		self.0.to_f64().unwrap_or(FloatType::NAN)
+	}
+
+	/// Converts `self` to a [`Number`], which is exact only if `self` is whole.
+	pub fn to_number(&self) -> Number {
+		if self.is_whole() {
+			Number::from(self.numer().clone())
+		} else {
+			Number::from(self.to_float())
+		}
+	}
+
+	/// Divides `self` by `divisor`, returning an error if `divisor` is zero.
+	pub fn checked_div(self, divisor: Self) -> Result<Self, DivideByZero> {
+		if divisor.0.is_zero() {
+			Err(DivideByZero)
+		} else {
+			Ok(Self(self.0 / divisor.0))
+		}
+	}
+
+	/// Gets the remainder of `self` divided by `divisor`, returning an error if `divisor` is zero.
+	///
+	/// Like [`Number`], the remainder has the same sign as `self`.
+	pub fn checked_rem(self, divisor: Self) -> Result<Self, DivideByZero> {
+		if divisor.0.is_zero() {
+			Err(DivideByZero)
+		} else {
+			Ok(Self(self.0 % divisor.0))
+		}
+	}
+
+	/// Raises `self` to an integral power, returning an error if `self` is zero and `exponent` is
+	/// negative.
+	pub fn checked_pow(&self, exponent: i32) -> Result<Self, DivideByZero> {
+		if exponent < 0 && self.0.is_zero() {
+			Err(DivideByZero)
+		} else {
+			Ok(Self(self.0.pow(exponent)))
+		}
+	}
+
+	/// How many bits the numerator and denominator need in total.
+	fn bits(&self) -> u64 {
+		self.numer().bits() + self.denom().bits()
+	}
+
+	/// Compares `self` against a float exactly, returning `None` if `rhs` is `NaN`.
+	fn partial_cmp_float(&self, rhs: FloatType) -> Option<Ordering> {
+		if rhs.is_nan() {
+			None
+		} else if rhs.is_infinite() {
+			Some(if rhs.is_sign_positive() { Ordering::Less } else { Ordering::Greater })
+		} else {
+			BigRational::from_float(rhs).map(|rhs| self.0.cmp(&rhs))
+		}
+	}
+}
+
+impl TryFrom<Number> for Rational {
+	type Error = NotFinite;
+
+	/// Converts `number` to a rational exactly; floats are converted to their exact binary value.
+	fn try_from(number: Number) -> Result<Self, Self::Error> {
+		match number.try_to_big() {
+			Ok(integer) => Ok(Self::from_integer(integer)),
+			Err(_) => {
+				let float = FloatType::from(number);
+				BigRational::from_float(float).map(Self).ok_or(NotFinite(float))
+			}
+		}
+	}
+}
+
+impl From<Rational> for Number {
+	#[inline]
+	fn from(rational: Rational) -> Self {
+		rational.to_number()
+	}
+}
+
+macro_rules! impl_exact_ops {
+	($($trait:ident $method:ident $assign_trait:ident $assign_method:ident)*) => {
+		$(
+			impl ops::$trait for Rational {
+				type Output = Self;
+
+				#[inline]
+				fn $method(self, rhs: Self) -> Self {
+					Self(ops::$trait::$method(self.0, rhs.0))
+				}
+			}
+
+			impl ops::$assign_trait for Rational {
+				#[inline]
+				fn $assign_method(&mut self, rhs: Self) {
+					ops::$assign_trait::$assign_method(&mut self.0, rhs.0)
+				}
+			}
+		)*
+	};
+}
+
+impl_exact_ops! {
+	Add add AddAssign add_assign
+	Sub sub SubAssign sub_assign
+	Mul mul MulAssign mul_assign
+}
+
+impl ops::Neg for Rational {
+	type Output = Self;
+
+	#[inline]
+	fn neg(self) -> Self {
+		Self(-self.0)
+	}
+}
+
+/// The arithmetic operations that can be performed between [`Rational`]s and [`Number`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Rem,
+}
+
+impl BinaryOp {
+	fn as_str(self) -> &'static str {
+		match self {
+			Self::Add => "+",
+			Self::Sub => "-",
+			Self::Mul => "*",
+			Self::Div => "/",
+			Self::Rem => "%",
+		}
+	}
+}
+
+/// The other operand of an arithmetic operation involving a [`Rational`].
+#[derive(Debug, Clone)]
+enum Operand {
+	/// Rationals and integers, which keep the result exact.
+	Exact(Rational),
+	/// Floats, which cause the result to be a float too.
+	Inexact(FloatType)
+}
+
+impl From<Number> for Operand {
+	fn from(number: Number) -> Self {
+		match number.try_to_big() {
+			Ok(integer) => Self::Exact(Rational::from_integer(integer)),
+			Err(_) => Self::Inexact(number.into())
+		}
+	}
+}
+
+impl Operand {
+	/// Converts `obj` to an operand, calling `@num` on it if it's not a [`Rational`] already.
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		if let Some(rational) = obj.downcast::<Rational>() {
+			Ok(Self::Exact(rational.clone()))
+		} else {
+			Ok(obj.call_downcast::<Number>()?.clone().into())
+		}
+	}
+
+	fn into_number(self) -> Number {
+		match self {
+			Self::Exact(rational) => Number::from(rational.to_float()),
+			Self::Inexact(float) => Number::from(float)
+		}
+	}
+
+	fn apply(self, op: BinaryOp, rhs: Self) -> crate::Result<Object> {
+		match (self, rhs) {
+			(Self::Exact(lhs), Self::Exact(rhs)) => {
+				// the result can need as many bits as both operands put together.
+				crate::sandbox::check_integer_bits(lhs.bits() + rhs.bits())?;
+
+				Ok(match op {
+					BinaryOp::Add => lhs + rhs,
+					BinaryOp::Sub => lhs - rhs,
+					BinaryOp::Mul => lhs * rhs,
+					BinaryOp::Div => lhs.checked_div(rhs)?,
+					BinaryOp::Rem => lhs.checked_rem(rhs)?,
+				}.into())
+			},
+			(lhs, rhs) => {
+				let (lhs, rhs) = (lhs.into_number(), rhs.into_number());
+
+				Ok(match op {
+					BinaryOp::Add => lhs + rhs,
+					BinaryOp::Sub => lhs - rhs,
+					BinaryOp::Mul => lhs * rhs,
+					BinaryOp::Div => lhs / rhs,
+					BinaryOp::Rem => lhs % rhs,
+				}.into())
+			}
+		}
+	}
+
+	fn partial_cmp(&self, rational: &Rational) -> Option<Ordering> {
+		match self {
+			Self::Exact(lhs) => Some(lhs.cmp(rational)),
+			Self::Inexact(lhs) => rational.partial_cmp_float(*lhs).map(Ordering::reverse)
+		}
+	}
+}
+
+impl Rational {
+	/// Performs `lhs op rhs`, where the left-hand side is a [`Number`].
+	///
+	/// This is used by [`Number`]'s operators so that mixed arithmetic stays exact.
+	pub(crate) fn number_op(op: BinaryOp, lhs: &Number, rhs: &Self) -> crate::Result<Object> {
+		Operand::from(lhs.clone()).apply(op, Operand::Exact(rhs.clone()))
+	}
+
+	/// Compares a [`Number`] against `rhs` exactly, returning `None` if `lhs` is `NaN`.
+	pub(crate) fn number_cmp(lhs: &Number, rhs: &Self) -> Option<Ordering> {
+		Operand::from(lhs.clone()).partial_cmp(rhs)
+	}
+
+	fn binary_op(op: BinaryOp, this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = Operand::from_object(args.try_arg(0)?)?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Operand::Exact(this).apply(op, rhs)
+	}
+
+	fn binary_op_assign(op: BinaryOp, this: &Object, args: Args) -> crate::Result<Object> {
+		let result = Self::binary_op(op, this, args)?;
+
+		Self::store(this, &result, op.as_str())
+	}
+
+	/// Stores `result` into `this`, as long as it's a rational.
+	///
+	/// In-place operators can't change `this` into a float, so rather than quietly giving a
+	/// different answer to the non-assigning operator, an error is returned.
+	fn store(this: &Object, result: &Object, op: &str) -> crate::Result<Object> {
+		let result = result.downcast::<Self>()
+			.ok_or_else(|| ValueError::Messaged(format!(
+				"the result of `{}=` isn't a rational; use `x = x {} y` instead", op, op)))?
+			.clone();
+
+		*this.try_downcast_mut::<Self>()? = result;
+		Ok(this.clone())
+	}
+
+	/// Calls [`Number`]'s version of `attr` with `this` converted to a number.
+	fn via_number(attr: &'static str, this: &Object, args: Args) -> crate::Result<Object> {
+		let number = Object::from(this.try_downcast::<Self>()?.to_number());
+
+		number.call_attr_lit(attr, args.as_ref())
+	}
+
+	/// Like [`via_number`](#via_number), except the result is stored in `this`.
+	fn via_number_assign(attr: &'static str, this: &Object, args: Args) -> crate::Result<Object> {
+		let result = Self::via_number(attr, this, args)?;
+		let result = Self::try_from(result.call_downcast::<Number>()?.clone())?;
+
+		*this.try_downcast_mut::<Self>()? = result;
+		Ok(this.clone())
+	}
+}
+
+/// Quest methods
+impl Rational {
+	/// Creates a new rational from a numerator and an optional denominator.
+	///
+	/// Both arguments may be integers, floats, or rationals themselves; floats are converted to
+	/// their exact binary value.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The numerator.
+	/// 2. (optional, `@num`) The denominator; defaults to `1`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// half = Rational::new(2, 4);
+	///
+	/// assert(half.numerator() == 1);
+	/// assert(half.denominator() == 2);
+	/// assert(Rational::new(0.75) == Rational::new(3, 4));
+	/// ```
+	#[instrument(name="Rational::new", level="trace", skip(args), fields(?args))]
+	pub fn qs_new(args: Args) -> crate::Result<Object> {
+		let numer = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let denom = args.arg(1)
+			.map(|denom| denom.call_downcast::<Self>().map(|denom| denom.clone()))
+			.transpose()?
+			.unwrap_or_else(|| Self::from_integer(1.into()));
+
+		Ok(numer.checked_div(denom)?.into())
+	}
+
+	/// Gets the numerator of `this`, which has the same sign as `this`.
+	#[instrument(name="Rational::numerator", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_numerator(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.numer().clone().into())
+	}
+
+	/// Gets the denominator of `this`, which is always positive.
+	#[instrument(name="Rational::denominator", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_denominator(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.denom().clone().into())
+	}
+
+	/// Converts `this` to a [`Text`] of the form `numer/denom`, or just `numer` if it's whole.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The base to use, which must be `2 <= base <= 36`; defaults to `10`.
+	#[instrument(name="Rational::@text", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_at_text(this: &Object, args: Args) -> crate::Result<Object> {
+		use std::convert::TryInto;
+		let this = this.try_downcast::<Self>()?;
+
+		if let Some(radix) = args.arg(0) {
+			this.to_string_radix(radix.call_downcast::<Number>()?.clone().try_into()?)
+				.map_err(|err| TypeError::Messaged(err.to_string()))
+				.map_err(crate::Error::from)
+				.map(Object::from)
+		} else {
+			Ok(Text::from(this.to_string()).into())
+		}
+	}
+
+	#[instrument(name="Rational::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(format!("{:?}", *this.try_downcast::<Self>()?)).into())
+	}
+
+	/// Converts `this` to a [`Number`].
+	///
+	/// Whole rationals are converted exactly, whereas everything else becomes a float.
+	#[instrument(name="Rational::@num", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_num(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_number().into())
+	}
+
+	/// Simply returns `this`.
+	#[instrument(name="Rational::@rational", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_rational(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Converts `this` to a [`Boolean`], returning `false` only if `this` is zero.
+	#[instrument(name="Rational::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(!this.try_downcast::<Self>()?.0.is_zero()).into())
+	}
+
+	/// Hashes `this`.
+	///
+	/// Rationals that are `==` to a [`Number`] hash the same as that number.
+	#[instrument(name="Rational::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let number = this.to_number();
+
+		if Self::number_cmp(&number, &this) == Some(Ordering::Equal) {
+			Ok(crate::utils::hash(&number).into())
+		} else {
+			Ok(crate::utils::hash(&*this).into())
+		}
+	}
+
+	/// Add `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The addend.
+	#[instrument(name="Rational::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op(BinaryOp::Add, this, args)
+	}
+
+	/// Add `this` and the first argument, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@rational`) The addend.
+	#[instrument(name="Rational::+=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op_assign(BinaryOp::Add, this, args)
+	}
+
+	/// Subtract the first argument from `this`.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The subtrahend.
+	#[instrument(name="Rational::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op(BinaryOp::Sub, this, args)
+	}
+
+	/// Subtract the first argument from `this`, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@rational`) The subtrahend.
+	#[instrument(name="Rational::-=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op_assign(BinaryOp::Sub, this, args)
+	}
+
+	/// Multiply `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The multiplicand.
+	#[instrument(name="Rational::*", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op(BinaryOp::Mul, this, args)
+	}
+
+	/// Multiply `this` and the first argument, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@rational`) The multiplicand.
+	#[instrument(name="Rational::*=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mul_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op_assign(BinaryOp::Mul, this, args)
+	}
+
+	/// Divide `this` by the first argument.
+	///
+	/// Unlike [`Number`], dividing by an exact zero is an error.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The divisor.
+	#[instrument(name="Rational::/", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_div(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op(BinaryOp::Div, this, args)
+	}
+
+	/// Divide `this` by the first argument, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@rational`) The divisor.
+	#[instrument(name="Rational::/=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_div_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op_assign(BinaryOp::Div, this, args)
+	}
+
+	/// Modulo `this` by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The divisor.
+	#[instrument(name="Rational::%", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mod(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op(BinaryOp::Rem, this, args)
+	}
+
+	/// Modulo `this` by the first argument, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@rational`) The divisor.
+	#[instrument(name="Rational::%=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mod_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::binary_op_assign(BinaryOp::Rem, this, args)
+	}
+
+	/// Raises `this` to the power of the first argument.
+	///
+	/// Integer exponents give an exact result; anything else gives a float.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The exponent.
+	#[instrument(name="Rational::**", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_pow(this: &Object, args: Args) -> crate::Result<Object> {
+		let exponent = Operand::from_object(args.try_arg(0)?)?;
+		let this = this.try_downcast::<Self>()?;
+
+		match exponent {
+			Operand::Exact(ref exponent) if exponent.is_whole() => {
+				let exponent = exponent.numer().to_i32()
+					.ok_or_else(|| ValueError::Messaged(format!("exponent {} is too large", exponent)))?;
+
+				// `bits - 2` is so that `1` and `-1` are always allowed.
+				let bits = this.bits().saturating_sub(2).saturating_mul(exponent.unsigned_abs().into());
+				crate::sandbox::check_integer_bits(bits)?;
+
+				Ok(this.checked_pow(exponent)?.into())
+			},
+			exponent => Ok(this.to_number().pow(exponent.into_number()).into())
+		}
+	}
+
+	/// Raises `this` to the power of the first argument, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The exponent, which must be an integer.
+	#[instrument(name="Rational::**=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_pow_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		let result = Self::qs_pow(this, args)?;
+
+		Self::store(this, &result, "**")
+	}
+
+	/// Negates `this`.
+	#[instrument(name="Rational::-@", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_neg(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok((-this.try_downcast::<Self>()?.clone()).into())
+	}
+
+	/// Simply returns `this`.
+	#[instrument(name="Rational::+@", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_pos(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Gets the absolute value of `this`.
+	#[instrument(name="Rational::abs", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_abs(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Self(this.try_downcast::<Self>()?.0.abs()).into())
+	}
+
+	/// Returns the largest integer less than or equal to `this`.
+	#[instrument(name="Rational::floor", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_floor(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.floor().to_integer().into())
+	}
+
+	/// Returns the smallest integer greater than or equal to `this`.
+	#[instrument(name="Rational::ceil", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_ceil(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.ceil().to_integer().into())
+	}
+
+	/// Returns the nearest integer to `this`, rounding half-way cases away from zero.
+	#[instrument(name="Rational::round", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_round(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.round().to_integer().into())
+	}
+
+	/// Checks to see if `this` is zero.
+	#[instrument(name="Rational::zero?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_zero_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.is_zero().into())
+	}
+
+	/// Checks to see if `this` is one.
+	#[instrument(name="Rational::one?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_one_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(num_traits::One::is_one(&this.try_downcast::<Self>()?.0).into())
+	}
+
+	/// Checks to see if `this` is greater than zero.
+	#[instrument(name="Rational::positive?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_positive_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.is_positive().into())
+	}
+
+	/// Checks to see if `this` is less than zero.
+	#[instrument(name="Rational::negative?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_negative_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.is_negative().into())
+	}
+
+	/// Checks to see if `this` is a whole number.
+	#[instrument(name="Rational::whole?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_whole_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_whole().into())
+	}
+
+	/// Checks to see if `this` is exactly equal to the first argument.
+	///
+	/// Rationals can be equal to both integers and floats.
+	#[instrument(name="Rational::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		let is_eql =
+			if let Some(rhs) = rhs.downcast::<Self>() {
+				*this == *rhs
+			} else if let Some(rhs) = rhs.downcast::<Number>() {
+				Self::number_cmp(&rhs, &this) == Some(Ordering::Equal)
+			} else {
+				false
+			};
+
+		Ok(is_eql.into())
+	}
+
+	/// Compares `this` to the first argument exactly.
+	///
+	/// If the first argument is `NaN`, `null` is returned.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The value to compare against.
+	#[instrument(name="Rational::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = Operand::from_object(args.try_arg(0)?)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.partial_cmp(&this).map(|ord| ord.reverse().into()).unwrap_or_default())
+	}
+}
+
+impl_object_type!{
+for Rational [(init_parent super::Number) (parents super::Number) (convert "@rational")]:
+	"new" => function Self::qs_new,
+
+	"numerator" => method Self::qs_numerator,
+	"denominator" => method Self::qs_denominator,
+
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@num" => method Self::qs_at_num,
+	"@rational" => method Self::qs_at_rational,
+	"@bool" => method Self::qs_at_bool,
+	"hash" => method Self::qs_hash,
+
+	"+"  => method Self::qs_add, "+="  => method Self::qs_add_assign,
+	"-"  => method Self::qs_sub, "-="  => method Self::qs_sub_assign,
+	"*"  => method Self::qs_mul, "*="  => method Self::qs_mul_assign,
+	"/"  => method Self::qs_div, "/="  => method Self::qs_div_assign,
+	"%"  => method Self::qs_mod, "%="  => method Self::qs_mod_assign,
+	"**" => method Self::qs_pow, "**=" => method Self::qs_pow_assign,
+
+	"-@"  => method Self::qs_neg,
+	"+@"  => method Self::qs_pos,
+	"abs" => method Self::qs_abs,
+	"floor" => method Self::qs_floor,
+	"ceil" => method Self::qs_ceil,
+	"round" => method Self::qs_round,
+	"whole?" => method Self::qs_whole_q,
+	"zero?" => method Self::qs_zero_q,
+	"one?" => method Self::qs_one_q,
+	"positive?" => method Self::qs_positive_q,
+	"negative?" => method Self::qs_negative_q,
+	"()"  => method Self::qs_mul,
+
+	// The rest of `Number`'s methods only make sense for numbers, so convert `this` first.
+	"&"  => method |this, args| Self::via_number("&", this, args),
+	"&=" => method |this, args| Self::via_number_assign("&", this, args),
+	"|"  => method |this, args| Self::via_number("|", this, args),
+	"|=" => method |this, args| Self::via_number_assign("|", this, args),
+	"^"  => method |this, args| Self::via_number("^", this, args),
+	"^=" => method |this, args| Self::via_number_assign("^", this, args),
+	"<<" => method |this, args| Self::via_number("<<", this, args),
+	"<<=" => method |this, args| Self::via_number_assign("<<", this, args),
+	">>" => method |this, args| Self::via_number(">>", this, args),
+	">>=" => method |this, args| Self::via_number_assign(">>", this, args),
+	"~"  => method |this, args| Self::via_number("~", this, args),
+	"sqrt" => method |this, args| Self::via_number("sqrt", this, args),
+	"upto" => method |this, args| Self::via_number("upto", this, args),
+	"downto" => method |this, args| Self::via_number("downto", this, args),
+	"between?" => method |this, args| Self::via_number("between?", this, args),
+	"even?" => method |this, args| Self::via_number("even?", this, args),
+	"odd?" => method |this, args| Self::via_number("odd?", this, args),
+	"chr" => method |this, args| Self::via_number("chr", this, args),
+
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rational(numer: i64, denom: i64) -> Rational {
+		Rational::new(numer.into(), denom.into()).unwrap()
+	}
+
+	#[test]
+	fn normalises() {
+		assert_eq!(rational(2, 4), rational(1, 2));
+		assert_eq!(rational(3, -6), rational(-1, 2));
+		assert_eq!(*rational(3, -6).denom(), BigIntType::from(2));
+		assert_eq!(Rational::new(1.into(), 0.into()), Err(DivideByZero));
+	}
+
+	#[test]
+	fn try_from_number() {
+		assert_eq!(Rational::try_from(Number::from(3)).unwrap(), rational(3, 1));
+		assert_eq!(Rational::try_from(Number::from(0.375)).unwrap(), rational(3, 8));
+		assert!(Rational::try_from(Number::NAN).is_err());
+		assert!(Rational::try_from(Number::INF).is_err());
+	}
+
+	#[test]
+	fn to_number() {
+		assert_eq!(rational(6, 3).to_number(), Number::from(2));
+		assert_eq!(rational(1, 4).to_number(), Number::from(0.25));
+	}
+
+
+	mod qs {
+		use super::*;
+
+		#[test]
+		fn arithmetic_is_exact() {
+			assert_call_eq!(Rational::qs_add(rational(1, 3), rational(1, 6)) -> Rational, rational(1, 2));
+			assert_call_eq!(Rational::qs_sub(rational(1, 3), 1) -> Rational, rational(-2, 3));
+			assert_call_eq!(Rational::qs_mul(rational(2, 3), 3) -> Rational, rational(2, 1));
+			assert_call_eq!(Rational::qs_div(rational(2, 3), 4) -> Rational, rational(1, 6));
+			assert_call_eq!(Rational::qs_mod(rational(7, 2), 1) -> Rational, rational(1, 2));
+			assert_call_eq!(Rational::qs_pow(rational(2, 3), 3) -> Rational, rational(8, 27));
+			assert_call_eq!(Rational::qs_pow(rational(2, 3), -2) -> Rational, rational(9, 4));
+
+			assert_call_err!(Rational::qs_div(rational(1, 2), 0), crate::Error::ValueError(..));
+			assert_call_err!(Rational::qs_pow(rational(0, 1), -1), crate::Error::ValueError(..));
+		}
+
+		#[test]
+		fn floats_are_inexact() {
+			assert_call_eq!(Rational::qs_add(rational(1, 2), 0.25) -> Number, 0.75);
+			assert_call_eq!(Rational::qs_pow(rational(1, 4), 0.5) -> Number, 0.5);
+		}
+
+		#[test]
+		fn assigning_a_float_is_an_error() {
+			let this = Object::from(rational(1, 2));
+			assert_call_err!(Rational::qs_add_assign(this.clone(), 0.25), crate::Error::ValueError(..));
+			assert_eq!(*this.downcast::<Rational>().unwrap(), rational(1, 2));
+
+			assert_call_eq!(Rational::qs_add_assign(this, 1) -> Rational, rational(3, 2));
+		}
+
+		#[test]
+		fn number_methods_convert_first() {
+			crate::init();
+
+			let call = |this: Rational, attr: &str, args: &[&Object]| {
+				Object::from(this).call_attr_lit(attr, args).unwrap()
+			};
+
+			assert_eq!(*call(rational(1, 4), "sqrt", &[]).downcast::<Number>().unwrap(), 0.5);
+			assert_eq!(*call(rational(6, 1), "&", &[&3.into()]).downcast::<Number>().unwrap(), 2);
+			assert_eq!(*call(rational(4, 1), "<<=", &[&2.into()]).downcast::<Rational>().unwrap(),
+				rational(16, 1));
+			assert_eq!(*call(rational(0, 1), "zero?", &[]).downcast::<Boolean>().unwrap(), true);
+			assert_eq!(*call(rational(-1, 2), "negative?", &[]).downcast::<Boolean>().unwrap(), true);
+			assert_call_eq!(Rational::qs_at_text(rational(5, 6), 2) -> Text, *"101/110");
+		}
+
+		#[test]
+		fn sandboxed_sizes() {
+			let _guard = crate::sandbox::Sandbox::new().max_integer_bits(64).enter();
+
+			assert_call_eq!(Rational::qs_pow(rational(-1, 1), 1_000_000) -> Rational, rational(1, 1));
+			assert_call_err!(Rational::qs_pow(rational(3, 2), 100), crate::Error::SandboxError(..));
+			assert_call_err!(Rational::qs_mul(rational(1 << 40, 3), rational(1 << 40, 5)),
+				crate::Error::SandboxError(..));
+		}
+
+		#[test]
+		fn hash_matches_number() {
+			assert_call_eq!(Rational::qs_hash(rational(4, 2)) -> Number, crate::utils::hash(&Number::from(2)));
+			assert_call_eq!(Rational::qs_hash(rational(1, 4)) -> Number, crate::utils::hash(&Number::from(0.25)));
+		}
+
+		#[test]
+		fn numer_and_denom() {
+			assert_call_eq!(Rational::qs_numerator(rational(-6, 4)) -> Number, -3);
+			assert_call_eq!(Rational::qs_denominator(rational(-6, 4)) -> Number, 2);
+		}
+
+		#[test]
+		fn compare() {
+			assert_call_eq!(Rational::qs_cmp(rational(1, 3), rational(1, 2)) -> Number, -1);
+			assert_call_eq!(Rational::qs_cmp(rational(1, 3), 0) -> Number, 1);
+			assert_call_eq!(Rational::qs_cmp(rational(1, 2), 0.5) -> Number, 0);
+			assert_call_eq!(Rational::qs_cmp(rational(1, 3), 0.3333) -> Number, 1);
+			assert_call_eq!(Rational::qs_cmp(rational(1, 3), Number::INF) -> Number, -1);
+			assert_call_eq!(Rational::qs_eql(rational(2, 1), 2) -> Boolean, true);
+			assert_call_eq!(Rational::qs_eql(rational(1, 10), 0.1) -> Boolean, false);
+		}
+	}
+}
@@ -70,7 +70,7 @@ impl Binding {
 				tracing::trace_span!("stackframe")
 			};
 		let _guard = span.enter();
-
+		let _frame = crate::sandbox::enter_frame()?;
 
 		Binding::with_stack(|stack| {
 			let binding = {
@@ -104,6 +104,8 @@ impl Binding {
 			match func(&binding) {
 				Err(crate::Error::Return { to, obj }) if to.as_ref().eq_obj(binding.as_ref())?
 					=> Ok(obj),
+				// this is done before the frame is popped so it's included in the trace.
+				Err(err) => Err(err.traced()),
 				other => other
 			}
 		})
@@ -122,6 +124,8 @@ impl Binding {
 			}
 		}
 
+		let _frame = crate::sandbox::enter_frame()?;
+
 		Self::with_stack(|stack| {
 			{
 				let mut stack = stack.write();
@@ -133,6 +137,8 @@ impl Binding {
 			match func(&binding) {
 				Err(crate::Error::Return { to, obj }) if to.as_ref().eq_obj(binding.as_ref())?
 					=> Ok(obj),
+				// this is done before the frame is popped so it's included in the trace.
+				Err(err) => Err(err.traced()),
 				other => other
 			}
 		})
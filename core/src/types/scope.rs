@@ -53,8 +53,8 @@ for Scope {
 			// static ref SCOPE: Object = Object::new_with_parent(Scope, vec![Scope::mapping()]);
 		// }
 		Object::new_with_parent(crate::types::Class::new("Scope"), vec![
-			Scope::mapping(),
-			crate::types::Kernel::mapping()
+			Scope::mapping().clone(),
+			crate::types::Kernel::current()
 		])
 		// SCOPE.deep_clone()
 	}
@@ -1,11 +1,16 @@
 use crate::{Object, Args, Literal};
 use crate::error::ValueError;
-use crate::types::{Number, List, Boolean, Regex};
+use crate::types::{Number, List, Boolean, Regex, Bytes};
+use crate::types::bytes::Encoding;
 use crate::Binding;
+use crate::utils::SliceIndex;
 use std::borrow::Cow;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::convert::TryFrom;
 use tracing::instrument;
+use unicode_segmentation::UnicodeSegmentation;
+
+mod format;
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Text(Cow<'static, str>);
@@ -45,9 +50,10 @@ impl Text {
 		}
 	}
 
+	/// The amount of Unicode scalar values (ie `char`s) in the text, which isn't its length in bytes.
 	#[inline]
 	pub fn len(&self) -> usize {
-		self.0.len()
+		self.0.chars().count()
 	}
 
 	#[inline]
@@ -213,8 +219,34 @@ impl Text {
 		self.as_mut().clear()
 	}
 
+	/// Gets a `char` or range of `char`s; negative indices count from the end.
+	#[inline]
+	pub fn get<I: SliceIndex<str>>(&self, index: I) -> Option<&I::Output> {
+		index.get(self.as_ref())
+	}
+
+	/// Reverses the grapheme clusters, so combining characters stay attached to what they combine with.
 	pub fn reverse(&self) -> Self {
-		self.0.as_ref().chars().rev().collect()
+		self.0.graphemes(true).rev().collect::<String>().into()
+	}
+
+	pub fn upcase(&self) -> Self {
+		self.0.to_uppercase().into()
+	}
+
+	pub fn downcase(&self) -> Self {
+		self.0.to_lowercase().into()
+	}
+
+	/// Folds case so that texts which only differ in case become equal (eg `"Straße"` and
+	/// `"STRASSE"` both become `"strasse"`).
+	///
+	/// This approximates Unicode's full case folding by lowercasing each `char`'s uppercase form.
+	pub fn casefold(&self) -> Self {
+		self.0.chars()
+			.flat_map(char::to_uppercase)
+			.flat_map(char::to_lowercase)
+			.collect()
 	}
 
 	pub fn strip(&self) -> Self {
@@ -270,13 +302,72 @@ impl Text {
 		Ok(List::from(&*this).into())
 	}
 
+	/// Converts the text to [`Bytes`].
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) The encoding: `"utf-8"` (the default), `"ascii"`, or `"latin-1"`.
+	///    A `ValueError` is raised if the text contains characters the encoding can't represent.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert("é".encode().len() == 2);
+	/// assert("é".encode("latin-1").len() == 1);
+	/// ```
+	#[instrument(name="Text::encode", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_encode(this: &Object, args: Args) -> crate::Result<Object> {
+		let encoding = Encoding::from_arg(&args, 0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Bytes::from(encoding.encode(this.as_ref())?).into())
+	}
+
+	/// Converts the text to UTF-8 [`Bytes`].
+	#[instrument(name="Text::@bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::qs_encode(this, Args::default())
+	}
+
+	/// Iterates over the characters; this is the same as `chars`.
 	#[instrument(name="Text::@iter", level="trace", skip(this), fields(self=?this))]
-	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
+	pub fn qs_at_iter(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_chars(this, args)
+	}
+
+	/// Iterates over the UTF-8 bytes of the text, as [`Number`]s.
+	#[instrument(name="Text::bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_bytes(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
 		Ok(crate::types::Iter::new(
-			this.as_ref().chars().collect::<Vec<_>>().into_iter().
-				map(From::from).map(Ok)).into())
+			this.as_ref().bytes().collect::<Vec<_>>().into_iter()
+				.map(Number::from).map(Object::from).map(Ok)).into())
+	}
+
+	/// Iterates over the characters (Unicode scalar values) of the text.
+	#[instrument(name="Text::chars", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_chars(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::types::Iter::new(
+			this.as_ref().chars().collect::<Vec<_>>().into_iter()
+				.map(Object::from).map(Ok)).into())
+	}
+
+	/// Iterates over the grapheme clusters of the text, which is what users see as characters.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// flag = "🇫🇷";
+	/// assert(flag.chars().@list().len() == 2);
+	/// assert(flag.graphemes().@list().len() == 1);
+	/// ```
+	#[instrument(name="Text::graphemes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_graphemes(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::types::Iter::new(
+			this.as_ref().graphemes(true).map(str::to_string).collect::<Vec<_>>().into_iter()
+				.map(Object::from).map(Ok)).into())
 	}
 
 	#[instrument(name="Text::@bool", level="trace", skip(this), fields(self=?this))]
@@ -298,8 +389,8 @@ impl Text {
 		let this = this.try_downcast::<Self>()?;
 
 		if let Some(radix) = args.arg(0) {
-			let radix = *radix.call_downcast::<Number>()?;
-			let radix = u32::try_from(radix)
+			let radix = radix.call_downcast::<Number>()?.clone();
+			let radix = u32::try_from(radix.clone())
 				.map_err(|err| ValueError::Messaged(format!("bad radix '{}': {}", radix, err)))?;
 
 			Number::from_str_radix(this.as_ref(), radix)
@@ -377,6 +468,21 @@ impl Text {
 		Ok(this.clone())
 	}
 
+	/// Hashes the text by its contents, so equal texts have equal hashes.
+	#[instrument(name="Text::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::utils::hash(&*this).into())
+	}
+
+	/// Gets the amount of characters (Unicode scalar values) in the text.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert("héllo".len() == 5);
+	/// assert("héllo".bytes().@list().len() == 6);
+	/// ```
 	#[instrument(name="Text::len", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -384,57 +490,39 @@ impl Text {
 		Ok(this.len().into())
 	}
 
-	fn correct_index(&self, idx: isize) -> Option<usize> {
-		if !idx.is_negative() {
-			if (idx as usize) < self.len() {
-				Some(idx as usize)
-			} else {
-				None
-			}
-		} else {
-			let idx = (-idx) as usize;
-			if idx <= self.len() {
-				Some(self.len() - idx)
-			} else {
-				None
-			}
-		}
-	}
-
+	/// Gets a character, or a range of characters.
+	///
+	/// Indices count Unicode scalar values, not bytes, and out of bounds indices return `null`.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The index of the character, or the start of the range.
+	/// 2. (optional, `@num`) The end of the range, inclusive.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert("héllo".get(1) == "é");
+	/// assert("héllo".get(1, -2) == "éll");
+	/// assert("héllo".get(9) == null);
+	/// ```
 	#[instrument(name="Text::get", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		let start: isize = isize::try_from(*args.try_arg(0)?.try_downcast::<Number>()?)?;
+		let start: isize = isize::try_from(args.try_arg(0)?.try_downcast::<Number>()?.clone())?;
 
 		let end = args.arg(1)
-			.map(|n| n.call_downcast::<Number>().map(|n| *n))
+			.map(|n| n.call_downcast::<Number>().map(|n| n.clone()))
 			.transpose()?
 			.map(isize::try_from)
 			.transpose()?;
 
-		let start =
-			if let Some(start) = this.correct_index(start) {
-				start
-			} else {
-				return Ok(Object::default())
+		let got =
+			match end {
+				Some(end) => this.get(start..=end),
+				None => this.get(start)
 			};
 
-		match end {
-			None =>
-				Ok(this.0.chars()
-					.nth(start)
-					.map(|x| x.to_string().into())
-					.unwrap_or_default()),
-			Some(end) => {
-				let end = this.correct_index(end).map(|x| x + 1).unwrap_or_else(|| this.len());
-				if end < start {
-					Ok(Object::default())
-				} else {
-					Ok(this.0[start..end].to_owned().into())
-				}
-			}
-		}
+		Ok(got.map(Object::from).unwrap_or_default())
 	}
 
 	#[instrument(name="Text::set", level="trace", skip(_this, _args), fields(self=?_this, args=?_args))]
@@ -520,6 +608,46 @@ impl Text {
 		Ok(this.reverse().into())
 	}
 
+	/// Replaces `{}` placeholders in the text with the arguments.
+	///
+	/// Placeholders can refer to arguments by position or by name, and can specify the width,
+	/// alignment, fill, precision and radix to use; see the [`format`] module for the details.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert("{} + {} = {}".format(1, 2, 3) == "1 + 2 = 3");
+	/// assert("[{:>5}|{:<5}]".format("ab", "cd") == "[   ab|cd   ]");
+	/// assert("{:.2} {:x} {:08b}".format(3.14159, 255, 5) == "3.14 ff 00000101");
+	/// assert("{name:?}".format(Map::new(["name", "sam"])) == "\"sam\"");
+	/// ```
+	#[instrument(name="Text::format", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_format(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format::format(this.as_ref(), args.as_ref())?.into())
+	}
+
+	#[instrument(name="Text::upcase", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_upcase(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.upcase().into())
+	}
+
+	#[instrument(name="Text::downcase", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_downcase(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.downcase().into())
+	}
+
+	/// Folds the case of the text, for comparing texts without regard to case.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert("Straße".casefold() == "STRASSE".casefold());
+	/// ```
+	#[instrument(name="Text::casefold", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_casefold(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.casefold().into())
+	}
+
 	#[instrument(name="Text::strip", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_strip(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -618,6 +746,7 @@ for Text
 	"inspect"  => method Self::qs_inspect,
 	"@num"    => method Self::qs_at_num,
 	"@list"   => method Self::qs_at_list,
+	"@bytes"  => method Self::qs_at_bytes,
 	"@bool"   => method Self::qs_at_bool,
 	"@iter"   => method Self::qs_at_iter,
 	"()"      => method Self::qs_call,
@@ -627,6 +756,7 @@ for Text
 	"->"      => method Self::qs_arrow,
 	"<=>"     => method Self::qs_cmp,
 	"=="      => method Self::qs_eql,
+	"hash"    => method Self::qs_hash,
 	"+"       => method Self::qs_add,
 	"+="      => method Self::qs_add_assign,
 
@@ -648,9 +778,17 @@ for Text
 	"split"   => method Self::qs_split,
 	"reverse" => method Self::qs_reverse, 
 	"strip"   => method Self::qs_strip,
+	"upcase"  => method Self::qs_upcase,
+	"downcase" => method Self::qs_downcase,
+	"casefold" => method Self::qs_casefold,
+	"bytes"   => method Self::qs_bytes,
+	"chars"   => method Self::qs_chars,
+	"graphemes" => method Self::qs_graphemes,
 	"replace" => method Self::qs_replace,
 	"sub" => method Self::qs_sub,
 	"gsub" => method Self::qs_gsub,
+	"encode" => method Self::qs_encode,
+	"format" => method Self::qs_format,
 
 	"count" => method Self::qs_count,
 	"empty?" => method Self::qs_empty_q,
@@ -662,3 +800,79 @@ for Text
 		Ok(this.as_ref().contains(rhs.as_ref()).into())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn len_counts_chars() {
+		assert_eq!(Text::from("héllo").len(), 5);
+		assert_eq!(Text::from("日本語").len(), 3);
+		assert_eq!(Text::from("").len(), 0);
+	}
+
+	#[test]
+	fn get_by_chars() {
+		let text = Text::from("héllo, 世界");
+
+		assert_eq!(text.get(1), Some("é"));
+		assert_eq!(text.get(-1), Some("界"));
+		assert_eq!(text.get(9), None);
+		assert_eq!(text.get(1..=4), Some("éllo"));
+		assert_eq!(text.get(-2..=100), Some("世界"));
+		assert_eq!(text.get(-100..=1), None);
+	}
+
+	#[test]
+	fn shift_pop_and_reverse() {
+		let mut text = Text::from("éab界");
+
+		assert_eq!(text.shift(), Some('é'));
+		assert_eq!(text.pop(), Some('界'));
+		assert_eq!(text, *"ab");
+
+		// the combining acute accent stays on the `e`.
+		assert_eq!(Text::from("cafe\u{301}!").reverse(), *"!e\u{301}fac");
+	}
+
+	#[test]
+	fn cases() {
+		assert_eq!(Text::from("straße ǆ").upcase(), *"STRASSE Ǆ");
+		assert_eq!(Text::from("ΣΊΣΥΦΟΣ").downcase(), *"σίσυφος");
+		assert_eq!(Text::from("Straße").casefold(), Text::from("STRASSE").casefold());
+		assert_eq!(Text::from("ΣΊΣΥΦΟΣ").casefold(), Text::from("σίσυφος").casefold());
+	}
+
+	mod qs {
+		use super::*;
+
+		fn collect(iter: Object) -> Vec<Object> {
+			iter.call_downcast::<List>().unwrap().iter().cloned().collect()
+		}
+
+		#[test]
+		fn get() {
+			assert_call_eq!(Text::qs_get("héllo", 1) -> Text, *"é");
+			assert_call_eq!(Text::qs_get("héllo", 1, -2) -> Text, *"éll");
+			assert_call_eq!(Text::qs_len("héllo") -> Number, 5);
+		}
+
+		#[test]
+		fn iterators() {
+			crate::init();
+
+			let text = Object::from("e\u{301}🇫🇷");
+			let bytes = collect(Text::qs_bytes(&text, args!()).unwrap());
+			let chars = collect(Text::qs_chars(&text, args!()).unwrap());
+			let graphemes = collect(Text::qs_graphemes(&text, args!()).unwrap());
+
+			assert_eq!(bytes.len(), 11);
+			assert_eq!(*bytes[1].downcast::<Number>().unwrap(), 0xcc);
+			assert_eq!(chars.len(), 4);
+			assert_eq!(*chars[1].downcast::<Text>().unwrap(), *"\u{301}");
+			assert_eq!(graphemes.len(), 2);
+			assert_eq!(*graphemes[1].downcast::<Text>().unwrap(), *"🇫🇷");
+		}
+	}
+}
@@ -0,0 +1,368 @@
+//! Formatting [`Text`](super::Text) with placeholders, as in `"{} is {age:>3}".format(...)`.
+//!
+//! Each placeholder is `{argument:spec}`, where both parts are optional:
+//! - The argument is either empty (the next positional argument), a position (eg `{0}`), or a
+//!   name (eg `{age}`). Names are looked up in the last argument: a `Map` is indexed by the name,
+//!   and anything else has the name looked up as an attribute (so `:0` gives local variables).
+//! - The spec is `[[fill]align][+][0][width][.precision][type]`, similarly to Rust's:
+//!   - `align` is `<` (left), `^` (center) or `>` (right), padding with `fill` (a space by
+//!     default) up to `width` characters. Numbers are right-aligned by default, everything else
+//!     is left-aligned.
+//!   - `+` always includes a number's sign, and `0` pads numbers with zeros after their sign.
+//!   - `precision` is the amount of digits after a number's decimal point, or the maximum amount
+//!     of characters for everything else.
+//!   - `type` is either omitted (`@text` is used), `?` (`inspect` is used), or one of `b`, `o`,
+//!     `x` and `X` for integers in binary, octal, or lower/upper case hexadecimal.
+//!
+//! Literal braces are written as `{{` and `}}`.
+
+use crate::{Object, Literal};
+use crate::error::{KeyError, ValueError};
+use crate::types::{Text, Number, Map};
+use crate::types::number::FloatType;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Where a placeholder gets its value from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Argument<'a> {
+	Next,
+	Index(usize),
+	Name(&'a str)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+	Left,
+	Center,
+	Right
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+	Text,
+	Inspect,
+	Radix { radix: u32, upper: bool }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Spec {
+	fill: char,
+	align: Option<Align>,
+	sign: bool,
+	zero: bool,
+	width: Option<usize>,
+	precision: Option<usize>,
+	kind: Kind
+}
+
+impl Default for Spec {
+	fn default() -> Self {
+		Self { fill: ' ', align: None, sign: false, zero: false, width: None, precision: None, kind: Kind::Text }
+	}
+}
+
+fn bad_spec(spec: &str, why: &str) -> crate::Error {
+	ValueError::Messaged(format!("invalid format spec {:?}: {}", spec, why)).into()
+}
+
+fn align_of(chr: char) -> Option<Align> {
+	match chr {
+		'<' => Some(Align::Left),
+		'^' => Some(Align::Center),
+		'>' => Some(Align::Right),
+		_ => None
+	}
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> Option<usize> {
+	let mut digits = String::new();
+
+	while let Some(&digit) = chars.peek().filter(|chr| chr.is_ascii_digit()) {
+		digits.push(digit);
+		chars.next();
+	}
+
+	digits.parse().ok()
+}
+
+impl Spec {
+	fn parse(spec: &str) -> crate::Result<Self> {
+		let mut parsed = Self::default();
+		let mut chars = spec.chars().peekable();
+
+		let mut lookahead = spec.chars();
+		match (lookahead.next(), lookahead.next().and_then(align_of)) {
+			(Some(fill), Some(align)) => {
+				parsed.fill = fill;
+				parsed.align = Some(align);
+				chars.nth(1);
+			},
+			(Some(chr), _) if align_of(chr).is_some() => {
+				parsed.align = align_of(chr);
+				chars.next();
+			},
+			_ => {}
+		}
+
+		parsed.sign = chars.next_if_eq(&'+').is_some();
+		parsed.zero = chars.next_if_eq(&'0').is_some();
+		parsed.width = parse_number(&mut chars);
+
+		if chars.next_if_eq(&'.').is_some() {
+			parsed.precision = Some(parse_number(&mut chars).ok_or_else(|| bad_spec(spec, "missing precision"))?);
+		}
+
+		parsed.kind =
+			match chars.next() {
+				None => Kind::Text,
+				Some('?') => Kind::Inspect,
+				Some('b') => Kind::Radix { radix: 2, upper: false },
+				Some('o') => Kind::Radix { radix: 8, upper: false },
+				Some('x') => Kind::Radix { radix: 16, upper: false },
+				Some('X') => Kind::Radix { radix: 16, upper: true },
+				Some(other) => return Err(bad_spec(spec, &format!("unknown type {:?}", other)))
+			};
+
+		if chars.next().is_some() {
+			return Err(bad_spec(spec, "unexpected characters after the type"));
+		}
+
+		Ok(parsed)
+	}
+
+	/// Formats a number's digits, without its sign.
+	fn digits(&self, num: &Number) -> crate::Result<String> {
+		let num = num.abs();
+
+		match (self.kind, self.precision) {
+			(Kind::Radix { radix, upper }, _) => {
+				let digits = num.to_string_radix(radix)
+					.map_err(|err| ValueError::Messaged(err.to_string()))?;
+
+				Ok(if upper { digits.to_uppercase() } else { digits })
+			},
+			(_, Some(precision)) => Ok(format!("{:.*}", precision, FloatType::from(num))),
+			(_, None) => Ok(num.to_string())
+		}
+	}
+
+	fn render(&self, value: &Object) -> crate::Result<String> {
+		if self.kind != Kind::Inspect {
+			if let Some(num) = value.downcast::<Number>() {
+				let num = num.clone();
+				return self.render_number(&num);
+			}
+		}
+
+		if let Kind::Radix { .. } = self.kind {
+			let num = value.call_downcast::<Number>()?.clone();
+			return self.render_number(&num);
+		}
+
+		let text =
+			if self.kind == Kind::Inspect {
+				value.call_attr_lit(&Literal::INSPECT, &[])?.call_downcast::<Text>()?.to_string()
+			} else {
+				value.call_downcast::<Text>()?.to_string()
+			};
+
+		let text =
+			match self.precision {
+				Some(precision) => text.chars().take(precision).collect(),
+				None => text
+			};
+
+		Ok(self.pad(text, "", false))
+	}
+
+	fn render_number(&self, num: &Number) -> crate::Result<String> {
+		let sign =
+			if *num < Number::ZERO {
+				"-"
+			} else if self.sign {
+				"+"
+			} else {
+				""
+			};
+
+		Ok(self.pad(self.digits(num)?, sign, true))
+	}
+
+	/// Pads `body`, with `sign` in front of it, to the width.
+	fn pad(&self, body: String, sign: &str, is_number: bool) -> String {
+		let len = sign.chars().count() + body.chars().count();
+		let padding = self.width.map_or(0, |width| width.saturating_sub(len));
+
+		if is_number && self.zero {
+			return format!("{}{}{}", sign, "0".repeat(padding), body);
+		}
+
+		let default_align = if is_number { Align::Right } else { Align::Left };
+		let (left, right) =
+			match self.align.unwrap_or(default_align) {
+				Align::Left => (0, padding),
+				Align::Center => (padding / 2, padding - padding / 2),
+				Align::Right => (padding, 0)
+			};
+
+		let fill = |amnt| std::iter::repeat_n(self.fill, amnt).collect::<String>();
+
+		format!("{}{}{}{}", fill(left), sign, body, fill(right))
+	}
+}
+
+/// Gets the value for `arg`.
+fn lookup(arg: Argument, next: &mut usize, args: &[&Object]) -> crate::Result<Object> {
+	let positional = |idx: usize|
+		args.get(idx)
+			.map(|arg| (*arg).clone())
+			.ok_or_else(|| KeyError::OutOfBounds { idx: idx as isize, len: args.len() }.into());
+
+	match arg {
+		Argument::Next => {
+			*next += 1;
+			positional(*next - 1)
+		},
+		Argument::Index(idx) => positional(idx),
+		Argument::Name(name) => {
+			let source = args.last().ok_or_else(||
+				ValueError::Messaged(format!("no argument to look up {:?} in", name)))?;
+			let key = Object::from(name);
+
+			if let Some(map) = source.downcast::<Map>() {
+				map.get(&key)?
+					.cloned()
+					.ok_or_else(|| KeyError::DoesntExist { attr: key.clone(), obj: (*source).clone() }.into())
+			} else {
+				source.get_attr(&key)
+			}
+		}
+	}
+}
+
+fn parse_argument(arg: &str) -> crate::Result<Argument<'_>> {
+	if arg.is_empty() {
+		Ok(Argument::Next)
+	} else if arg.chars().all(|chr| chr.is_ascii_digit()) {
+		arg.parse()
+			.map(Argument::Index)
+			.map_err(|err| ValueError::Messaged(format!("bad position {:?}: {}", arg, err)).into())
+	} else {
+		Ok(Argument::Name(arg))
+	}
+}
+
+/// Replaces the placeholders in `fmt` with `args`.
+pub fn format(fmt: &str, args: &[&Object]) -> crate::Result<String> {
+	let mut formatted = String::with_capacity(fmt.len());
+	let mut next = 0;
+	let mut rest = fmt;
+
+	while let Some(idx) = rest.find(['{', '}']) {
+		formatted.push_str(&rest[..idx]);
+		let brace = &rest[idx..];
+
+		if brace.starts_with("{{") || brace.starts_with("}}") {
+			formatted.push_str(&brace[..1]);
+			rest = &brace[2..];
+			continue;
+		}
+
+		if brace.starts_with('}') {
+			return Err(ValueError::Messaged("unmatched '}' in format string".into()).into());
+		}
+
+		let end = brace.find('}')
+			.ok_or_else(|| ValueError::Messaged("unmatched '{' in format string".into()))?;
+		let placeholder = &brace[1..end];
+
+		let (arg, spec) =
+			match placeholder.find(':') {
+				Some(colon) => (&placeholder[..colon], Spec::parse(&placeholder[colon + 1..])?),
+				None => (placeholder, Spec::default())
+			};
+
+		let value = lookup(parse_argument(arg)?, &mut next, args)?;
+		formatted.push_str(&spec.render(&value)?);
+		rest = &brace[end + 1..];
+	}
+
+	formatted.push_str(rest);
+	Ok(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::List;
+
+	fn fmt(fmt: &str, args: &[Object]) -> String {
+		crate::init();
+
+		format(fmt, &args.iter().collect::<Vec<_>>()).unwrap()
+	}
+
+	fn fmt_err(fmt: &str, args: &[Object]) -> crate::Error {
+		crate::init();
+
+		format(fmt, &args.iter().collect::<Vec<_>>()).unwrap_err()
+	}
+
+	#[test]
+	fn arguments() {
+		assert_eq!(fmt("{} and {}", &["a".into(), 2.into()]), "a and 2");
+		assert_eq!(fmt("{1}{0}{1}", &["a".into(), "b".into()]), "bab");
+		assert_eq!(fmt("{{{}}}", &["a".into()]), "{a}");
+		assert_eq!(fmt("no placeholders", &[]), "no placeholders");
+
+		assert!(matches!(fmt_err("{} {}", &["a".into()]), crate::Error::KeyError(..)));
+		assert!(matches!(fmt_err("{", &[]), crate::Error::ValueError(..)));
+		assert!(matches!(fmt_err("}", &[]), crate::Error::ValueError(..)));
+	}
+
+	#[test]
+	fn named() {
+		let mut map = Map::new();
+		map.insert("name".into(), "sam".into()).unwrap();
+		assert_eq!(fmt("hi {name}", &[map.clone().into()]), "hi sam");
+		assert!(matches!(fmt_err("{age}", &[map.into()]), crate::Error::KeyError(..)));
+
+		let object = Object::new_with_parent((), ());
+		object.set_attr_lit("age", 3.into()).unwrap();
+		assert_eq!(fmt("{age:>3}", &[object]), "  3");
+	}
+
+	#[test]
+	fn width_alignment_and_fill() {
+		assert_eq!(fmt("[{:5}]", &["ab".into()]), "[ab   ]");
+		assert_eq!(fmt("[{:5}]", &[12.into()]), "[   12]");
+		assert_eq!(fmt("[{:^6}]", &["ab".into()]), "[  ab  ]");
+		assert_eq!(fmt("[{:*>5}]", &["ab".into()]), "[***ab]");
+		assert_eq!(fmt("[{:é<4}]", &["日本".into()]), "[日本éé]");
+		assert_eq!(fmt("[{:1}]", &["long".into()]), "[long]");
+	}
+
+	#[test]
+	fn numbers() {
+		assert_eq!(fmt("{:.2}", &[1.23456.into()]), "1.23");
+		assert_eq!(fmt("{:.1}", &[2.into()]), "2.0");
+		assert_eq!(fmt("{:+}", &[5.into()]), "+5");
+		assert_eq!(fmt("{:05}", &[(-42).into()]), "-0042");
+		assert_eq!(fmt("{:+08.3}", &[1.5.into()]), "+001.500");
+		assert_eq!(fmt("{:x} {:X} {:o} {:b}", &[255.into(), 255.into(), 8.into(), 5.into()]), "ff FF 10 101");
+		assert_eq!(fmt("{:x}", &[(-255).into()]), "-ff");
+		assert_eq!(fmt("{:#>6b}", &[5.into()]), "###101");
+
+		assert!(matches!(fmt_err("{:x}", &[1.5.into()]), crate::Error::ValueError(..)));
+		assert!(matches!(fmt_err("{:q}", &[1.into()]), crate::Error::ValueError(..)));
+	}
+
+	#[test]
+	fn text_and_inspect() {
+		assert_eq!(fmt("{:.3}", &["abcdef".into()]), "abc");
+		assert_eq!(fmt("{:?}", &["a".into()]), "\"a\"");
+		assert_eq!(fmt("{}", &[List::from(vec![Object::from(1), Object::from("b")]).into()]), "[1, \"b\"]");
+		assert_eq!(fmt("{:>5?}", &["a".into()]), "  \"a\"");
+	}
+}
@@ -0,0 +1,238 @@
+//! The [`Thread`] type in Quest.
+
+use crate::{Object, Args, Binding, Literal, Error};
+use crate::types::{Exception, Kernel};
+use parking_lot::Mutex;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use tracing::instrument;
+
+/// What a thread finished with: either its return value, or the exception it raised.
+type Outcome = Result<Object, Object>;
+
+#[derive(Default)]
+struct Inner {
+	handle: Mutex<Option<JoinHandle<Outcome>>>,
+	outcome: Mutex<Option<Outcome>>,
+	finished: AtomicBool
+}
+
+/// A handle to a block that's running in a separate OS thread.
+///
+/// The thread starts with a copy of the spawning thread's stack, so blocks called within it see
+/// the same variables as they would have in the spawning thread.
+#[derive(Clone, Default)]
+pub struct Thread(Arc<Inner>);
+
+impl Debug for Thread {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_struct("Thread")
+			.field("id", &Arc::as_ptr(&self.0))
+			.field("finished", &self.is_finished())
+			.finish()
+	}
+}
+
+/// Converts an error that a thread finished with into an exception that can be raised elsewhere.
+fn outcome_of(result: crate::Result<Object>) -> Outcome {
+	match result {
+		Ok(obj) => Ok(obj),
+		Err(err) => match err.into_exception() {
+			Ok(exception) => Err(exception),
+			// there's no stackframe to return to, so a `return` just ends the thread.
+			Err(Error::Return { obj, .. }) => Ok(obj),
+			Err(other) => Err(Exception::from_error(&other))
+		}
+	}
+}
+
+/// Marks the thread as finished when dropped, so that it's marked even if the thread panics.
+struct FinishGuard(Arc<Inner>);
+
+impl Drop for FinishGuard {
+	fn drop(&mut self) {
+		self.0.finished.store(true, Ordering::SeqCst);
+	}
+}
+
+impl Thread {
+	/// Calls `block` with `args` in a new thread.
+	///
+	/// The new thread's stack is seeded with the current thread's stack, and it uses the current
+	/// thread's [`Kernel`].
+	pub fn spawn(block: Object, args: Vec<Object>) -> Self {
+		let thread = Self::default();
+		let stack = Binding::stack();
+		let kernel = Kernel::current();
+		let inner = thread.0.clone();
+
+		let handle = std::thread::spawn(move || {
+			let _guard = FinishGuard(inner);
+			let _kernel = Kernel::enter(kernel);
+			Binding::set_stack(stack);
+
+			let args = args.iter().collect::<Vec<_>>();
+			outcome_of(block.call_attr_lit(&Literal::CALL, args))
+		});
+
+		*thread.0.handle.lock() = Some(handle);
+		thread
+	}
+
+	/// Checks to see if the thread has finished running.
+	pub fn is_finished(&self) -> bool {
+		self.0.finished.load(Ordering::SeqCst)
+	}
+
+	/// Waits for the thread to finish, returning its result.
+	///
+	/// This can be called multiple times, and from multiple threads.
+	pub fn join(&self) -> Outcome {
+		// the handle is locked whilst joining so concurrent `join`s wait for the first one.
+		let mut handle = self.0.handle.lock();
+
+		if let Some(handle) = handle.take() {
+			let outcome = handle.join().unwrap_or_else(|_| {
+				Err(Exception::from_error(&Error::Messaged("thread panicked".to_string())))
+			});
+
+			*self.0.outcome.lock() = Some(outcome);
+		}
+
+		self.0.outcome.lock().clone().expect("thread was joined without an outcome")
+	}
+}
+
+/// Quest methods
+impl Thread {
+	/// Calls the block in a new thread, passing it the remaining arguments.
+	///
+	/// # Arguments
+	/// 1. (required, `()`) The block to run.
+	/// 2. (optional, rest) Arguments to pass to the block.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// x = 10;
+	/// thread = spawn({ x + _0 }, 5);
+	///
+	/// assert(thread.join() == 15);
+	/// assert(Thread::spawn({ x }).join() == 10);
+	/// ```
+	#[instrument(name="Thread::spawn", level="trace", skip(block, args), fields(?block, ?args))]
+	pub fn qs_spawn(block: &Object, args: Args) -> crate::Result<Object> {
+		crate::sandbox::require(crate::sandbox::Capability::PROCESS, "Thread::spawn")?;
+
+		let args = args.as_ref().iter().map(|arg| (*arg).clone()).collect();
+
+		Ok(Self::spawn(block.clone(), args).into())
+	}
+
+	/// Waits for the thread to finish, returning the value its block returned.
+	///
+	/// If the thread raised an exception, it's rethrown in the joining thread.
+	#[instrument(name="Thread::join", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_join(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		this.join().map_err(Error::Thrown)
+	}
+
+	/// Checks to see if the thread is still running.
+	#[instrument(name="Thread::alive?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_alive_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok((!this.try_downcast::<Self>()?.is_finished()).into())
+	}
+
+	/// Gets the thread's result without waiting: `null` if it's still running, the exception if it
+	/// raised one, and its return value otherwise.
+	#[instrument(name="Thread::result", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_result(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		if this.is_finished() {
+			Ok(this.join().unwrap_or_else(|exception| exception))
+		} else {
+			Ok(Object::default())
+		}
+	}
+
+	#[instrument(name="Thread::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("{:?}", *this.try_downcast::<Self>()?).into())
+	}
+}
+
+impl_object_type!{
+for Thread [(parents super::Basic)]:
+	"spawn" => function |args| {
+		let block = args.try_arg(0)?;
+
+		Self::qs_spawn(block, args.args(1..).unwrap_or_default())
+	},
+	"join" => method Self::qs_join,
+	"alive?" => method Self::qs_alive_q,
+	"result" => method Self::qs_result,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{RustClosure, Number};
+
+	#[test]
+	fn join_returns_result() {
+		crate::init();
+
+		let block = Object::from(RustClosure::new(|args| {
+			let n = args.try_arg(0)?.call_downcast::<Number>()?.clone();
+			Ok((n + Number::ONE).into())
+		}));
+
+		let thread = Thread::spawn(block, vec![Object::from(41)]);
+		assert_eq!(*thread.join().unwrap().downcast::<Number>().unwrap(), 42);
+		assert!(thread.is_finished());
+
+		// joining again gives the same result.
+		assert_eq!(*thread.join().unwrap().downcast::<Number>().unwrap(), 42);
+	}
+
+	#[test]
+	fn errors_become_exceptions() {
+		crate::init();
+
+		let block = Object::from(RustClosure::new(|_| Err(Error::Messaged("oops".into()))));
+		let thread = Thread::spawn(block, vec![]);
+
+		let exception = thread.join().unwrap_err();
+		assert_eq!(exception.downcast::<Exception>().unwrap().message(), "oops");
+	}
+
+	#[test]
+	fn panics_finish_the_thread() {
+		crate::init();
+
+		let block = Object::from(RustClosure::new(|_| panic!("oops")));
+		let thread = Object::from(Thread::spawn(block, vec![]));
+		assert!(thread.downcast::<Thread>().unwrap().join().is_err());
+
+		assert_call_eq!(Thread::qs_alive_q(thread.clone()) -> crate::types::Boolean, false);
+		call_unwrap!(Thread::qs_result(thread) -> Exception; |exception| {
+			assert_eq!(exception.message(), "thread panicked");
+		});
+	}
+
+	#[test]
+	fn inherits_spawning_stack() {
+		crate::init();
+
+		let block = Object::from(RustClosure::new(|_|
+			Ok(Binding::stack().last().cloned().expect("no stack?").into())));
+
+		let outermost = Thread::spawn(block, vec![]).join().unwrap();
+		assert!(outermost.is_identical(Binding::stack().last().unwrap().as_ref()));
+	}
+}
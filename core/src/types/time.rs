@@ -0,0 +1,598 @@
+//! Dates, times, durations and clocks.
+
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Number, Text};
+use chrono::{DateTime, Datelike, FixedOffset, SecondsFormat, TimeZone, Timelike, Utc};
+use chrono::format::{self, Item, Parsed, StrftimeItems};
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use tracing::instrument;
+
+mod duration;
+mod instant;
+pub use duration::Duration;
+pub use instant::Instant;
+
+/// A point in time, along with the fixed UTC offset it's displayed in.
+///
+/// Two times are equal if they're the same instant, even if their offsets differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time(DateTime<FixedOffset>);
+
+fn utc() -> FixedOffset {
+	FixedOffset::east(0)
+}
+
+fn out_of_range() -> crate::Error {
+	ValueError::Messaged("time out of range".to_string()).into()
+}
+
+/// Parses a strftime-style format, failing if it contains unknown specifiers.
+fn strftime(fmt: &str) -> crate::Result<Vec<Item<'_>>> {
+	let items = StrftimeItems::new(fmt).collect::<Vec<_>>();
+
+	if items.iter().any(|item| matches!(item, Item::Error)) {
+		Err(ValueError::Messaged(format!("invalid time format: {:?}", fmt)).into())
+	} else {
+		Ok(items)
+	}
+}
+
+/// Parses offsets like `Z`, `UTC`, `+05:30`, `-0800` and `+09`.
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+	if text == "Z" || text.eq_ignore_ascii_case("utc") {
+		return Some(utc());
+	}
+
+	let (sign, rest) =
+		match text.as_bytes().first()? {
+			b'+' => (1, &text[1..]),
+			b'-' => (-1, &text[1..]),
+			_ => return None
+		};
+
+	let digits = rest.replacen(':', "", 1);
+
+	if !digits.bytes().all(|byte| byte.is_ascii_digit()) || !matches!(digits.len(), 2 | 4) {
+		return None;
+	}
+
+	let hours = digits[..2].parse::<i32>().ok()?;
+	let minutes = digits.get(2..).filter(|m| !m.is_empty()).map_or(Some(0), |m| m.parse::<i32>().ok())?;
+
+	if minutes >= 60 {
+		return None;
+	}
+
+	FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Converts `arg` to an offset, either as a number of seconds east of UTC or as text.
+fn offset_arg(arg: &Object) -> crate::Result<FixedOffset> {
+	if let Some(secs) = arg.downcast::<Number>() {
+		let secs: f64 = secs.clone().into();
+
+		return FixedOffset::east_opt(secs as i32)
+			.filter(|_| secs.abs() < 86400.0)
+			.ok_or_else(|| ValueError::Messaged(format!("invalid UTC offset: {}", secs)).into());
+	}
+
+	let text = arg.call_downcast::<Text>()?;
+
+	parse_offset(text.as_ref())
+		.ok_or_else(|| ValueError::Messaged(format!("invalid UTC offset: {:?}", text.as_ref())).into())
+}
+
+fn optional_offset(args: &Args, idx: usize) -> crate::Result<FixedOffset> {
+	args.arg(idx).map_or_else(|| Ok(utc()), offset_arg)
+}
+
+impl Time {
+	/// The current time, in UTC.
+	pub fn now() -> Self {
+		Self(Utc::now().with_timezone(&utc()))
+	}
+
+	/// Creates a time from seconds since the unix epoch, returning `None` if it's out of range.
+	pub fn from_timestamp(secs: f64, offset: FixedOffset) -> Option<Self> {
+		if !secs.is_finite() {
+			return None;
+		}
+
+		let whole = secs.floor();
+		let nanos = ((secs - whole) * 1e9).round().min(999_999_999.0) as u32;
+
+		if whole.abs() >= i64::MAX as f64 {
+			return None;
+		}
+
+		offset.timestamp_opt(whole as i64, nanos).single().map(Self)
+	}
+
+	/// Parses `text` according to the strftime-style `fmt`.
+	///
+	/// If `fmt` doesn't include an offset, `offset` is used. If it doesn't include a time of day,
+	/// midnight is used.
+	pub fn parse(text: &str, fmt: &str, offset: FixedOffset) -> crate::Result<Self> {
+		let bad_time = |err: format::ParseError| -> crate::Error {
+			ValueError::Messaged(format!("can't parse {:?} as {:?}: {}", text, fmt, err)).into()
+		};
+
+		let mut parsed = Parsed::new();
+		format::parse(&mut parsed, text, strftime(fmt)?.iter()).map_err(bad_time)?;
+
+		if parsed.offset.is_none() {
+			parsed.set_offset(offset.local_minus_utc() as i64).map_err(bad_time)?;
+		}
+
+		if parsed.timestamp.is_none() && parsed.hour_mod_12.is_none() && parsed.hour_div_12.is_none() {
+			parsed.set_hour(0).and_then(|_| parsed.set_minute(0)).map_err(bad_time)?;
+		}
+
+		parsed.to_datetime().map(Self).map_err(bad_time)
+	}
+
+	/// Parses an RFC 3339 time, such as `2021-03-04T05:06:07.5+08:00`.
+	pub fn parse_rfc3339(text: &str) -> crate::Result<Self> {
+		DateTime::parse_from_rfc3339(text)
+			.map(Self)
+			.map_err(|err| ValueError::Messaged(format!("can't parse {:?} as a time: {}", text, err)).into())
+	}
+
+	/// Formats the time according to the strftime-style `fmt`.
+	pub fn format(&self, fmt: &str) -> crate::Result<String> {
+		Ok(self.0.format_with_items(strftime(fmt)?.iter()).to_string())
+	}
+
+	/// Seconds since the unix epoch, including fractional seconds.
+	pub fn timestamp(&self) -> Number {
+		match self.0.timestamp_subsec_nanos() {
+			0 => Number::from(self.0.timestamp()),
+			nanos => Number::from(self.0.timestamp() as f64 + nanos as f64 / 1e9)
+		}
+	}
+
+	/// The offset the time is displayed in.
+	pub fn offset(&self) -> FixedOffset {
+		*self.0.offset()
+	}
+
+	/// The same instant, displayed in a different offset.
+	pub fn to_offset(&self, offset: FixedOffset) -> Self {
+		Self(self.0.with_timezone(&offset))
+	}
+
+	/// Moves `self` by `duration`, returning `None` if it's out of range.
+	pub fn checked_add(&self, duration: &Duration) -> Option<Self> {
+		self.0.checked_add_signed((*duration).into()).map(Self)
+	}
+
+	/// How long it is from `earlier` until `self`, which is negative if `earlier` is later.
+	pub fn duration_since(&self, earlier: &Self) -> Duration {
+		self.0.signed_duration_since(earlier.0).into()
+	}
+}
+
+impl From<DateTime<FixedOffset>> for Time {
+	#[inline]
+	fn from(time: DateTime<FixedOffset>) -> Self {
+		Self(time)
+	}
+}
+
+impl From<Time> for DateTime<FixedOffset> {
+	#[inline]
+	fn from(time: Time) -> Self {
+		time.0
+	}
+}
+
+impl Display for Time {
+	/// Formats the time as RFC 3339, using `Z` for UTC.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0.to_rfc3339_opts(SecondsFormat::AutoSi, true), f)
+	}
+}
+
+/// Quest methods
+impl Time {
+	/// Creates a time from its components.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The year.
+	/// 2. (optional, `@num`) The month, from `1` (the default) to `12`.
+	/// 3. (optional, `@num`) The day of the month, starting from `1` (the default).
+	/// 4. (optional, `@num`) The hour, defaulting to `0`.
+	/// 5. (optional, `@num`) The minute, defaulting to `0`.
+	/// 6. (optional, `@num`) The second, which can be fractional, defaulting to `0`.
+	/// 7. (optional, `Number` or `@text`) The UTC offset in seconds, or as text like `"+05:30"`.
+	///    Defaults to UTC.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// time = Time(2021, 3, 4, 5, 6, 7.5, "+08:00");
+	/// assert(time.@text() == "2021-03-04T05:06:07.500+08:00");
+	/// ```
+	#[instrument(name="Time::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let component = |idx: usize, default: f64| -> crate::Result<f64> {
+			match args.arg(idx) {
+				Some(arg) => Ok(arg.call_downcast::<Number>()?.clone().into()),
+				None => Ok(default)
+			}
+		};
+
+		let year: f64 = args.try_arg(0)?.call_downcast::<Number>()?.clone().into();
+		let (month, day) = (component(1, 1.0)?, component(2, 1.0)?);
+		let (hour, minute, second) = (component(3, 0.0)?, component(4, 0.0)?, component(5, 0.0)?);
+		let offset = optional_offset(&args, 6)?;
+
+		let invalid = || ValueError::Messaged("invalid date or time".to_string());
+		let to_u32 = |num: f64| if (0.0..=u32::MAX as f64).contains(&num) { Ok(num as u32) } else { Err(invalid()) };
+
+		let date = chrono::NaiveDate::from_ymd_opt(year as i32, to_u32(month)?, to_u32(day)?).ok_or_else(invalid)?;
+		let nanos = ((second - second.floor()) * 1e9).round().min(999_999_999.0);
+		let time = date.and_hms_nano_opt(to_u32(hour)?, to_u32(minute)?, to_u32(second.floor())?, nanos as u32)
+			.ok_or_else(invalid)?;
+
+		Ok(offset.from_local_datetime(&time).single().map(Self).ok_or_else(invalid)?.into())
+	}
+
+	/// Gets the current time.
+	///
+	/// # Arguments
+	/// 1. (optional, `Number` or `@text`) The UTC offset to display it in, defaulting to UTC.
+	#[instrument(name="Time::now", level="trace", skip(args), fields(?args))]
+	pub fn qs_now(args: Args) -> crate::Result<Object> {
+		Ok(Self::now().to_offset(optional_offset(&args, 0)?).into())
+	}
+
+	/// Creates a time from seconds since the unix epoch.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The number of seconds, which can be fractional.
+	/// 2. (optional, `Number` or `@text`) The UTC offset to display it in, defaulting to UTC.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Time::at(0).@text() == "1970-01-01T00:00:00Z");
+	/// ```
+	#[instrument(name="Time::at", level="trace", skip(args), fields(?args))]
+	pub fn qs_at(args: Args) -> crate::Result<Object> {
+		let secs: f64 = args.try_arg(0)?.call_downcast::<Number>()?.clone().into();
+		let offset = optional_offset(&args, 1)?;
+
+		Ok(Self::from_timestamp(secs, offset).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Parses text as a time.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The text to parse.
+	/// 2. (optional, `@text`) A strftime-style format, such as `"%Y-%m-%d %H:%M"`. If omitted,
+	///    the text must be RFC 3339.
+	/// 3. (optional, `Number` or `@text`) The UTC offset to use if the format doesn't include
+	///    one, defaulting to UTC.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// time = Time::parse("04/03/2021 17:30", "%d/%m/%Y %H:%M", "-05:00");
+	/// assert(time.@text() == "2021-03-04T17:30:00-05:00");
+	/// ```
+	#[instrument(name="Time::parse", level="trace", skip(args), fields(?args))]
+	pub fn qs_parse(args: Args) -> crate::Result<Object> {
+		let text = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		match args.arg(1) {
+			Some(fmt) => {
+				let fmt = fmt.call_downcast::<Text>()?;
+				let offset = optional_offset(&args, 2)?;
+
+				Ok(Self::parse(text.as_ref(), fmt.as_ref(), offset)?.into())
+			},
+			None => Ok(Self::parse_rfc3339(text.as_ref())?.into())
+		}
+	}
+
+	/// Formats the time.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) A strftime-style format, such as `"%Y-%m-%d %H:%M"`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Time(2021, 3, 4).format("%A, %B %-d") == "Thursday, March 4");
+	/// ```
+	#[instrument(name="Time::format", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_format(this: &Object, args: Args) -> crate::Result<Object> {
+		let fmt = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(this.try_downcast::<Self>()?.format(fmt.as_ref())?.into())
+	}
+
+	fn component(this: &Object, func: impl FnOnce(&DateTime<FixedOffset>) -> u32) -> crate::Result<Object> {
+		Ok(func(&this.try_downcast::<Self>()?.0).into())
+	}
+
+	/// Gets the year.
+	#[instrument(name="Time::year", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_year(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.year().into())
+	}
+
+	/// Gets the month, from `1` to `12`.
+	#[instrument(name="Time::month", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_month(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Datelike::month)
+	}
+
+	/// Gets the day of the month, starting from `1`.
+	#[instrument(name="Time::day", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_day(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Datelike::day)
+	}
+
+	/// Gets the hour, from `0` to `23`.
+	#[instrument(name="Time::hour", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hour(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Timelike::hour)
+	}
+
+	/// Gets the minute, from `0` to `59`.
+	#[instrument(name="Time::minute", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_minute(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Timelike::minute)
+	}
+
+	/// Gets the whole second, from `0` to `59`.
+	#[instrument(name="Time::second", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_second(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Timelike::second)
+	}
+
+	/// Gets the nanoseconds past the whole second.
+	#[instrument(name="Time::nanosecond", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_nanosecond(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Timelike::nanosecond)
+	}
+
+	/// Gets the day of the week, from `1` (Monday) to `7` (Sunday).
+	#[instrument(name="Time::weekday", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_weekday(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, |time| time.weekday().number_from_monday())
+	}
+
+	/// Gets the day of the year, starting from `1`.
+	#[instrument(name="Time::yday", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_yday(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::component(this, Datelike::ordinal)
+	}
+
+	/// Gets the UTC offset, in seconds east of UTC.
+	#[instrument(name="Time::offset", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_offset(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.offset().local_minus_utc().into())
+	}
+
+	/// Gets the same instant, displayed in UTC.
+	#[instrument(name="Time::utc", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_utc(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_offset(utc()).into())
+	}
+
+	/// Gets the same instant, displayed in a different UTC offset.
+	///
+	/// # Arguments
+	/// 1. (required, `Number` or `@text`) The offset in seconds, or as text like `"+05:30"`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// time = Time(2021, 3, 4, 12).to_offset("-08:00");
+	/// assert(time.hour() == 4);
+	/// assert(time == Time(2021, 3, 4, 12));
+	/// ```
+	#[instrument(name="Time::to_offset", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_to_offset(this: &Object, args: Args) -> crate::Result<Object> {
+		let offset = offset_arg(args.try_arg(0)?)?;
+
+		Ok(this.try_downcast::<Self>()?.to_offset(offset).into())
+	}
+
+	/// Converts the time to seconds since the unix epoch, including fractional seconds.
+	#[instrument(name="Time::@num", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_num(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.timestamp().into())
+	}
+
+	/// Formats the time as RFC 3339.
+	#[instrument(name="Time::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+
+	#[instrument(name="Time::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("Time({})", *this.try_downcast::<Self>()?).into())
+	}
+
+	/// Gets a later (or, for negative durations, earlier) time.
+	///
+	/// # Arguments
+	/// 1. (required, `Duration`) How much later it should be.
+	#[instrument(name="Time::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = *args.try_arg(0)?.try_downcast::<Duration>()?;
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(this.checked_add(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Gets the [`Duration`] between two times, or an earlier time.
+	///
+	/// # Arguments
+	/// 1. (required, `Time` or `Duration`) The time to measure from, or how much earlier the
+	///    result should be.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert((Time(2021, 3, 5) - Time(2021, 3, 4)) == Duration::from_days(1));
+	/// assert((Time(2021, 3, 5) - Duration::from_hours(1)) == Time(2021, 3, 4, 23));
+	/// ```
+	#[instrument(name="Time::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = *this.try_downcast::<Self>()?;
+
+		if let Some(rhs) = rhs.downcast::<Self>() {
+			return Ok(this.duration_since(&rhs).into());
+		}
+
+		let rhs = *rhs.try_downcast::<Duration>()?;
+		let rhs = Duration::from_nanos(-rhs.as_nanos()).ok_or_else(out_of_range)?;
+
+		Ok(this.checked_add(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Checks to see if `this` is the same instant as the first argument, regardless of offset.
+	///
+	/// # Arguments
+	/// 1. (required) The value to compare against.
+	#[instrument(name="Time::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.downcast::<Self>().is_some_and(|rhs| *this == *rhs).into())
+	}
+
+	/// Compares `this` to another time, returning `null` for anything else.
+	///
+	/// # Arguments
+	/// 1. (required) The value to compare against.
+	#[instrument(name="Time::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.downcast::<Self>()
+			.map(|rhs| Ordering::into(this.cmp(&rhs)))
+			.unwrap_or_default())
+	}
+}
+
+impl_object_type!{
+for Time [(init_parent super::Basic super::Comparable) (parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"now" => function Self::qs_now,
+	"at" => function Self::qs_at,
+	"parse" => function Self::qs_parse,
+	"format" => method Self::qs_format,
+
+	"year" => method Self::qs_year,
+	"month" => method Self::qs_month,
+	"day" => method Self::qs_day,
+	"hour" => method Self::qs_hour,
+	"minute" => method Self::qs_minute,
+	"second" => method Self::qs_second,
+	"nanosecond" => method Self::qs_nanosecond,
+	"weekday" => method Self::qs_weekday,
+	"yday" => method Self::qs_yday,
+
+	"offset" => method Self::qs_offset,
+	"utc" => method Self::qs_utc,
+	"to_offset" => method Self::qs_to_offset,
+
+	"@num" => method Self::qs_at_num,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn time(text: &str) -> Time {
+		Time::parse_rfc3339(text).unwrap()
+	}
+
+	#[test]
+	fn offsets() {
+		assert_eq!(parse_offset("Z"), Some(utc()));
+		assert_eq!(parse_offset("utc"), Some(utc()));
+		assert_eq!(parse_offset("+05:30"), FixedOffset::east_opt(19800));
+		assert_eq!(parse_offset("-0800"), FixedOffset::east_opt(-28800));
+		assert_eq!(parse_offset("+09"), FixedOffset::east_opt(32400));
+		assert_eq!(parse_offset("05:30"), None);
+		assert_eq!(parse_offset("+5:30"), None);
+		assert_eq!(parse_offset("+05:60"), None);
+	}
+
+	#[test]
+	fn parsing_and_formatting() {
+		let parsed = Time::parse("04/03/2021 17:30", "%d/%m/%Y %H:%M", FixedOffset::east(-18000)).unwrap();
+		assert_eq!(parsed.to_string(), "2021-03-04T17:30:00-05:00");
+
+		let parsed = Time::parse("2021-03-04 +0100", "%Y-%m-%d %z", utc()).unwrap();
+		assert_eq!(parsed.to_string(), "2021-03-04T00:00:00+01:00");
+
+		let parsed = Time::parse("1614816000", "%s", utc()).unwrap();
+		assert_eq!(parsed, time("2021-03-04T00:00:00Z"));
+
+		assert!(Time::parse("2021-03-04", "%Y-%m-%d %H", utc()).is_err());
+		assert!(Time::parse("2021-03-04", "%Q", utc()).is_err());
+		assert!(Time::parse_rfc3339("yesterday").is_err());
+
+		let time = time("2021-03-04T05:06:07.5+08:00");
+		assert_eq!(time.format("%A, %B %-d %H:%M:%S%.3f %:z").unwrap(), "Thursday, March 4 05:06:07.500 +08:00");
+		assert!(time.format("%Q").is_err());
+	}
+
+	#[test]
+	fn arithmetic() {
+		let start = time("2021-03-04T23:00:00Z");
+		let day = Duration::from_secs_f64(86400.0).unwrap();
+
+		assert_eq!(start.checked_add(&day), Some(time("2021-03-05T23:00:00Z")));
+		assert_eq!(time("2021-03-05T23:00:00Z").duration_since(&start), day);
+		assert_eq!(start.duration_since(&time("2021-03-05T23:00:00Z")), Duration::from_secs_f64(-86400.0).unwrap());
+
+		assert_eq!(start.to_offset(FixedOffset::east(3600)).to_string(), "2021-03-05T00:00:00+01:00");
+		assert_eq!(start.to_offset(FixedOffset::east(3600)), start);
+
+		assert_eq!(Time::from_timestamp(0.5, utc()).unwrap().to_string(), "1970-01-01T00:00:00.500Z");
+		assert_eq!(Time::from_timestamp(1.5, utc()).unwrap().timestamp(), Number::from(1.5));
+		assert_eq!(start.timestamp(), Number::from(1614898800));
+		assert_eq!(Time::from_timestamp(f64::NAN, utc()), None);
+	}
+
+	#[test]
+	fn quest_methods() {
+		crate::init();
+
+		let time = Time::qs_call(&Object::default(), args!(2021, 3, 4, 5, 6, 7.5, "+08:00")).unwrap();
+		assert_eq!(*time.downcast::<Time>().unwrap(), self::time("2021-03-04T05:06:07.5+08:00"));
+
+		assert_eq!(*Time::qs_weekday(&time, args!()).unwrap().downcast::<Number>().unwrap(), Number::from(4));
+		assert_eq!(*Time::qs_offset(&time, args!()).unwrap().downcast::<Number>().unwrap(), Number::from(28800));
+		assert!(Time::qs_call(&Object::default(), args!(2021, 2, 30)).is_err());
+		assert!(Time::qs_call(&Object::default(), args!(2021, 1, 1, 0, 0, 0, "soon")).is_err());
+
+		let utc = Time::qs_utc(&time, args!()).unwrap();
+		assert_eq!(*Time::qs_hour(&utc, args!()).unwrap().downcast::<Number>().unwrap(), Number::from(21));
+
+		let earlier = Time::qs_parse(args!("2021-03-02T21:06:07.5Z")).unwrap();
+		let diff = time.call_attr_lit("-", &[&earlier]).unwrap();
+		assert_eq!(diff.downcast::<Duration>().unwrap().to_string(), "24h0m0s");
+
+		let gth = time.call_attr_lit(">", &[&earlier]).unwrap();
+		assert!(gth.call_downcast::<crate::types::Boolean>().unwrap().into_inner());
+
+		assert_eq!(*time.call_downcast::<Number>().unwrap(), Number::from(1614805567.5));
+	}
+}
@@ -0,0 +1,483 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::Number;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use tracing::instrument;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// A signed span of time, with nanosecond precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration(chrono::Duration);
+
+fn out_of_range() -> crate::Error {
+	ValueError::Messaged("duration out of range".to_string()).into()
+}
+
+impl Duration {
+	/// Creates a duration of `nanos` nanoseconds, returning `None` if it's out of range.
+	pub fn from_nanos(nanos: i128) -> Option<Self> {
+		let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC)).ok()?;
+		let nanos = nanos.rem_euclid(NANOS_PER_SEC) as i64;
+
+		// `chrono::Duration::seconds` panics if it's given too many seconds.
+		if secs.unsigned_abs() > (i64::MAX / 1000) as u64 {
+			return None;
+		}
+
+		chrono::Duration::seconds(secs)
+			.checked_add(&chrono::Duration::nanoseconds(nanos))
+			.map(Self)
+	}
+
+	/// Creates a duration of `secs` seconds, returning `None` if it's out of range or not finite.
+	pub fn from_secs_f64(secs: f64) -> Option<Self> {
+		let nanos = (secs * NANOS_PER_SEC as f64).round();
+
+		if nanos.is_finite() && nanos.abs() < i128::MAX as f64 {
+			Self::from_nanos(nanos as i128)
+		} else {
+			None
+		}
+	}
+
+	/// The total number of nanoseconds.
+	pub fn as_nanos(&self) -> i128 {
+		let secs = self.0.num_seconds();
+		let subsec = (self.0 - chrono::Duration::seconds(secs)).num_nanoseconds()
+			.expect("less than a second always fits");
+
+		secs as i128 * NANOS_PER_SEC + subsec as i128
+	}
+
+	/// The total number of seconds, including fractional seconds.
+	pub fn as_secs_f64(&self) -> f64 {
+		self.as_nanos() as f64 / NANOS_PER_SEC as f64
+	}
+
+	/// Checks to see if the duration is less than zero.
+	pub fn is_negative(&self) -> bool {
+		self.0 < chrono::Duration::zero()
+	}
+
+	/// Converts `self` to a [`std::time::Duration`], returning `None` if it's negative.
+	pub fn to_std(&self) -> Option<std::time::Duration> {
+		self.0.to_std().ok()
+	}
+
+	/// Adds `rhs` to `self`, returning `None` if it's out of range.
+	pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+		self.0.checked_add(&rhs.0).map(Self)
+	}
+
+	/// Subtracts `rhs` from `self`, returning `None` if it's out of range.
+	pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+		self.0.checked_sub(&rhs.0).map(Self)
+	}
+
+	/// Multiplies `self` by `rhs`, returning `None` if it's out of range.
+	pub fn checked_mul(&self, rhs: &Number) -> Option<Self> {
+		if rhs.is_integer() {
+			let rhs = i128::try_from(rhs.clone()).ok()?;
+			Self::from_nanos(self.as_nanos().checked_mul(rhs)?)
+		} else {
+			Self::from_secs_f64(self.as_secs_f64() * f64::from(rhs.clone()))
+		}
+	}
+
+	/// Divides `self` by `rhs`, returning `None` if it's out of range (or `rhs` is zero).
+	pub fn checked_div(&self, rhs: &Number) -> Option<Self> {
+		if rhs.is_integer() {
+			let rhs = i128::try_from(rhs.clone()).ok()?;
+			Self::from_nanos(self.as_nanos().checked_div(rhs)?)
+		} else {
+			Self::from_secs_f64(self.as_secs_f64() / f64::from(rhs.clone()))
+		}
+	}
+}
+
+impl Default for Duration {
+	#[inline]
+	fn default() -> Self {
+		Self(chrono::Duration::zero())
+	}
+}
+
+// `chrono::Duration` only implements `Hash` with its `oldtime` feature.
+impl Hash for Duration {
+	fn hash<H: Hasher>(&self, h: &mut H) {
+		self.as_nanos().hash(h)
+	}
+}
+
+impl From<chrono::Duration> for Duration {
+	#[inline]
+	fn from(duration: chrono::Duration) -> Self {
+		Self(duration)
+	}
+}
+
+impl From<Duration> for chrono::Duration {
+	#[inline]
+	fn from(duration: Duration) -> Self {
+		duration.0
+	}
+}
+
+impl From<std::time::Duration> for Duration {
+	/// Converts a [`std::time::Duration`], saturating if it's too large.
+	fn from(duration: std::time::Duration) -> Self {
+		Self(chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::max_value()))
+	}
+}
+
+/// Writes `whole.frac` with trailing zeros (and a trailing `.`) removed.
+fn write_decimal(f: &mut Formatter, whole: i128, frac: i128, digits: usize) -> fmt::Result {
+	let frac = format!("{:0width$}", frac, width=digits);
+	let frac = frac.trim_end_matches('0');
+
+	if frac.is_empty() {
+		write!(f, "{}", whole)
+	} else {
+		write!(f, "{}.{}", whole, frac)
+	}
+}
+
+impl Display for Duration {
+	/// Formats the duration like `1h2m3.5s`, or `1.5ms` for durations less than a second.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut nanos = self.as_nanos();
+
+		if nanos < 0 {
+			write!(f, "-")?;
+			nanos = -nanos;
+		}
+
+		match nanos {
+			0 => write!(f, "0s"),
+			1..=999 => write!(f, "{}ns", nanos),
+			1_000..=999_999 => write_decimal(f, nanos / 1_000, nanos % 1_000, 3).and_then(|_| write!(f, "µs")),
+			1_000_000..=999_999_999 => write_decimal(f, nanos / 1_000_000, nanos % 1_000_000, 6).and_then(|_| write!(f, "ms")),
+			_ => {
+				let secs = nanos / NANOS_PER_SEC;
+				let (hours, minutes) = (secs / 3600, secs / 60 % 60);
+
+				if hours != 0 {
+					write!(f, "{}h", hours)?;
+				}
+
+				if hours != 0 || minutes != 0 {
+					write!(f, "{}m", minutes)?;
+				}
+
+				write_decimal(f, secs % 60, nanos % NANOS_PER_SEC, 9)?;
+				write!(f, "s")
+			}
+		}
+	}
+}
+
+/// Converts `arg` to a [`Duration`] of that many seconds.
+fn secs_arg(arg: &Object, scale: f64) -> crate::Result<Duration> {
+	let secs: f64 = arg.call_downcast::<Number>()?.clone().into();
+
+	Duration::from_secs_f64(secs * scale).ok_or_else(out_of_range)
+}
+
+/// Quest methods
+impl Duration {
+	/// Creates a duration from a number of seconds.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The number of seconds, which can be fractional or negative.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Duration(1.5).@text() == "1.5s");
+	/// assert(Duration(90) == Duration::from_minutes(1.5));
+	/// ```
+	#[instrument(name="Duration::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(secs_arg(args.try_arg(0)?, 1.0)?.into())
+	}
+
+	/// Creates a duration from a number of milliseconds.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The number of milliseconds.
+	#[instrument(name="Duration::from_millis", level="trace", skip(args), fields(?args))]
+	pub fn qs_from_millis(args: Args) -> crate::Result<Object> {
+		Ok(secs_arg(args.try_arg(0)?, 1e-3)?.into())
+	}
+
+	/// Creates a duration from a number of minutes.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The number of minutes.
+	#[instrument(name="Duration::from_minutes", level="trace", skip(args), fields(?args))]
+	pub fn qs_from_minutes(args: Args) -> crate::Result<Object> {
+		Ok(secs_arg(args.try_arg(0)?, 60.0)?.into())
+	}
+
+	/// Creates a duration from a number of hours.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The number of hours.
+	#[instrument(name="Duration::from_hours", level="trace", skip(args), fields(?args))]
+	pub fn qs_from_hours(args: Args) -> crate::Result<Object> {
+		Ok(secs_arg(args.try_arg(0)?, 3600.0)?.into())
+	}
+
+	/// Creates a duration from a number of days.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The number of days.
+	#[instrument(name="Duration::from_days", level="trace", skip(args), fields(?args))]
+	pub fn qs_from_days(args: Args) -> crate::Result<Object> {
+		Ok(secs_arg(args.try_arg(0)?, 86400.0)?.into())
+	}
+
+	fn total(this: &Object, scale: f64) -> crate::Result<Object> {
+		Ok((this.try_downcast::<Self>()?.as_secs_f64() / scale).into())
+	}
+
+	/// Gets the total number of seconds, including fractional seconds.
+	#[instrument(name="Duration::seconds", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_seconds(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::total(this, 1.0)
+	}
+
+	/// Gets the total number of milliseconds, including fractional milliseconds.
+	#[instrument(name="Duration::millis", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_millis(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::total(this, 1e-3)
+	}
+
+	/// Gets the total number of minutes, including fractional minutes.
+	#[instrument(name="Duration::minutes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_minutes(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::total(this, 60.0)
+	}
+
+	/// Gets the total number of hours, including fractional hours.
+	#[instrument(name="Duration::hours", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hours(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::total(this, 3600.0)
+	}
+
+	/// Gets the total number of days, including fractional days.
+	#[instrument(name="Duration::days", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_days(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::total(this, 86400.0)
+	}
+
+	/// Converts the duration to a number of seconds.
+	#[instrument(name="Duration::@num", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_num(this: &Object, _: Args) -> crate::Result<Object> {
+		Self::total(this, 1.0)
+	}
+
+	/// Formats the duration like `1h2m3.5s`.
+	#[instrument(name="Duration::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+
+	#[instrument(name="Duration::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("Duration({})", *this.try_downcast::<Self>()?).into())
+	}
+
+	/// Adds two durations together.
+	///
+	/// # Arguments
+	/// 1. (required, `Duration`) The duration to add.
+	#[instrument(name="Duration::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = *args.try_arg(0)?.try_downcast::<Self>()?;
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(this.checked_add(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Subtracts a duration from `this`.
+	///
+	/// # Arguments
+	/// 1. (required, `Duration`) The duration to subtract.
+	#[instrument(name="Duration::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = *args.try_arg(0)?.try_downcast::<Self>()?;
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(this.checked_sub(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Scales `this` by a number.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) What to multiply by.
+	#[instrument(name="Duration::*", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Number>()?.clone();
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(this.checked_mul(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Divides `this` by a number, or by another duration to get their ratio.
+	///
+	/// # Arguments
+	/// 1. (required, `Duration` or `@num`) What to divide by.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert((Duration(3) / 2) == Duration(1.5));
+	/// assert((Duration(3) / Duration(2)) == 1.5);
+	/// ```
+	#[instrument(name="Duration::/", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_div(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = *this.try_downcast::<Self>()?;
+
+		if let Some(rhs) = rhs.downcast::<Self>() {
+			return Ok((this.as_secs_f64() / rhs.as_secs_f64()).into());
+		}
+
+		let rhs = rhs.call_downcast::<Number>()?.clone();
+
+		Ok(this.checked_div(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Negates the duration.
+	#[instrument(name="Duration::-@", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_neg(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(Self::from_nanos(-this.as_nanos()).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Gets the duration without its sign.
+	#[instrument(name="Duration::abs", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_abs(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(Self::from_nanos(this.as_nanos().abs()).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Checks to see if `this` is the same length as the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The value to compare against.
+	#[instrument(name="Duration::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.downcast::<Self>().is_some_and(|rhs| *this == *rhs).into())
+	}
+
+	/// Compares `this` to another duration, returning `null` for anything else.
+	///
+	/// # Arguments
+	/// 1. (required) The value to compare against.
+	#[instrument(name="Duration::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.downcast::<Self>()
+			.map(|rhs| Ordering::into(this.cmp(&rhs)))
+			.unwrap_or_default())
+	}
+}
+
+impl_object_type!{
+for Duration [(init_parent super::super::Basic super::super::Comparable) (parents super::super::Basic)]:
+	"()" => method Self::qs_call,
+	"from_millis" => function Self::qs_from_millis,
+	"from_minutes" => function Self::qs_from_minutes,
+	"from_hours" => function Self::qs_from_hours,
+	"from_days" => function Self::qs_from_days,
+
+	"seconds" => method Self::qs_seconds,
+	"millis" => method Self::qs_millis,
+	"minutes" => method Self::qs_minutes,
+	"hours" => method Self::qs_hours,
+	"days" => method Self::qs_days,
+
+	"@num" => method Self::qs_at_num,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+	"*" => method Self::qs_mul,
+	"/" => method Self::qs_div,
+	"-@" => method Self::qs_neg,
+	"abs" => method Self::qs_abs,
+
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn secs(secs: f64) -> Duration {
+		Duration::from_secs_f64(secs).unwrap()
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(secs(0.0).to_string(), "0s");
+		assert_eq!(Duration::from_nanos(15).unwrap().to_string(), "15ns");
+		assert_eq!(Duration::from_nanos(1_500).unwrap().to_string(), "1.5µs");
+		assert_eq!(secs(0.25).to_string(), "250ms");
+		assert_eq!(secs(1.5).to_string(), "1.5s");
+		assert_eq!(secs(3723.5).to_string(), "1h2m3.5s");
+		assert_eq!(secs(7200.0).to_string(), "2h0m0s");
+		assert_eq!(secs(-90.0).to_string(), "-1m30s");
+	}
+
+	#[test]
+	fn arithmetic() {
+		assert_eq!(secs(1.5).as_nanos(), 1_500_000_000);
+		assert_eq!(secs(-1.5).as_nanos(), -1_500_000_000);
+		assert_eq!(secs(1.0).checked_add(&secs(0.5)), Some(secs(1.5)));
+		assert_eq!(secs(1.0).checked_sub(&secs(1.5)), Some(secs(-0.5)));
+		assert_eq!(secs(1.5).checked_mul(&Number::from(3)), Some(secs(4.5)));
+		assert_eq!(secs(1.5).checked_mul(&Number::from(0.5)), Some(secs(0.75)));
+		assert_eq!(secs(3.0).checked_div(&Number::from(2)), Some(secs(1.5)));
+		assert_eq!(secs(3.0).checked_div(&Number::from(0)), None);
+		assert_eq!(Duration::from_secs_f64(f64::INFINITY), None);
+		assert_eq!(Duration::from_secs_f64(1e300), None);
+		assert!(secs(-1.0).is_negative());
+		assert_eq!(secs(-1.0).to_std(), None);
+	}
+
+	#[test]
+	fn quest_methods() {
+		crate::init();
+
+		let half = Duration::qs_from_millis(args!(500)).unwrap();
+		let thirty = Duration::qs_from_minutes(args!(0.5)).unwrap();
+		assert_eq!(*half.downcast::<Duration>().unwrap(), secs(0.5));
+		assert_eq!(*thirty.downcast::<Duration>().unwrap(), secs(30.0));
+
+		let sum = half.call_attr_lit("+", &[&thirty]).unwrap();
+		assert_eq!(*sum.downcast::<Duration>().unwrap(), secs(30.5));
+
+		let ratio = thirty.call_attr_lit("/", &[&half]).unwrap();
+		assert_eq!(*ratio.downcast::<Number>().unwrap(), Number::from(60));
+
+		let lth = half.call_attr_lit("<", &[&thirty]).unwrap();
+		assert!(lth.call_downcast::<crate::types::Boolean>().unwrap().into_inner());
+
+		let num = sum.call_downcast::<Number>().unwrap().clone();
+		assert_eq!(num, Number::from(30.5));
+	}
+}
@@ -0,0 +1,164 @@
+use super::Duration;
+use crate::{Object, Args};
+use crate::error::ValueError;
+use std::cmp::Ordering;
+use tracing::instrument;
+
+/// A reading of a monotonic clock, for measuring how long something takes.
+///
+/// Unlike [`Time`](super::Time), instants never go backwards, but they're only meaningful
+/// relative to one another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(std::time::Instant);
+
+impl Instant {
+	/// Reads the monotonic clock.
+	pub fn now() -> Self {
+		Self(std::time::Instant::now())
+	}
+
+	/// How long it's been since `self`.
+	pub fn elapsed(&self) -> Duration {
+		self.0.elapsed().into()
+	}
+
+	/// How long it is from `earlier` until `self`, which is negative if `earlier` is later.
+	pub fn duration_since(&self, earlier: &Self) -> Duration {
+		if self.0 >= earlier.0 {
+			(self.0 - earlier.0).into()
+		} else {
+			let duration = Duration::from(earlier.0 - self.0);
+			Duration::from_nanos(-duration.as_nanos()).unwrap_or_default()
+		}
+	}
+
+	/// Moves `self` by `duration` (backwards if it's negative), returning `None` if that's not
+	/// representable.
+	pub fn checked_add(&self, duration: &Duration) -> Option<Self> {
+		if duration.is_negative() {
+			let duration = Duration::from_nanos(-duration.as_nanos())?.to_std()?;
+			self.0.checked_sub(duration).map(Self)
+		} else {
+			self.0.checked_add(duration.to_std()?).map(Self)
+		}
+	}
+}
+
+fn out_of_range() -> crate::Error {
+	ValueError::Messaged("instant out of range".to_string()).into()
+}
+
+/// Quest methods
+impl Instant {
+	/// Reads the monotonic clock.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// start = Instant::now();
+	/// sleep(0.1);
+	/// assert(start.elapsed() >= Duration(0.1));
+	/// ```
+	#[instrument(name="Instant::now", level="trace")]
+	pub fn qs_now(_: Args) -> crate::Result<Object> {
+		Ok(Self::now().into())
+	}
+
+	/// Gets how long it's been since `this`, as a [`Duration`].
+	#[instrument(name="Instant::elapsed", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_elapsed(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.elapsed().into())
+	}
+
+	/// Gets a later instant.
+	///
+	/// # Arguments
+	/// 1. (required, `Duration`) How much later it should be.
+	#[instrument(name="Instant::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = *args.try_arg(0)?.try_downcast::<Duration>()?;
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(this.checked_add(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Gets the [`Duration`] between two instants, or an earlier instant.
+	///
+	/// # Arguments
+	/// 1. (required, `Instant` or `Duration`) The instant to measure from, or how much earlier
+	///    the result should be.
+	#[instrument(name="Instant::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = *this.try_downcast::<Self>()?;
+
+		if let Some(rhs) = rhs.downcast::<Self>() {
+			return Ok(this.duration_since(&rhs).into());
+		}
+
+		let rhs = *rhs.try_downcast::<Duration>()?;
+		let rhs = Duration::from_nanos(-rhs.as_nanos()).ok_or_else(out_of_range)?;
+
+		Ok(this.checked_add(&rhs).ok_or_else(out_of_range)?.into())
+	}
+
+	/// Checks to see if `this` is the same instant as the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The value to compare against.
+	#[instrument(name="Instant::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.downcast::<Self>().is_some_and(|rhs| *this == *rhs).into())
+	}
+
+	/// Compares `this` to another instant, returning `null` for anything else.
+	///
+	/// # Arguments
+	/// 1. (required) The value to compare against.
+	#[instrument(name="Instant::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.downcast::<Self>()
+			.map(|rhs| Ordering::into(this.cmp(&rhs)))
+			.unwrap_or_default())
+	}
+
+	#[instrument(name="Instant::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(format!("Instant(elapsed={})", this.try_downcast::<Self>()?.elapsed()).into())
+	}
+}
+
+impl_object_type!{
+for Instant [(init_parent super::super::Basic super::super::Comparable) (parents super::super::Basic)]:
+	"now" => function Self::qs_now,
+	"elapsed" => method Self::qs_elapsed,
+
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn arithmetic() {
+		let start = Instant::now();
+		let later = start.checked_add(&Duration::from_secs_f64(1.5).unwrap()).unwrap();
+
+		assert!(later > start);
+		assert_eq!(later.duration_since(&start), Duration::from_secs_f64(1.5).unwrap());
+		assert_eq!(start.duration_since(&later), Duration::from_secs_f64(-1.5).unwrap());
+		assert_eq!(later.checked_add(&Duration::from_secs_f64(-1.5).unwrap()), Some(start));
+		assert!(start.elapsed() >= Duration::default());
+	}
+}
@@ -54,6 +54,26 @@ pub fn correct_index(idx: isize, len: usize) -> Result<usize, IndexError> {
 	}
 }
 
+/// Corrects a Quest-style range, where both ends are inclusive and can be negative.
+///
+/// An end that's past `len` is clamped to it, but `None` is returned if the start is out of bounds
+/// or the range is backwards.
+pub fn correct_range(range: std::ops::RangeInclusive<isize>, len: usize) -> Option<std::ops::Range<usize>> {
+	let start = correct_index(*range.start(), len).ok()?;
+	let end =
+		match correct_index(*range.end(), len) {
+			Ok(end) => end + 1,
+			Err(IndexError::TooPositive) => len,
+			Err(IndexError::TooNegative) => return None
+		};
+
+	if end < start {
+		None
+	} else {
+		Some(start..end)
+	}
+}
+
 #[test]
 fn test_correct_index() {
 	assert_eq!(correct_index(2, 0), Err(IndexError::TooPositive));
@@ -38,6 +38,62 @@ impl<T> SliceIndex<[T]> for isize {
 	}
 }
 
+/// Quest-style ranges: both ends are inclusive and can be negative, and an end that's past the
+/// end of the slice is clamped to it.
+impl<T> SliceIndex<[T]> for RangeInclusive<isize> {
+	type Output = [T];
+
+	fn get(self, slice: &[T]) -> Option<&Self::Output> {
+		slice.get(super::correct_range(self, slice.len())?)
+	}
+
+	fn get_mut(self, slice: &mut [T]) -> Option<&mut Self::Output> {
+		slice.get_mut(super::correct_range(self, slice.len())?)
+	}
+}
+
+/// `str`s are indexed by `char`s, not bytes, and so a single index yields a one-`char` `str`.
+impl SliceIndex<str> for isize {
+	type Output = str;
+
+	fn get(self, slice: &str) -> Option<&Self::Output> {
+		(self..=self).get(slice)
+	}
+
+	fn get_mut(self, slice: &mut str) -> Option<&mut Self::Output> {
+		(self..=self).get_mut(slice)
+	}
+}
+
+impl SliceIndex<str> for RangeInclusive<isize> {
+	type Output = str;
+
+	fn get(self, slice: &str) -> Option<&Self::Output> {
+		slice.get(byte_range(self, slice)?)
+	}
+
+	fn get_mut(self, slice: &mut str) -> Option<&mut Self::Output> {
+		let range = byte_range(self, slice)?;
+		slice.get_mut(range)
+	}
+}
+
+/// Converts a range of `char`s in `slice` to a range of bytes.
+fn byte_range(range: RangeInclusive<isize>, slice: &str) -> Option<Range<usize>> {
+	let chars = super::correct_range(range, slice.chars().count())?;
+	let mut offsets = slice.char_indices().map(|(idx, _)| idx).chain(std::iter::once(slice.len()));
+
+	let start = offsets.nth(chars.start)?;
+	let end =
+		if chars.is_empty() {
+			start
+		} else {
+			offsets.nth(chars.len() - 1)?
+		};
+
+	Some(start..end)
+}
+
 
 // 	pub fn arg(&self, index: usize) -> Option<&'o Object> {
 // 		self.0.get(index).copied()
@@ -0,0 +1,378 @@
+//! Procedural macros for exposing Rust types to Quest.
+//!
+//! These expand to paths within the `quest` crate, so they should be used through its
+//! re-exports (such as [`quest::object`](https://docs.rs/quest)) rather than directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, format_ident};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+	parse_macro_input, parse_quote, Attribute, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl,
+	LitStr, Path, ReturnType, Token, Type
+};
+
+mod kw {
+	syn::custom_keyword!(name);
+	syn::custom_keyword!(parent);
+	syn::custom_keyword!(convert);
+	syn::custom_keyword!(skip);
+}
+
+/// Exposes the associated functions of an `impl` block to Quest.
+///
+/// This implements `ObjectType` for the type, along with `IntoObject` and `FromObject`. Every
+/// function in the block becomes an attribute of the type's class:
+///
+/// - `&self`, `&mut self` and `self` receivers are taken from the object the function is called
+///   on. Functions without a receiver ignore it, and can be used as constructors.
+/// - The remaining parameters are converted with `FromObject`. Trailing `Option`s are optional,
+///   and every other parameter is required. Passing the wrong number of arguments raises an
+///   `ArgumentError::InvalidLength`.
+/// - The return value is converted with `IntoObject`. If the return type is a `Result`, errors are
+///   raised within Quest instead.
+///
+/// The type itself accepts the following options:
+///
+/// - `name = "..."`, the name of the class (defaults to the type's name).
+/// - `parent = path::To::Parent`, the class to inherit from (defaults to `Basic`).
+/// - `convert = "@..."`, the conversion function, which also implements `Convertible`.
+///
+/// Individual functions can be annotated with `#[quest(name = "...")]` to rename the attribute
+/// (e.g. to `"+"`), or with `#[quest(skip)]` to not expose them at all.
+#[proc_macro_attribute]
+pub fn object(attr: TokenStream, item: TokenStream) -> TokenStream {
+	let options = parse_macro_input!(attr as ObjectOptions);
+	let item = parse_macro_input!(item as ItemImpl);
+
+	expand_object(options, item)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+#[derive(Default)]
+struct ObjectOptions {
+	name: Option<LitStr>,
+	parent: Option<Path>,
+	convert: Option<LitStr>
+}
+
+impl Parse for ObjectOptions {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let mut options = Self::default();
+
+		while !input.is_empty() {
+			let lookahead = input.lookahead1();
+
+			if lookahead.peek(kw::name) {
+				input.parse::<kw::name>()?;
+				input.parse::<Token![=]>()?;
+				options.name = Some(input.parse()?);
+			} else if lookahead.peek(kw::parent) {
+				input.parse::<kw::parent>()?;
+				input.parse::<Token![=]>()?;
+				options.parent = Some(input.parse()?);
+			} else if lookahead.peek(kw::convert) {
+				input.parse::<kw::convert>()?;
+				input.parse::<Token![=]>()?;
+				options.convert = Some(input.parse()?);
+			} else {
+				return Err(lookahead.error());
+			}
+
+			if !input.is_empty() {
+				input.parse::<Token![,]>()?;
+			}
+		}
+
+		Ok(options)
+	}
+}
+
+/// The options given to a function via `#[quest(...)]`.
+#[derive(Default)]
+struct MethodOptions {
+	name: Option<LitStr>,
+	skip: bool
+}
+
+impl Parse for MethodOptions {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let mut options = Self::default();
+
+		for option in Punctuated::<MethodOption, Token![,]>::parse_terminated(input)? {
+			match option {
+				MethodOption::Name(name) => options.name = Some(name),
+				MethodOption::Skip => options.skip = true
+			}
+		}
+
+		Ok(options)
+	}
+}
+
+enum MethodOption {
+	Name(LitStr),
+	Skip
+}
+
+impl Parse for MethodOption {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let lookahead = input.lookahead1();
+
+		if lookahead.peek(kw::name) {
+			input.parse::<kw::name>()?;
+			input.parse::<Token![=]>()?;
+			Ok(Self::Name(input.parse()?))
+		} else if lookahead.peek(kw::skip) {
+			input.parse::<kw::skip>()?;
+			Ok(Self::Skip)
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
+
+/// Removes the `#[quest(...)]` attributes from `attrs`, merging their options together.
+fn take_method_options(attrs: &mut Vec<Attribute>) -> syn::Result<MethodOptions> {
+	let mut options = MethodOptions::default();
+	let mut error = None;
+
+	attrs.retain(|attr| {
+		if !attr.path.is_ident("quest") {
+			return true;
+		}
+
+		match attr.parse_args::<MethodOptions>() {
+			Ok(parsed) => {
+				options.name = parsed.name.or_else(|| options.name.take());
+				options.skip |= parsed.skip;
+			},
+			Err(err) => error = Some(err)
+		}
+
+		false
+	});
+
+	match error {
+		Some(err) => Err(err),
+		None => Ok(options)
+	}
+}
+
+enum Receiver {
+	None,
+	Ref,
+	Mut,
+	Owned
+}
+
+/// Gets the last path segment of `ty`, if it's a path.
+fn last_segment(ty: &Type) -> Option<&Ident> {
+	match ty {
+		Type::Path(path) if path.qself.is_none() => path.path.segments.last().map(|seg| &seg.ident),
+		_ => None
+	}
+}
+
+/// Creates the function that unpacks the arguments, calls `method`, and converts its result.
+fn expand_wrapper(self_ty: &Type, wrapper: &Ident, method: &ImplItemMethod) -> syn::Result<TokenStream2> {
+	let sig = &method.sig;
+	let ident = &sig.ident;
+
+	if !sig.generics.params.is_empty() {
+		return Err(syn::Error::new(sig.generics.span(), "functions exposed to Quest can't be generic"));
+	}
+
+	let mut receiver = Receiver::None;
+	let mut types = Vec::new();
+
+	for input in &sig.inputs {
+		match input {
+			FnArg::Receiver(recv) if recv.reference.is_none() => receiver = Receiver::Owned,
+			FnArg::Receiver(recv) if recv.mutability.is_some() => receiver = Receiver::Mut,
+			FnArg::Receiver(_) => receiver = Receiver::Ref,
+			FnArg::Typed(typed) => types.push(&*typed.ty)
+		}
+	}
+
+	let total = types.len();
+	let required = total - types.iter().rev()
+		.take_while(|ty| last_segment(ty).is_some_and(|seg| seg == "Option"))
+		.count();
+
+	let (arity_check, expected) =
+		if required == total {
+			(quote! { given != #total }, quote! { #total })
+		} else if required == 0 {
+			(quote! { given > #total }, quote! { #total })
+		} else {
+			(
+				quote! { !(#required..=#total).contains(&given) },
+				quote! { if given < #required { #required } else { #total } }
+			)
+		};
+
+	let args = (0..total).map(|idx| format_ident!("arg{}", idx)).collect::<Vec<_>>();
+	let conversions = types.iter().enumerate().map(|(idx, ty)| {
+		if idx < required {
+			quote! { <#ty as ::quest::FromObject>::from_object(args.try_arg(#idx)?)? }
+		} else {
+			quote! {
+				match args.arg(#idx) {
+					Some(arg) => <#ty as ::quest::FromObject>::from_object(arg)?,
+					None => None
+				}
+			}
+		}
+	});
+
+	let call = match receiver {
+		Receiver::None => quote! { <#self_ty>::#ident(#(#args),*) },
+		Receiver::Ref => quote! {{
+			let this = this.try_downcast::<#self_ty>()?;
+			<#self_ty>::#ident(&*this, #(#args),*)
+		}},
+		Receiver::Mut => quote! {{
+			let mut this = this.try_downcast_mut::<#self_ty>()?;
+			<#self_ty>::#ident(&mut *this, #(#args),*)
+		}},
+		Receiver::Owned => quote! {{
+			let this = this.try_downcast::<#self_ty>()?.clone();
+			<#self_ty>::#ident(this, #(#args),*)
+		}}
+	};
+
+	let returns_result = match &sig.output {
+		ReturnType::Type(_, ty) => last_segment(ty).is_some_and(|seg| seg == "Result"),
+		ReturnType::Default => false
+	};
+
+	let result = if returns_result { quote! { result? } } else { quote! { result } };
+
+	Ok(quote! {
+		#[allow(unused_variables)]
+		fn #wrapper(this: &::quest::Object, args: ::quest::Args) -> ::quest::__core::Result<::quest::Object> {
+			let given = args.len();
+
+			if #arity_check {
+				return Err(::quest::__core::error::ArgumentError::InvalidLength { given, expected: #expected }.into());
+			}
+
+			#(let #args = #conversions;)*
+			let result = #call;
+
+			Ok(::quest::IntoObject::into_object(#result))
+		}
+	})
+}
+
+fn expand_object(options: ObjectOptions, mut item: ItemImpl) -> syn::Result<TokenStream2> {
+	if !item.generics.params.is_empty() {
+		return Err(syn::Error::new(item.generics.span(), "generic types can't be exposed to Quest"));
+	}
+
+	if let Some((_, path, _)) = &item.trait_ {
+		return Err(syn::Error::new(path.span(), "`object` must be used on an inherent `impl`"));
+	}
+
+	let self_ty = (*item.self_ty).clone();
+	let name = match options.name {
+		Some(name) => name,
+		None => match last_segment(&self_ty) {
+			Some(ident) => LitStr::new(&ident.to_string(), ident.span()),
+			None => return Err(syn::Error::new(self_ty.span(), "a `name` is needed for this type"))
+		}
+	};
+	let parent = options.parent.unwrap_or_else(|| parse_quote!(::quest::__core::types::Basic));
+
+	let mut wrappers = Vec::new();
+	let mut attrs = Vec::new();
+
+	for impl_item in &mut item.items {
+		let method = match impl_item {
+			ImplItem::Method(method) => method,
+			_ => continue
+		};
+
+		let method_options = take_method_options(&mut method.attrs)?;
+
+		if method_options.skip {
+			continue;
+		}
+
+		let wrapper = format_ident!("qs_{}", method.sig.ident);
+		let attr_name = method_options.name
+			.unwrap_or_else(|| LitStr::new(&method.sig.ident.to_string(), method.sig.ident.span()));
+		let func_name = format!("{}::{}", name.value(), attr_name.value());
+
+		wrappers.push(expand_wrapper(&self_ty, &wrapper, method)?);
+		attrs.push(quote! {
+			class.set_value_lit(#attr_name, ::quest::__core::types::RustFn::method(#func_name, #wrapper))?;
+		});
+	}
+
+	let (convertible, from_object) = match options.convert {
+		Some(convert) => (
+			quote! {
+				impl ::quest::__core::types::Convertible for #self_ty {
+					const CONVERT_FUNC: ::quest::__core::Literal = ::quest::__core::Literal::new(#convert);
+				}
+			},
+			quote! { Ok(obj.call_downcast::<Self>()?.clone()) }
+		),
+		None => (quote! {}, quote! { Ok(obj.try_downcast::<Self>()?.clone()) })
+	};
+
+	Ok(quote! {
+		#item
+
+		impl ::quest::__core::ObjectType for #self_ty {
+			fn initialize() -> ::quest::__core::Result<()> {
+				use ::std::sync::atomic::{AtomicBool, Ordering};
+
+				static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+				if INITIALIZED.swap(true, Ordering::SeqCst) {
+					return Ok(());
+				}
+
+				<#parent as ::quest::__core::ObjectType>::initialize()?;
+
+				#(#wrappers)*
+
+				let class = <Self as ::quest::__core::ObjectType>::mapping();
+				class.set_attr_lit(::quest::__core::Literal::NAME, ::quest::Object::from(#name))?;
+				#(#attrs)*
+
+				Ok(())
+			}
+
+			fn mapping() -> &'static ::quest::Object {
+				static CLASS: ::std::sync::OnceLock<::quest::Object> = ::std::sync::OnceLock::new();
+
+				CLASS.get_or_init(|| ::quest::Object::new_with_parent(
+					::quest::__core::types::Class::new(#name),
+					vec![<#parent as ::quest::__core::ObjectType>::mapping().clone()]
+				))
+			}
+		}
+
+		#convertible
+
+		impl ::quest::IntoObject for #self_ty {
+			#[inline]
+			fn into_object(self) -> ::quest::Object {
+				::quest::Object::from(self)
+			}
+		}
+
+		impl ::quest::FromObject for #self_ty {
+			fn from_object(obj: &::quest::Object) -> ::quest::__core::Result<Self> {
+				#from_object
+			}
+		}
+	})
+}
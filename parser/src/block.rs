@@ -1,5 +1,6 @@
 use quest_core::impl_object_type;
-use quest_core::{Object, Args, Binding};
+use quest_core::{Object, Args, Binding, Literal};
+use quest_core::types::SourceLocation;
 
 use crate::Result;
 use crate::token::{Token, ParenType};
@@ -7,9 +8,12 @@ use crate::stream::{Context, Contexted};
 use crate::expression::{Constructable, Expression, PutBack, Executable};
 use std::fmt::{self, Debug, Display, Formatter};
 
+/// A single line within a [`Block`].
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum Line {
+pub enum Line {
+	/// A line with just one expression.
 	Single(Expression),
+	/// A line with comma-separated expressions, which is executed into a `List`.
 	Multiple(Vec<Expression>)
 }
 
@@ -174,6 +178,20 @@ impl Block {
 		self.paren_type
 	}
 
+	/// The lines within the block.
+	#[must_use]
+	#[inline]
+	pub fn lines(&self) -> &[Line] {
+		&self.lines
+	}
+
+	/// Where the block was parsed from.
+	#[must_use]
+	#[inline]
+	pub fn context(&self) -> &Context {
+		&self.context
+	}
+
 	#[tracing::instrument(name="Block::run_block")]
 	pub(super) fn run_block(&self) -> quest_core::Result<Option<LineResult>> {
 		if let Some((last, rest)) = self.lines.split_last() {
@@ -245,7 +263,14 @@ impl Constructable for Block {
 					return Ok(Some(block))
 				},
 
-				rparen @ Token::Right(..) => return Err(parse_error!(ctor, UnexpectedToken(rparen))),
+				rparen @ Token::Right(..) => match ctor.next().transpose()? {
+					// if the stream ends right after a mismatched paren, it's our paren that's missing.
+					None => return Err(parse_error!(ctor, MissingClosingParen(paren))),
+					Some(tkn) => {
+						ctor.put_back(Ok(tkn));
+						return Err(parse_error!(ctor, UnexpectedToken(rparen)))
+					}
+				},
 				Token::Endline(_) => 
 					if let Some(curr_line) = curr_line.take() {
 						block.lines.push(curr_line);
@@ -279,7 +304,8 @@ impl Block {
 	pub fn qs_call(this: &Object, args: Args) -> quest_core::Result<Object> {
 		let this_cloned = this.try_downcast::<Self>()?;
 		Binding::new_stackframe(Some(this.clone()), args, move |binding| {
-			binding.as_ref().set_attr_lit("source_location", format!("{:?}", this_cloned.context).into())?;
+			this_cloned.enter_stackframe(binding)?;
+
 			/*match */this_cloned.run_block_to_object()/* {
 				Ok(v) => Ok(v),
 				Err(err @ quest_core::Error::Return { .. }) => Err(err),
@@ -295,7 +321,29 @@ impl Block {
 
 	#[inline]
 	pub fn qs_call_noscope(this: &Object, _: Args) -> quest_core::Result<Object> {
-		this.try_downcast::<Self>()?.run_block_to_object()
+		let this_block = this.try_downcast::<Self>()?;
+		let binding = Binding::instance();
+
+		// `->` functions create their own stackframe for the block before calling this.
+		if binding.parents()?.iter().any(|parent| parent.is_identical(this)) {
+			this_block.enter_stackframe(&binding)?;
+		}
+
+		this_block.run_block_to_object()
+	}
+
+	/// Sets up a stackframe that was created to run this block.
+	///
+	/// The block's location is recorded for stack traces, and if the block was defined in an
+	/// imported module, the module's variables are made visible.
+	pub fn enter_stackframe(&self, binding: &Binding) -> quest_core::Result<()> {
+		binding.set_attr_lit(Literal::SOURCE_LOCATION, SourceLocation::from(&self.context).into())?;
+
+		if let Some(module) = self.context.file.as_deref().and_then(crate::import::module_for) {
+			binding.as_ref().add_parent(module)?;
+		}
+
+		Ok(())
 	}
 }
 
@@ -16,6 +16,7 @@ pub enum ErrorType {
 	Message(&'static str),
 	MessagedString(String),
 	ExpectedExpression,
+	UnexpectedEndOfInput,
 	MissingClosingParen(ParenType),
 
 	// StreamError(std::io::Error),
@@ -25,7 +26,8 @@ pub enum ErrorType {
 
 #[derive(Debug)]
 pub struct Error {
-	context: Context,
+	// boxed so that results containing errors aren't too large.
+	context: Box<Context>,
 	r#type: ErrorType
 }
 
@@ -34,7 +36,19 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 impl Error {
 	pub fn new(context: Context, r#type: ErrorType) -> Self {
-		Error { context, r#type }
+		Error { context: Box::new(context), r#type }
+	}
+
+	/// Get the kind of error that occurred.
+	#[must_use]
+	pub fn error_type(&self) -> &ErrorType {
+		&self.r#type
+	}
+}
+
+impl crate::stream::Contexted for Error {
+	fn context(&self) -> &Context {
+		&self.context
 	}
 }
 
@@ -42,7 +56,7 @@ impl Display for Error {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		const TAB_REPLACEMENT: &str = "  ";
 
-		let Context { ref file, lineno, mut column, ref line } = self.context;
+		let Context { ref file, lineno, mut column, ref line } = *self.context;
 		let file = file.as_ref()
 			.map(|x| x.to_string_lossy().to_owned().to_string())
 			.unwrap_or_else(|| "<eval>".to_string());
@@ -81,13 +95,13 @@ impl Display for ErrorType {
 			UnexpectedToken(tkn) => write!(f, "unexpected token `{}`", tkn),
 			MissingClosingParen(paren) => write!(f, "missing closing paren `{}`", paren.right()),
 			ExpectedExpression => write!(f, "expected an expression"),
+			UnexpectedEndOfInput => write!(f, "expected an expression, but the input ended"),
 			Message(msg) => write!(f, "{}", msg),
 			MessagedString(msg) => write!(f, "{}", msg),
 		}
 	}
 }
 
-
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
 		match self.r#type {
@@ -99,6 +113,35 @@ impl std::error::Error for Error {
 	}
 }
 
+impl Error {
+	/// Checks to see if this error was caused by the input ending too early, such as an unclosed
+	/// paren or quote, or a trailing operator. More input could make this error go away.
+	#[must_use]
+	pub fn is_incomplete_input(&self) -> bool {
+		matches!(self.r#type,
+			ErrorType::UnterminatedQuote
+				| ErrorType::UnterminatedBlockComment
+				| ErrorType::UnexpectedEndOfInput
+				| ErrorType::MissingClosingParen(_))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Expression, stream::{BufStream, Stream}};
 
+	fn parse(input: &str) -> crate::Result<Expression> {
+		Expression::parse_stream(BufStream::from(input).tokens())
+	}
 
+	#[test]
+	fn incomplete_input() {
+		assert!(parse("(1 +").unwrap_err().is_incomplete_input());
+		assert!(parse("1 +").unwrap_err().is_incomplete_input());
+		assert!(parse("x = -").unwrap_err().is_incomplete_input());
+		assert!(parse("(1").unwrap_err().is_incomplete_input());
 
+		assert!(!parse("(1 + )").unwrap_err().is_incomplete_input());
+		assert!(!parse("1 + ;").unwrap_err().is_incomplete_input());
+	}
+}
@@ -4,10 +4,14 @@ use crate::stream::Contexted;
 use crate::Result;
 use std::fmt::{self, Display, Formatter};
 
+/// The operands of a [`BoundOperator`], aside from the value it's called on.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum OperArgs {
+pub enum OperArgs {
+	/// The operator takes no other operands, eg `!x`.
 	Unary,
+	/// The operator takes one other operand, eg `x + y`.
 	Binary(Expression),
+	/// The operator takes two other operands, eg `x.y = z`.
 	Ternary(Expression, Expression)
 }
 
@@ -43,6 +47,23 @@ impl Display for BoundOperator {
 }
 
 impl BoundOperator {
+	/// The operator itself.
+	#[inline]
+	pub fn oper(&self) -> Operator {
+		self.oper
+	}
+
+	/// The value the operator is called on.
+	#[inline]
+	pub fn this(&self) -> &Expression {
+		&self.this
+	}
+
+	/// The rest of the operands.
+	#[inline]
+	pub fn args(&self) -> &OperArgs {
+		&self.args
+	}
 }
 impl Executable for BoundOperator {
 
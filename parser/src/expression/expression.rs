@@ -133,10 +133,6 @@ impl Expression {
 
 		impl<I: Iterator<Item=Result<Token>>> super::PutBack for WrappedBlock<I> {
 			fn put_back(&mut self, item: Self::Item) {
-				if self.0 == Where::End {
-					self.0 = Where::GivenCode
-				}
-
 				self.1.put_back(item);
 			}
 		}
@@ -153,7 +149,8 @@ impl Expression {
 						self.0 = Where::End;
 						Some(Ok(Token::Right(ParenType::Round)))
 					}),
-					Where::End => None,
+					// only tokens that were put back are left at this point.
+					Where::End => self.1.next(),
 				}
 			}
 		}
@@ -164,7 +161,17 @@ impl Expression {
 			}
 		}
 
-		Self::try_construct(&mut WrappedBlock(Where::Start, Constructor::new(iter)))
+		let mut wrapped = WrappedBlock(Where::Start, Constructor::new(iter));
+
+		Self::try_construct(&mut wrapped).map_err(|err| {
+			// if we'd already reached the implicit closing paren, the expression is only missing
+			// because the input ended.
+			if wrapped.0 == Where::End && matches!(err.error_type(), crate::ErrorType::ExpectedExpression) {
+				crate::Error::new(err.context().clone(), crate::ErrorType::UnexpectedEndOfInput)
+			} else {
+				err
+			}
+		})
 	}
 }
 
@@ -18,5 +18,5 @@ pub trait Constructable {
 }
 
 pub(crate) use constructor::Constructor;
-pub use bound_operator::BoundOperator;
+pub use bound_operator::{BoundOperator, OperArgs};
 pub use expression::Expression;
\ No newline at end of file
@@ -0,0 +1,453 @@
+//! Loading other Quest files as modules.
+//!
+//! Each file is run in its own fresh [`Scope`], which then becomes the module object. Modules are
+//! cached by their canonical path, so importing the same file twice yields the same object.
+//!
+//! The cache and search path are stored on the current [`Kernel`], so interpreters with their own
+//! kernels don't share modules.
+
+use crate::{Expression, Stream};
+use crate::stream::BufStream;
+use quest_core::impl_object_type;
+use quest_core::{Object, Args, Binding, Error, Literal};
+use quest_core::types::{Scope, Text, List, SourceLocation, Kernel};
+use quest_core::sandbox::{self, Capability};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::thread::{self, ThreadId};
+
+/// The environment variable that contains extra directories to search for imports.
+pub const QUEST_PATH: &str = "QUEST_PATH";
+
+/// The extension that's tried if a file can't be found as-is.
+pub const EXTENSION: &str = "qs";
+
+#[derive(Debug, Clone)]
+enum Module {
+	/// The module is currently being run by `thread`.
+	Loading { thread: ThreadId, module: Object },
+	Loaded(Object)
+}
+
+impl Module {
+	fn object(&self) -> &Object {
+		match self {
+			Module::Loading { module, .. } | Module::Loaded(module) => module
+		}
+	}
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+	modules: Mutex<HashMap<PathBuf, Module>>,
+	/// Notified whenever a module is done loading, whether or not it succeeded.
+	done_loading: Condvar,
+	search_path: RwLock<Vec<PathBuf>>
+}
+
+/// The modules that have been imported, along with the directories that are searched for them.
+///
+/// Each kernel has its own, which is created the first time it's needed.
+#[derive(Debug, Clone, Default)]
+pub struct Imports(Arc<Inner>);
+
+impl Imports {
+	const ATTR: Literal = Literal::new("__imports__");
+
+	/// Gets the imports of `kernel`, creating them if it doesn't have any yet.
+	pub fn of(kernel: &Object) -> quest_core::Result<Self> {
+		// Keeps two threads from both creating imports for the same kernel.
+		static CREATE: Mutex<()> = Mutex::new(());
+
+		let _lock = CREATE.lock().expect("imports poisoned");
+
+		// Only the kernel's own imports count, otherwise every kernel would share the builtin one's.
+		if let Some(imports) = kernel.get_own_attr_lit(&Self::ATTR) {
+			return Ok(imports.try_downcast::<Self>()?.clone());
+		}
+
+		let imports = Self::default();
+		kernel.set_attr_lit(Self::ATTR, imports.clone().into())?;
+		Ok(imports)
+	}
+
+	/// Gets the imports of the [current kernel](Kernel::current).
+	#[inline]
+	pub fn current() -> quest_core::Result<Self> {
+		Self::of(&Kernel::current())
+	}
+
+	/// Adds a directory to search for imports in.
+	///
+	/// Directories added here are searched in the order they were added, before any in
+	/// `QUEST_PATH`.
+	pub fn add_search_path(&self, dir: impl Into<PathBuf>) {
+		self.0.search_path.write().expect("search path poisoned").push(dir.into());
+	}
+
+	/// Gets every directory that's searched for imports, excluding the importing file's directory.
+	pub fn search_path(&self) -> Vec<PathBuf> {
+		let mut dirs = self.0.search_path.read().expect("search path poisoned").clone();
+
+		if let Some(quest_path) = std::env::var_os(QUEST_PATH) {
+			dirs.extend(std::env::split_paths(&quest_path));
+		}
+
+		dirs
+	}
+
+	/// Gets the module that was imported from `file`, if any.
+	pub fn module(&self, file: &Path) -> Option<Object> {
+		self.0.modules.lock().expect("modules poisoned")
+			.get(file)
+			.map(|module| module.object().clone())
+	}
+
+	/// Imports the module at the already-resolved `path`, loading it if it hasn't been already.
+	///
+	/// If another thread is loading the module, this waits for it to finish. An error is returned
+	/// if the current thread is already loading it, as that means there's an import cycle.
+	fn import(&self, path: PathBuf) -> quest_core::Result<Object> {
+		let current_thread = thread::current().id();
+		let mut modules = self.0.modules.lock().expect("modules poisoned");
+
+		loop {
+			match modules.get(&path) {
+				Some(Module::Loaded(module)) => return Ok(module.clone()),
+				Some(Module::Loading { thread, .. }) if *thread == current_thread =>
+					return Err(Error::Messaged(format!("import cycle detected with {:?}", path))),
+				Some(Module::Loading { .. }) =>
+					modules = self.0.done_loading.wait(modules).expect("modules poisoned"),
+				None => break
+			}
+		}
+
+		let module = new_module(&path)?;
+		modules.insert(path.clone(), Module::Loading { thread: current_thread, module: module.clone() });
+		drop(modules);
+
+		let result = run_module(&path, &module);
+
+		let mut modules = self.0.modules.lock().expect("modules poisoned");
+		match result {
+			Ok(()) => { modules.insert(path, Module::Loaded(module.clone())); },
+			Err(_) => { modules.remove(&path); }
+		}
+		drop(modules);
+		self.0.done_loading.notify_all();
+
+		result.and(Ok(module))
+	}
+}
+
+impl_object_type!{
+for Imports [(parents quest_core::types::Basic)]:
+}
+
+/// Adds a directory to search for imports in, for the current kernel.
+///
+/// See [`Imports::add_search_path`] for details.
+pub fn add_search_path(dir: impl Into<PathBuf>) -> quest_core::Result<()> {
+	Imports::current().map(|imports| imports.add_search_path(dir))
+}
+
+/// Finds the file that's currently being executed.
+///
+/// Blocks record where they were defined on the stackframes they create, so the nearest block on
+/// the stack is used. The top level of a file isn't within a block, so we fall back to the
+/// `__file__` that's set on modules and the main scope.
+fn importing_file() -> quest_core::Result<Option<PathBuf>> {
+	for binding in Binding::stack() {
+		let location = binding.get_own_attr_lit(&Literal::SOURCE_LOCATION)
+			.and_then(|location| location.downcast::<SourceLocation>().and_then(|loc| loc.file.clone()));
+
+		if let Some(file) = location {
+			return Ok(Some(file));
+		}
+
+		if binding.has_attr_lit("__file__")? {
+			let file = binding.get_attr_lit("__file__")?;
+			let file = file.call_downcast::<Text>()?;
+
+			return Ok(Some(PathBuf::from(file.as_ref())));
+		}
+	}
+
+	Ok(None)
+}
+
+fn candidates(path: &Path) -> impl Iterator<Item=PathBuf> {
+	let with_ext =
+		if path.extension().is_none() {
+			Some(path.with_extension(EXTENSION))
+		} else {
+			None
+		};
+
+	std::iter::once(path.to_path_buf()).chain(with_ext)
+}
+
+/// Resolves `path` to the file it refers to.
+///
+/// Paths starting with `.` are relative to the importing file's directory (or the current
+/// directory if there's no importing file). Other relative paths are checked relative to the
+/// importing file first, followed by each entry in the current kernel's
+/// [search path](Imports::search_path). If the path doesn't exist, it's tried again with a `.qs`
+/// extension.
+pub fn resolve(path: &Path) -> quest_core::Result<PathBuf> {
+	let base = importing_file()?
+		.and_then(|file| file.parent().map(Path::to_path_buf))
+		.unwrap_or_default();
+
+	let is_explicitly_relative = path.starts_with(".") || path.starts_with("..");
+
+	let mut dirs = vec![base];
+	if !path.is_absolute() && !is_explicitly_relative {
+		dirs.extend(Imports::current()?.search_path());
+	}
+
+	dirs.iter()
+		.flat_map(|dir| candidates(&dir.join(path)))
+		.find(|candidate| candidate.is_file())
+		.ok_or_else(|| Error::Messaged(format!("unable to find module {:?}", path)))
+		.and_then(|found| found.canonicalize().map_err(Error::from))
+}
+
+/// Gets the module that was imported from `file` by the current kernel, if any.
+///
+/// Quest looks up variables through the caller's stackframe, so functions defined in a module
+/// wouldn't otherwise be able to see the module's other variables when called from elsewhere.
+pub fn module_for(file: &Path) -> Option<Object> {
+	Imports::current().ok()?.module(file)
+}
+
+fn new_module(path: &Path) -> quest_core::Result<Object> {
+	let module = Object::new(Scope);
+	module.set_attr_lit("__file__", path.display().to_string().into())?;
+	Ok(module)
+}
+
+fn run_module(path: &Path, module: &Object) -> quest_core::Result<()> {
+	let stream = BufStream::try_from(path)?;
+	let expression = Expression::parse_stream(stream.tokens())
+		.map_err(|err| Box::new(err) as Box<_>)?;
+
+	Binding::run_stackframe(module.clone().into(), |_| crate::run(&expression)).and(Ok(()))
+}
+
+/// Runs the file at `path` in a fresh scope, returning the scope.
+///
+/// No caching is performed; generally, [`import`] should be used instead.
+pub fn load(path: &Path) -> quest_core::Result<Object> {
+	let module = new_module(path)?;
+
+	run_module(path, &module).and(Ok(module))
+}
+
+/// Imports the module at `path` into the current kernel, loading it if it hasn't been already.
+///
+/// If another thread is loading the module, this waits for it to finish. An error is returned if
+/// the current thread is already loading it, as that means there's an import cycle.
+pub fn import(path: &Path) -> quest_core::Result<Object> {
+	sandbox::require(Capability::FILESYSTEM, "Kernel::import")?;
+
+	Imports::current()?.import(resolve(path)?)
+}
+
+/// Imports a module, returning the module object.
+///
+/// # Arguments
+///
+/// 1. (required, `@text`) The path to the module.
+///
+/// # Quest Examples
+/// ```quest
+/// frac = import("./frac.qs");
+///
+/// assert(frac.Frac(1, 2).@text() == "1/2");
+/// assert(frac.__id__ == import("./frac").__id__);
+/// ```
+pub fn qs_import(args: Args) -> quest_core::Result<Object> {
+	let path = args.try_arg(0)?.call_downcast::<Text>()?;
+
+	import(Path::new(path.as_ref()))
+}
+
+/// Imports a module and then copies each of its attributes into the current scope.
+///
+/// Attributes that start with `__` aren't copied. The module object is returned.
+///
+/// # Arguments
+///
+/// 1. (required, `@text`) The path to the module.
+///
+/// # Quest Examples
+/// ```quest
+/// require("./frac.qs");
+///
+/// assert(Frac(1, 2).@text() == "1/2");
+/// ```
+pub fn qs_require(args: Args) -> quest_core::Result<Object> {
+	let module = qs_import(args)?;
+	let binding = Binding::instance();
+	let keys = module.call_attr_lit("__keys__", &[&false.into()])?;
+
+	for key in keys.call_downcast::<List>()?.iter() {
+		let is_private = key.downcast::<Text>()
+			.filter(|key| key.as_ref().starts_with("__"))
+			.is_some();
+
+		if !is_private {
+			binding.set_attr(key.clone(), module.get_attr(key)?)?;
+		}
+	}
+
+	Ok(module)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use quest_core::types::{Class, RustClosure, ObjectType};
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::time::Duration;
+
+	/// Runs `func` with a kernel of its own, so tests don't share modules. The kernel has a `ran`
+	/// function, which counts how many times it's been called.
+	fn with_kernel<T>(func: impl FnOnce(&Object, &AtomicUsize) -> T) -> T {
+		quest_core::init();
+		crate::init();
+
+		let ran = Arc::new(AtomicUsize::new(0));
+		let kernel = Object::new_with_parent(Class::new("Kernel"), vec![Kernel::mapping().clone()]);
+		let counter = ran.clone();
+		kernel.set_attr_lit("ran", RustClosure::new(move |_| {
+			counter.fetch_add(1, Ordering::SeqCst);
+			thread::sleep(Duration::from_millis(50));
+			Ok(Object::default())
+		}).into()).unwrap();
+
+		let _kernel = Kernel::enter(kernel.clone());
+		func(&kernel, &ran)
+	}
+
+	fn write(dir: &Path, file: &str, src: &str) -> PathBuf {
+		let path = dir.join(file);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(&path, src).unwrap();
+		path
+	}
+
+	fn value(module: quest_core::Result<Object>) -> String {
+		module.unwrap().get_attr_lit("value").unwrap()
+			.downcast::<Text>().expect("value isn't text")
+			.to_string()
+	}
+
+	#[test]
+	fn candidates_add_extension() {
+		assert_eq!(
+			candidates(Path::new("lib/frac")).collect::<Vec<_>>(),
+			vec![PathBuf::from("lib/frac"), PathBuf::from("lib/frac.qs")]
+		);
+
+		assert_eq!(
+			candidates(Path::new("frac.qs")).collect::<Vec<_>>(),
+			vec![PathBuf::from("frac.qs")]
+		);
+	}
+
+	#[test]
+	fn modules_are_run_once() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = write(dir.path(), "lib.qs", "ran(); value = 'lib';");
+
+		with_kernel(|_, ran| {
+			let first = import(&path).unwrap();
+			let second = import(&path.with_extension("")).unwrap();
+
+			assert_eq!(first.id(), second.id());
+			assert_eq!(ran.load(Ordering::SeqCst), 1);
+		});
+
+		// other kernels have their own modules.
+		with_kernel(|_, ran| {
+			assert_eq!(value(import(&path)), "lib");
+			assert_eq!(ran.load(Ordering::SeqCst), 1);
+		});
+	}
+
+	#[test]
+	fn other_threads_wait_for_modules_to_load() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = write(dir.path(), "lib.qs", "ran();");
+
+		with_kernel(|kernel, ran| {
+			let threads = (0..2).map(|_| {
+				let (kernel, path) = (kernel.clone(), path.clone());
+
+				thread::spawn(move || {
+					let _kernel = Kernel::enter(kernel);
+					import(&path).map(|module| module.id()).map_err(|err| err.to_string())
+				})
+			}).collect::<Vec<_>>();
+
+			let ids = threads.into_iter()
+				.map(|thread| thread.join().unwrap().unwrap())
+				.collect::<Vec<_>>();
+
+			assert_eq!(ids[0], ids[1]);
+			assert_eq!(ran.load(Ordering::SeqCst), 1);
+		});
+	}
+
+	#[test]
+	fn import_cycles_are_detected() {
+		let dir = tempfile::tempdir().unwrap();
+		let a = write(dir.path(), "a.qs", "import('./b');");
+		let b = write(dir.path(), "b.qs", "import('./a');");
+
+		with_kernel(|_, _| {
+			let err = import(&a).unwrap_err().to_string();
+			assert!(err.contains("import cycle detected"), "wrong error: {}", err);
+
+			// neither module is left half-loaded.
+			let imports = Imports::current().unwrap();
+			assert!(imports.module(&a.canonicalize().unwrap()).is_none());
+			assert!(imports.module(&b.canonicalize().unwrap()).is_none());
+		});
+	}
+
+	#[test]
+	fn relative_paths_are_relative_to_the_importing_file() {
+		let dir = tempfile::tempdir().unwrap();
+		let main = write(dir.path(), "main.qs", "value = import('./sub/b').value;");
+		write(dir.path(), "sub/b.qs", "value = import('./c').value;");
+		write(dir.path(), "sub/c.qs", "value = 'sub/c';");
+		write(dir.path(), "c.qs", "value = 'c';");
+
+		with_kernel(|_, _| assert_eq!(value(import(&main)), "sub/c"));
+	}
+
+	#[test]
+	fn search_path_precedence() {
+		let dir = tempfile::tempdir().unwrap();
+		write(dir.path(), "one/lib.qs", "value = 'one';");
+		write(dir.path(), "two/lib.qs", "value = 'two';");
+		let main = write(dir.path(), "main/main.qs", "value = import('lib').value;");
+
+		with_kernel(|_, _| {
+			add_search_path(dir.path().join("one")).unwrap();
+			add_search_path(dir.path().join("two")).unwrap();
+
+			// directories are searched in the order they were added...
+			assert_eq!(value(import(Path::new("lib"))), "one");
+
+			// ...but only after the importing file's directory.
+			write(dir.path(), "main/lib.qs", "value = 'main';");
+			assert_eq!(value(import(&main)), "main");
+		});
+	}
+}
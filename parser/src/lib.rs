@@ -3,7 +3,6 @@
 /// Setup the quest parser. This should be run before anything within `quest_parser` is used.
 pub fn init() {
 	use quest_core::{Binding, types::{ObjectType, RustFn, Text, Kernel}};
-	use crate::expression::Executable;
 
 	use std::sync::Once;
 
@@ -21,14 +20,46 @@ pub fn init() {
 					Binding::set_binding(binding.clone());
 				}
 
-				Expression::parse_stream(stream::BufStream::from(this.to_string()).tokens())
-					.map_err(|err| Box::new(err) as Box<_>)?
-					.execute()
+				let expression = Expression::parse_stream(stream::BufStream::from(this.to_string()).tokens())
+					.map_err(|err| Box::new(err) as Box<_>)?;
+
+				run(&expression)
 			})
 		})).expect("couldn't define `eval`");
+
+		Kernel::mapping().set_value_lit("import", RustFn::function("Kernel::import", import::qs_import))
+			.expect("couldn't define `import`");
+
+		Kernel::mapping().set_value_lit("require", RustFn::function("Kernel::require", import::qs_require))
+			.expect("couldn't define `require`");
 	});
 }
 
+/// A way of running parsed code.
+pub type Engine = fn(&Expression) -> quest_core::Result<quest_core::Object>;
+
+lazy_static::lazy_static! {
+	static ref ENGINE: std::sync::RwLock<Engine> = std::sync::RwLock::new(|expr| {
+		use crate::expression::Executable;
+
+		expr.execute()
+	});
+}
+
+/// Changes how code that's parsed at runtime, such as by `Text::eval` and `import`, is run.
+///
+/// By default, the syntax tree is walked directly.
+pub fn set_engine(engine: Engine) {
+	*ENGINE.write().expect("engine poisoned") = engine;
+}
+
+/// Runs `expr` with the current [`Engine`].
+pub fn run(expr: &Expression) -> quest_core::Result<quest_core::Object> {
+	let engine = *ENGINE.read().expect("engine poisoned");
+
+	engine(expr)
+}
+
 #[macro_use]
 mod macros;
 mod error;
@@ -36,6 +67,7 @@ pub mod expression;
 pub mod token;
 pub mod stream;
 pub mod block;
+pub mod import;
 
 // TODO: change public exports to more minimal.
 pub use block::Block;
@@ -1,3 +1,4 @@
+use quest_core::types::SourceLocation;
 use std::path::PathBuf;
 
 /// Types implementing this trait should be able to supply a "current execution context".
@@ -29,6 +30,17 @@ impl Context {
 	}
 }
 
+impl From<&Context> for SourceLocation {
+	fn from(context: &Context) -> Self {
+		Self {
+			file: context.file.clone(),
+			lineno: context.lineno,
+			column: context.column,
+			line: context.line.clone()
+		}
+	}
+}
+
 impl<T: Into<PathBuf>> From<T> for Context {
 	fn from(file: T) -> Self {
 		Self::new(Some(file.into()))
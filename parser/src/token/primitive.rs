@@ -14,6 +14,9 @@ pub mod regex;
 /// A text literal.
 pub use text::Text;
 
+/// A text literal with `#{...}`s in it.
+pub use text::Interpolation;
+
 /// A number literal.
 pub use number::Number;
 
@@ -39,6 +42,10 @@ pub enum Primitive {
 	///
 	/// See [`Text`](#) for more information on parsing.
 	Text(Text),
+	/// A double-quoted text literal with embedded expressions.
+	///
+	/// See [`Interpolation`](#) for more information.
+	Interpolation(Interpolation),
 	/// A literal number.
 	///
 	/// See [`Number`](#) for more information on parsing.
@@ -56,6 +63,7 @@ impl Display for Primitive {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			Primitive::Text(t) => Display::fmt(&t, f),
+			Primitive::Interpolation(i) => Display::fmt(&i, f),
 			Primitive::Number(n) => Display::fmt(&n, f),
 			Primitive::Variable(v) => Display::fmt(&v, f),
 			Primitive::Regex(r) => Display::fmt(&r, f),
@@ -68,6 +76,7 @@ impl Executable for Primitive {
 	fn execute(&self) -> quest_core::Result<quest_core::Object> {
 		match self {
 			Primitive::Text(t) => t.execute(),
+			Primitive::Interpolation(i) => i.execute(),
 			Primitive::Number(n) => n.execute(),
 			Primitive::Variable(v) => v.execute(),
 			Primitive::Regex(r) => r.execute(),
@@ -84,17 +93,18 @@ impl From<Primitive> for Token {
 
 impl Tokenizable for Primitive {
 	fn try_tokenize<S: Stream>(stream: &mut S) -> Result<Option<Self>> {
-		match Variable::try_tokenize(stream)?.map(Primitive::Variable) {
+		// this has to come before variables, as raw text starts with an `r`.
+		match text::try_tokenize(stream)? {
 			None => { /* do nothing, parse the next one */ },
 			other => return Ok(other)
 		}
 
-		match Number::try_tokenize(stream)?.map(Primitive::Number) {
+		match Variable::try_tokenize(stream)?.map(Primitive::Variable) {
 			None => { /* do nothing, parse the next one */ },
 			other => return Ok(other)
 		}
 
-		match Text::try_tokenize(stream)?.map(Primitive::Text) {
+		match Number::try_tokenize(stream)?.map(Primitive::Number) {
 			None => { /* do nothing, parse the next one */ },
 			other => return Ok(other)
 		}
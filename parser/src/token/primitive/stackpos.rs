@@ -14,6 +14,13 @@ impl Display for StackPos {
 	}
 }
 
+impl From<StackPos> for isize {
+	#[inline]
+	fn from(pos: StackPos) -> Self {
+		pos.0
+	}
+}
+
 impl Executable for StackPos {
 	fn execute(&self) -> quest_core::Result<quest_core::Object> {
 		let stack = Binding::stack();
@@ -1,9 +1,12 @@
 //! Parsing a literal text
 
 use crate::{Result, Stream};
-use crate::expression::Executable;
-use crate::token::{Operator, Tokenizable, primitive::Variable};
+use crate::block::Line;
+use crate::expression::{Executable, Expression};
+use crate::stream::{Context, Contexted};
+use crate::token::{Operator, ParenType, Primitive, Token, Tokenizable, primitive::Variable};
 use quest_core::Object;
+use std::fmt::{self, Display, Formatter};
 
 /// A literal text is actually just a `quest_core::Text`.
 pub use quest_core::types::Text;
@@ -15,38 +18,366 @@ impl Executable for Text {
 	}
 }
 
-fn try_tokenize_quoted<S: Stream>(stream: &mut S, quote: char) -> Result<Option<Text>> {
+/// A piece of an [`Interpolation`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Segment {
+	/// Text that's included as-is.
+	Text(String),
+	/// An expression from within a `#{...}`, whose `@text` is included.
+	Expression(Expression)
+}
+
+/// A double-quoted text literal containing `#{...}`s, such as `"1 + 2 = #{1 + 2}"`.
+///
+/// The expressions are evaluated in the current stackframe each time the literal is.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Interpolation(Vec<Segment>);
+
+impl Interpolation {
+	/// The literal's pieces, in order.
+	pub fn segments(&self) -> &[Segment] {
+		&self.0
+	}
+}
+
+impl Display for Interpolation {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "\"")?;
+
+		for segment in &self.0 {
+			match segment {
+				Segment::Text(text) => {
+					let escaped = format!("{:?}", text).replace("#{", "\\#{");
+					write!(f, "{}", &escaped[1..escaped.len() - 1])?;
+				},
+				Segment::Expression(expr) => write!(f, "#{{{}}}", expr)?
+			}
+		}
+
+		write!(f, "\"")
+	}
+}
+
+impl Executable for Interpolation {
+	fn execute(&self) -> quest_core::Result<Object> {
+		let mut text = String::new();
+
+		for segment in &self.0 {
+			match segment {
+				Segment::Text(literal) => text.push_str(literal),
+				Segment::Expression(expr) => text.push_str(expr.execute()?.call_downcast::<Text>()?.as_ref())
+			}
+		}
+
+		Ok(text.into())
+	}
+}
+
+/// The tokens of an expression within a `#{...}`.
+struct Embedded {
+	tokens: std::vec::IntoIter<Token>,
+	context: Context
+}
+
+impl Iterator for Embedded {
+	type Item = Result<Token>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.tokens.next().map(Ok)
+	}
+}
+
+impl Contexted for Embedded {
+	fn context(&self) -> &Context {
+		&self.context
+	}
+}
+
+/// Parses the expression after a `#{`, up to and including the matching `}`.
+fn try_tokenize_embedded<S: Stream>(stream: &mut S) -> Result<Expression> {
+	let context = stream.context().clone();
+	let mut tokens = vec![];
+	let mut depth = 0usize;
+
+	loop {
+		match Token::try_parse(stream)? {
+			Some(Token::Right(ParenType::Curly)) if depth == 0 => break,
+			Some(token) => {
+				match token {
+					Token::Left(ParenType::Curly) => depth += 1,
+					Token::Right(ParenType::Curly) => depth -= 1,
+					_ => {}
+				}
+
+				tokens.push(token);
+			},
+			None => return Err(parse_error!(context=context, UnterminatedQuote))
+		}
+	}
+
+	if tokens.is_empty() {
+		return Err(parse_error!(context=context, ExpectedExpression));
+	}
+
+	// `parse_stream` wraps everything in parens, which aren't needed for a single expression.
+	match Expression::parse_stream(Embedded { tokens: tokens.into_iter(), context })? {
+		Expression::Block(block) if block.lines().len() == 1 => match &block.lines()[0] {
+			Line::Single(expr) => Ok(expr.clone()),
+			Line::Multiple(_) => Ok(Expression::Block(block))
+		},
+		other => Ok(other)
+	}
+}
+
+/// An error for the escape sequence character that was just read.
+fn bad_escape<S: Stream>(stream: &S, chr: char) -> crate::Error {
+	let mut context = stream.context().clone();
+	context.column -= 1;
+	parse_error!(context=context, BadEscapeChar(chr))
+}
+
+fn hex_digits<S: Stream>(stream: &mut S, count: usize, starting_context: &Context) -> Result<u32> {
+	let mut value = 0;
+
+	for _ in 0..count {
+		match stream.next().transpose()? {
+			Some(chr) => value = value * 16 + chr.to_digit(16).ok_or_else(|| bad_escape(stream, chr))?,
+			None => return Err(parse_error!(context=starting_context.clone(), UnterminatedQuote))
+		}
+	}
+
+	Ok(value)
+}
+
+/// Parses the `{...}` of a `\u{...}`, which contains between one and six hex digits.
+fn braced_hex_digits<S: Stream>(stream: &mut S, starting_context: &Context) -> Result<u32> {
+	let mut value = 0;
+	let mut digits = 0;
+
+	loop {
+		match stream.next().transpose()? {
+			Some('}') if digits != 0 => return Ok(value),
+			Some(chr) if digits < 6 && chr.is_ascii_hexdigit() => {
+				value = value * 16 + chr.to_digit(16).unwrap();
+				digits += 1;
+			},
+			Some(chr) => return Err(bad_escape(stream, chr)),
+			None => return Err(parse_error!(context=starting_context.clone(), UnterminatedQuote))
+		}
+	}
+}
+
+/// Parses a `\u` escape, which is either `\u{...}` or four hex digits.
+///
+/// A high surrogate in the four digit form must be followed by a `\u` escape for the low
+/// surrogate, like in JSON.
+fn unicode_escape<S: Stream>(stream: &mut S, letter: char, starting_context: &Context) -> Result<char> {
+	let mut letter_context = stream.context().clone();
+	letter_context.column -= 1;
+
+	let code =
+		if stream.next_if_starts_with("{")? {
+			braced_hex_digits(stream, starting_context)?
+		} else {
+			let high = hex_digits(stream, 4, starting_context)?;
+
+			if (0xd800..=0xdbff).contains(&high)
+				&& (stream.next_if_starts_with("\\u")? || stream.next_if_starts_with("\\U")?)
+			{
+				match hex_digits(stream, 4, starting_context)? {
+					low @ 0xdc00..=0xdfff => 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00),
+					_ => high
+				}
+			} else {
+				high
+			}
+		};
+
+	std::char::from_u32(code).ok_or_else(|| parse_error!(context=letter_context, BadEscapeChar(letter)))
+}
+
+/// Parses the escape sequence after a `\` in a double-quoted text, adding what it represents.
+fn tokenize_escape<S: Stream>(stream: &mut S, text: &mut String, starting_context: &Context) -> Result<()> {
+	match stream.next().transpose()? {
+		Some(chr @ '\\')
+			| Some(chr @ '\'')
+			| Some(chr @ '\"')
+			| Some(chr @ '#') => text.push(chr),
+		Some('n') => text.push('\n'),
+		Some('\n') => { /* do nothing */ },
+		Some('\r') => { stream.next_if_starts_with("\n")?; },
+		Some('t') => text.push('\t'),
+		Some('r') => text.push('\r'),
+		Some('0') => text.push('\0'),
+		Some('a') => text.push('\x07'),
+		Some('b') => text.push('\x08'),
+		Some('e') => text.push('\x1b'),
+		// `\xHH` is the codepoint `U+00HH`, so `\xe9` is `é` and not a lone byte.
+		Some('x') | Some('X') => text.push(char::from(hex_digits(stream, 2, starting_context)? as u8)),
+		Some(letter @ 'u') | Some(letter @ 'U') => text.push(unicode_escape(stream, letter, starting_context)?),
+		Some(chr) => return Err(bad_escape(stream, chr)),
+		None => return Err(parse_error!(context=starting_context.clone(), UnterminatedQuote)),
+	}
+
+	Ok(())
+}
+
+/// The different kinds of quoted text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quote {
+	/// `'...'`, which only has `\\` and `\'` escapes.
+	Single,
+	/// `"..."`, which has escapes and `#{...}`s.
+	Double,
+	/// A `"""` on its own line, through a line with just a `"""`.
+	///
+	/// These work like double quotes, except quotes don't need to be escaped. The indentation of
+	/// the closing `"""` is removed from every line, as is the newline before it.
+	Heredoc
+}
+
+/// A piece of a quoted text, before heredoc indentation has been removed.
+#[derive(Debug)]
+enum Piece {
+	Segment(Segment),
+	/// Leading whitespace on a line in a heredoc, and whether the line is otherwise empty.
+	Indent(String, bool, Context)
+}
+
+/// Strips the heredoc indentation from `pieces`, and then combines them into a literal.
+fn finish_quoted(pieces: Vec<Piece>, indent: &str) -> Result<Primitive> {
+	let mut segments = Vec::<Segment>::new();
+
+	for piece in pieces {
+		let text =
+			match piece {
+				Piece::Segment(Segment::Text(text)) => text,
+				Piece::Segment(expr) => { segments.push(expr); continue },
+				Piece::Indent(whitespace, _, _) if whitespace.starts_with(indent) =>
+					whitespace[indent.len()..].to_string(),
+				Piece::Indent(_, true, _) => continue,
+				Piece::Indent(_, false, context) =>
+					return Err(parse_error!(context=context, Message("line is indented less than the closing `\"\"\"`")))
+			};
+
+		match segments.last_mut() {
+			Some(Segment::Text(prev)) => prev.push_str(&text),
+			_ => segments.push(Segment::Text(text))
+		}
+	}
+
+	match segments.as_slice() {
+		[] => Ok(Primitive::Text(Text::default())),
+		[Segment::Text(text)] => Ok(Primitive::Text(text.as_str().into())),
+		_ => {
+			segments.retain(|segment| !matches!(segment, Segment::Text(text) if text.is_empty()));
+			Ok(Primitive::Interpolation(Interpolation(segments)))
+		}
+	}
+}
+
+fn try_tokenize_quoted<S: Stream>(stream: &mut S, quote: Quote) -> Result<Option<Primitive>> {
 	let mut text = String::new();
+	let mut pieces = vec![];
+	let mut at_line_start = quote == Quote::Heredoc;
 
 	let starting_context = stream.context().clone();
 
-	while let Some(chr) = stream.next().transpose()? {
+	macro_rules! flush {
+		() => {
+			if !text.is_empty() {
+				pieces.push(Piece::Segment(Segment::Text(std::mem::take(&mut text))));
+			}
+		};
+	}
+
+	loop {
+		if at_line_start {
+			at_line_start = false;
+
+			let mut whitespace = String::new();
+			let line_context = stream.context().clone();
+
+			while let Some(chr) = stream.next().transpose()? {
+				if chr == ' ' || chr == '\t' {
+					whitespace.push(chr);
+				} else {
+					unseek_char!(stream; chr);
+					break;
+				}
+			}
+
+			if stream.next_if_starts_with("\"\"\"")? {
+				flush!();
+
+				// the newline before the closing quotes isn't a part of the text.
+				if let Some(Piece::Segment(Segment::Text(last))) = pieces.last_mut() {
+					if last.ends_with('\n') {
+						last.pop();
+
+						if last.ends_with('\r') {
+							last.pop();
+						}
+					}
+				}
+
+				return finish_quoted(pieces, &whitespace).map(Some);
+			}
+
+			let is_blank = stream.starts_with("\n")? || stream.starts_with("\r\n")?;
+
+			flush!();
+			pieces.push(Piece::Indent(whitespace, is_blank, line_context));
+		}
+
+		let chr =
+			match stream.next().transpose()? {
+				Some(chr) => chr,
+				None => return Err(parse_error!(context=starting_context, UnterminatedQuote))
+			};
+
 		match chr {
-			'\\' if quote == '"' => match stream.next().transpose()? {
-				Some(chr @ '\\')
-					| Some(chr @ '\'')
-					| Some(chr @ '\"') => text.push(chr),
-				Some('n') => text.push('\n'),
-				Some('\n') => { /* do nothing */ },
-				Some('\r') => { stream.next_if_starts_with("\n")?; },
-				Some('t') => text.push('\t'),
-				Some('r') => text.push('\r'),
-				Some('0') => text.push('\0'),
-				Some('u') | Some('U')
-					| Some('x') | Some('X') => todo!("additional string parsing"),
-				Some(chr) => return Err(parse_error!(stream, BadEscapeChar(chr))),
-				None      => return Err(parse_error!(context=starting_context, UnterminatedQuote)),
+			'#' if quote != Quote::Single && stream.next_if_starts_with("{")? => {
+				flush!();
+				pieces.push(Piece::Segment(Segment::Expression(try_tokenize_embedded(stream)?)));
 			},
+			'\\' if quote != Quote::Single => tokenize_escape(stream, &mut text, &starting_context)?,
 			'\\' => match stream.next().transpose()? {
 				Some(chr @ '\\') | Some(chr @ '\'') => text.push(chr),
 				Some(other) => { text.push('\\'); text.push(other); },
 				None => return Err(parse_error!(context=starting_context, UnterminatedQuote))
 			},
-			chr if chr == quote => return Ok(Some(text.into())),
+			'\n' if quote == Quote::Heredoc => {
+				text.push(chr);
+				at_line_start = true;
+			},
+			'\'' if quote == Quote::Single => break,
+			'"' if quote == Quote::Double => break,
 			chr => text.push(chr)
 		}
 	}
 
+	flush!();
+	finish_quoted(pieces, "").map(Some)
+}
+
+/// Parses a raw text after its `r`, such as `r"C:\path"` or `r#"say "hi""#`.
+///
+/// Nothing within raw text is escaped, and it's only ended by a `"` followed by as many `#`s as
+/// came before the opening quote.
+fn try_tokenize_raw<S: Stream>(stream: &mut S, hashes: usize) -> Result<Option<Primitive>> {
+	let starting_context = stream.context().clone();
+	let closing = "#".repeat(hashes);
+	let mut text = String::new();
+
+	while let Some(chr) = stream.next().transpose()? {
+		if chr == '"' && stream.next_if_starts_with(&closing)? {
+			return Ok(Some(Primitive::Text(text.into())));
+		}
+
+		text.push(chr);
+	}
+
 	Err(parse_error!(context=starting_context, UnterminatedQuote))
 }
 
@@ -78,16 +409,135 @@ fn try_tokenize_dollar_sign<S: Stream>(stream: &mut S) -> Result<Option<Text>> {
 	}
 }
 
-impl Tokenizable for Text {
-	fn try_tokenize<S: Stream>(stream: &mut S) -> Result<Option<Self>> {
-		match stream.next().transpose()? {
-			Some('$') => try_tokenize_dollar_sign(stream),
-			Some(quote @ '\"') | Some(quote @ '\'') => try_tokenize_quoted(stream, quote),
-			Some(chr) => {
-				unseek_char!(stream; chr);
-				Ok(None)
-			},
-			None => Ok(None)
+/// Parses a text literal, which is an [`Interpolation`] if it contains any `#{...}`s.
+pub(super) fn try_tokenize<S: Stream>(stream: &mut S) -> Result<Option<Primitive>> {
+	match stream.next().transpose()? {
+		Some('$') => Ok(try_tokenize_dollar_sign(stream)?.map(Primitive::Text)),
+		Some('\'') => try_tokenize_quoted(stream, Quote::Single),
+		Some('"') if stream.next_if_starts_with("\"\"\n")? || stream.next_if_starts_with("\"\"\r\n")? =>
+			try_tokenize_quoted(stream, Quote::Heredoc),
+		Some('"') => try_tokenize_quoted(stream, Quote::Double),
+		Some('r') => {
+			let mut hashes = 0;
+
+			while stream.next_if_starts_with("#")? {
+				hashes += 1;
+			}
+
+			if stream.next_if_starts_with("\"")? {
+				return try_tokenize_raw(stream, hashes);
+			}
+
+			std::io::Seek::seek(stream, std::io::SeekFrom::Current(-(hashes as i64 + 1)))
+				.map_err(|err| parse_error!(stream, CantReadStream(err)))?;
+			Ok(None)
+		},
+		Some(chr) => {
+			unseek_char!(stream; chr);
+			Ok(None)
+		},
+		None => Ok(None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::stream::BufStream;
+
+	fn parse(src: &str) -> Result<Option<Primitive>> {
+		try_tokenize(&mut BufStream::from(src))
+	}
+
+	#[test]
+	fn plain_text() {
+		assert_eq!(parse(r##""a#b{c}""##).unwrap(), Some(Primitive::Text("a#b{c}".into())));
+		assert_eq!(parse(r##""\#{x}""##).unwrap(), Some(Primitive::Text("#{x}".into())));
+		assert_eq!(parse("'#{x}'").unwrap(), Some(Primitive::Text("#{x}".into())));
+	}
+
+	#[test]
+	fn interpolation() {
+		let interp = match parse(r##""a#{ {1}() }b#{"#{2}"}""##).unwrap() {
+			Some(Primitive::Interpolation(interp)) => interp,
+			other => panic!("expected an interpolation, got {:?}", other)
+		};
+
+		assert_eq!(interp.segments().len(), 4);
+		assert_eq!(interp.segments()[0], Segment::Text("a".into()));
+		assert_eq!(interp.segments()[2], Segment::Text("b".into()));
+		assert!(matches!(interp.segments()[1], Segment::Expression(Expression::FunctionCall(..))));
+		assert_eq!(interp.to_string(), r##""a#{{ 1 }()}b#{"#{2}"}""##);
+	}
+
+	fn text(src: &str) -> String {
+		match parse(src) {
+			Ok(Some(Primitive::Text(text))) => text.to_string(),
+			other => panic!("expected text from {:?}, got {:?}", src, other)
 		}
 	}
+
+	fn bad_escape_column(src: &str) -> (char, usize) {
+		let err = parse(src).unwrap_err();
+
+		match err.error_type() {
+			crate::ErrorType::BadEscapeChar(chr) => (*chr, err.context().column),
+			other => panic!("expected a bad escape from {:?}, got {:?}", src, other)
+		}
+	}
+
+	#[test]
+	fn escapes() {
+		assert_eq!(text(r#""\a\b\e\t\0""#), "\x07\x08\x1b\t\0");
+		assert_eq!(text(r#""\x41\X7e\xe9""#), "A~é");
+		assert_eq!(text(r#""\u00e9\U2603\u{1F600}\u{41}""#), "é☃😀A");
+		assert_eq!(text(r#""\ud83d\ude00""#), "😀");
+	}
+
+	#[test]
+	fn bad_escapes() {
+		assert_eq!(bad_escape_column(r#""ab\q""#), ('q', 4));
+		assert_eq!(bad_escape_column(r#""\x4g""#), ('g', 4));
+		assert_eq!(bad_escape_column(r#""\u{}""#), ('}', 4));
+		assert_eq!(bad_escape_column(r#""\u{1234567}""#), ('7', 10));
+		assert_eq!(bad_escape_column(r#""\u{110000}""#), ('u', 2));
+		assert_eq!(bad_escape_column(r#""x\ud83d""#), ('u', 3));
+		assert_eq!(bad_escape_column(r#""\ude00""#), ('u', 2));
+		assert!(parse(r#""\x4"#).unwrap_err().is_incomplete_input());
+	}
+
+	#[test]
+	fn raw() {
+		assert_eq!(text(r#"r"C:\new\#{x}""#), r"C:\new\#{x}");
+		assert_eq!(text(r###"r#"say "hi""#"###), r#"say "hi""#);
+		assert_eq!(text("r##\"a\"#b\n\"##"), "a\"#b\n");
+		assert!(parse("r#\"a\"").is_err());
+
+		let mut stream = BufStream::from("r#x");
+		assert!(try_tokenize(&mut stream).unwrap().is_none());
+		assert_eq!(stream.context().column, 0);
+	}
+
+	#[test]
+	fn heredoc() {
+		assert_eq!(text("\"\"\"\n\t\tline \"one\"\n\n\t\t  two\\t\n\t\t\"\"\"\n"), "line \"one\"\n\n  two\t");
+		assert_eq!(text("\"\"\"\n\"\"\""), "");
+		assert_eq!(text("\"\"\"\r\n  a\r\n  \"\"\""), "a");
+
+		match parse("\"\"\"\n  a #{1}\n  \"\"\"").unwrap() {
+			Some(Primitive::Interpolation(interp)) => assert_eq!(interp.segments()[0], Segment::Text("a ".into())),
+			other => panic!("expected an interpolation, got {:?}", other)
+		}
+
+		assert!(parse("\"\"\"\n a\n  \"\"\"").is_err());
+		assert!(parse("\"\"\"\n a\n").unwrap_err().is_incomplete_input());
+		assert_eq!(text("\"\"\"x\""), "");
+	}
+
+	#[test]
+	fn unterminated() {
+		assert!(parse(r##""#{1 + 2"##).is_err());
+		assert!(parse(r##""#{}""##).is_err());
+		assert!(parse(r##""#{1}"##).is_err());
+	}
 }
@@ -0,0 +1,171 @@
+//! Converting between Rust values and Quest [`Object`]s.
+
+use quest_core::Object;
+use quest_core::types::{Boolean, Number, Text, List, Map, Null, Bytes};
+use std::convert::TryFrom;
+
+/// Converting a Rust value into an [`Object`].
+pub trait IntoObject {
+	/// Creates an object from `self`.
+	fn into_object(self) -> Object;
+}
+
+/// Converting an [`Object`] into a Rust value.
+///
+/// Conversions go through the object's conversion methods, so anything with a `@num` can be
+/// converted to an `i64`, anything with a `@text` to a `String`, and so on.
+pub trait FromObject: Sized {
+	/// Creates a value from `obj`, raising an error if it can't be converted.
+	fn from_object(obj: &Object) -> quest_core::Result<Self>;
+}
+
+impl IntoObject for Object {
+	#[inline]
+	fn into_object(self) -> Object {
+		self
+	}
+}
+
+impl IntoObject for &Object {
+	#[inline]
+	fn into_object(self) -> Object {
+		self.clone()
+	}
+}
+
+impl FromObject for Object {
+	#[inline]
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		Ok(obj.clone())
+	}
+}
+
+impl IntoObject for () {
+	#[inline]
+	fn into_object(self) -> Object {
+		Null.into()
+	}
+}
+
+impl FromObject for () {
+	#[inline]
+	fn from_object(_: &Object) -> quest_core::Result<Self> {
+		Ok(())
+	}
+}
+
+macro_rules! impl_via_into {
+	($($ty:ty),*) => {
+		$(
+			impl IntoObject for $ty {
+				#[inline]
+				fn into_object(self) -> Object {
+					self.into()
+				}
+			}
+		)*
+	};
+}
+
+impl_via_into!(bool, &str, String, char, f32, f64, Null, Boolean, Number, Text, List, Map, Bytes);
+
+macro_rules! impl_integers {
+	($($int:ty)*) => {
+		$(
+			impl IntoObject for $int {
+				#[inline]
+				fn into_object(self) -> Object {
+					self.into()
+				}
+			}
+
+			impl FromObject for $int {
+				fn from_object(obj: &Object) -> quest_core::Result<Self> {
+					let num = obj.call_downcast::<Number>()?.clone();
+					<$int>::try_from(num).map_err(From::from)
+				}
+			}
+		)*
+	};
+}
+
+impl_integers!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Types that are converted by cloning the result of [`Object::call_downcast`].
+macro_rules! impl_via_downcast {
+	($($ty:ty),*) => {
+		$(
+			impl FromObject for $ty {
+				fn from_object(obj: &Object) -> quest_core::Result<Self> {
+					Ok(obj.call_downcast::<$ty>()?.clone())
+				}
+			}
+		)*
+	};
+}
+
+impl_via_downcast!(Boolean, Number, Text, List, Map, Bytes);
+
+impl FromObject for Null {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		Ok(*obj.try_downcast::<Null>()?)
+	}
+}
+
+impl FromObject for bool {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		Ok(obj.call_downcast::<Boolean>()?.into_inner())
+	}
+}
+
+impl FromObject for f64 {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		Ok(obj.call_downcast::<Number>()?.clone().into())
+	}
+}
+
+impl FromObject for f32 {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		f64::from_object(obj).map(|float| float as f32)
+	}
+}
+
+impl FromObject for String {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		Ok(obj.call_downcast::<Text>()?.to_string())
+	}
+}
+
+/// Lists are converted element-by-element.
+impl<T: IntoObject> IntoObject for Vec<T> {
+	fn into_object(self) -> Object {
+		self.into_iter().map(IntoObject::into_object).collect::<Vec<_>>().into()
+	}
+}
+
+/// Anything with a `@list` can be converted, as long as each element can be.
+impl<T: FromObject> FromObject for Vec<T> {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		let list = obj.call_downcast::<List>()?.iter().cloned().collect::<Vec<_>>();
+
+		list.iter().map(T::from_object).collect()
+	}
+}
+
+/// `None` is converted to `null`.
+impl<T: IntoObject> IntoObject for Option<T> {
+	fn into_object(self) -> Object {
+		self.map_or_else(|| Null.into(), IntoObject::into_object)
+	}
+}
+
+/// `null` is converted to `None`, and everything else is converted to a `T`.
+impl<T: FromObject> FromObject for Option<T> {
+	fn from_object(obj: &Object) -> quest_core::Result<Self> {
+		if obj.is_a::<Null>() {
+			Ok(None)
+		} else {
+			T::from_object(obj).map(Some)
+		}
+	}
+}
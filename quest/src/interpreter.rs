@@ -0,0 +1,441 @@
+use crate::{Result, FromObject, IntoObject};
+use quest_core::{Object, Args, Binding, Literal, ObjectType};
+use quest_core::types::{Scope, RustClosure, Class, Text, Kernel};
+use quest_core::sandbox::Sandbox;
+use quest_parser::{Expression, Stream};
+use quest_parser::stream::BufStream;
+use quest_parser::import::Imports;
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/// A Quest interpreter, with its own global scope.
+///
+/// Code run with [`eval`](Self::eval) and [`eval_file`](Self::eval_file) runs directly in the
+/// interpreter's global scope, so variables assigned by one call are visible to the next. Each
+/// interpreter's globals are separate from every other's, even on the same thread.
+///
+/// Each interpreter also has its own `Kernel`, which inherits from the builtin one, so assigning
+/// attributes of `Kernel` only affects that interpreter. The classes of builtin types, such as
+/// `Number`, are the same for every interpreter.
+///
+/// If the interpreter has a [`Sandbox`], it's entered every time the interpreter runs code, and
+/// its limits start over each time.
+#[derive(Debug, Clone)]
+pub struct Interpreter {
+	globals: Object,
+	kernel: Object,
+	sandbox: Option<Sandbox>
+}
+
+impl Default for Interpreter {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Puts the previous stack back once an interpreter is done running.
+struct RestoreStack(Option<Vec<Binding>>);
+
+impl Drop for RestoreStack {
+	fn drop(&mut self) {
+		if let Some(stack) = self.0.take() {
+			Binding::set_stack(stack);
+		}
+	}
+}
+
+impl Interpreter {
+	/// Creates a new interpreter, initializing Quest if it hasn't been already.
+	pub fn new() -> Self {
+		quest_core::init();
+		quest_parser::init();
+
+		let kernel = Object::new_with_parent(Class::new("Kernel"), vec![Kernel::mapping().clone()]);
+		kernel.set_attr_lit("Kernel", kernel.clone())
+			.expect("couldn't set the interpreter's kernel");
+
+		let globals = {
+			let _kernel = Kernel::enter(kernel.clone());
+			Object::new(Scope)
+		};
+
+		globals.set_attr_lit("name", Object::from("main"))
+			.expect("couldn't set the name of the global scope");
+
+		Self { globals, kernel, sandbox: None }
+	}
+
+	/// Creates a new interpreter that always runs code within `sandbox`.
+	pub fn sandboxed(sandbox: Sandbox) -> Self {
+		Self { sandbox: Some(sandbox), ..Self::new() }
+	}
+
+	/// Gets the sandbox code is run within, if any.
+	#[inline]
+	pub fn sandbox(&self) -> Option<&Sandbox> {
+		self.sandbox.as_ref()
+	}
+
+	/// Sets the sandbox code is run within; `None` runs code without one.
+	#[inline]
+	pub fn set_sandbox(&mut self, sandbox: Option<Sandbox>) {
+		self.sandbox = sandbox;
+	}
+
+	/// Gets the object that holds the interpreter's global variables.
+	#[inline]
+	pub fn globals(&self) -> &Object {
+		&self.globals
+	}
+
+	/// Runs `func` with the global scope as the only stackframe.
+	///
+	/// Quest keeps track of stackframes per thread, so the thread's current stack is swapped out
+	/// while `func` runs. This keeps interpreters from seeing each other's variables, even if one
+	/// is run from within a function that another one called.
+	///
+	/// If the code `return`s out of the global scope, the returned value is converted instead.
+	pub fn run<T: FromObject>(&self, func: impl FnOnce() -> quest_core::Result<T>) -> quest_core::Result<T> {
+		let mut previous = Binding::set_stack(Vec::new());
+		// `set_stack` expects the most recent stackframe first, but gives it back last.
+		previous.reverse();
+		let _restore = RestoreStack(Some(previous));
+		let _kernel = Kernel::enter(self.kernel.clone());
+		let _sandbox = self.sandbox.clone().map(Sandbox::enter);
+
+		let mut result = None;
+
+		let returned = Binding::run_stackframe(self.globals.clone().into(), |_| {
+			result = Some(func()?);
+			Ok(Object::default())
+		})?;
+
+		// `result` is only ever missing if the code `return`ed out of the global scope.
+		match result {
+			Some(result) => Ok(result),
+			None => T::from_object(&returned)
+		}
+	}
+
+	fn eval_stream<B: BufRead>(&self, stream: BufStream<B>) -> Result<Object> {
+		let expression = Expression::parse_stream(stream.tokens())?;
+
+		self.run(|| quest_parser::run(&expression)).map_err(From::from)
+	}
+
+	/// Runs `src`, converting what it evaluates to into a `T`.
+	pub fn eval<T: FromObject>(&self, src: &str) -> Result<T> {
+		let result = self.eval_stream(BufStream::from(src.to_string()))?;
+
+		self.run(|| T::from_object(&result)).map_err(From::from)
+	}
+
+	/// Runs the file at `path`, converting what it evaluates to into a `T`.
+	///
+	/// The global `__file__` is set to `path` while the file runs, so that imports are relative
+	/// to it. Its previous value is restored afterwards.
+	pub fn eval_file<T: FromObject>(&self, path: impl AsRef<Path>) -> Result<T> {
+		let path = path.as_ref();
+		let stream = BufStream::try_from(path)?;
+
+		let previous = self.globals.get_own_attr_lit("__file__");
+		self.globals.set_attr_lit("__file__", Object::from(path.display().to_string()))?;
+		let result = self.eval_stream(stream);
+
+		match previous {
+			Some(previous) => self.globals.set_attr_lit("__file__", previous)?,
+			None => { self.globals.del_attr_lit("__file__")?; }
+		}
+
+		let result = result?;
+		self.run(|| T::from_object(&result)).map_err(From::from)
+	}
+
+	/// Adds a directory to search for imports in.
+	///
+	/// Each interpreter has its own search path and imported modules.
+	pub fn add_search_path(&self, dir: impl Into<PathBuf>) -> Result<()> {
+		Imports::of(&self.kernel)?.add_search_path(dir);
+		Ok(())
+	}
+
+	/// Assigns the global variable `name`.
+	pub fn set_global(&self, name: &str, value: impl IntoObject) -> Result<()> {
+		self.globals.set_attr(Object::from(name), value.into_object()).map_err(From::from)
+	}
+
+	/// Gets the global variable `name`, converting it into a `T`.
+	///
+	/// Like in Quest code, attributes of `Kernel` (such as `print`) are also globals.
+	pub fn get_global<T: FromObject>(&self, name: &str) -> Result<T> {
+		self.run(|| T::from_object(&self.globals.get_attr(&Object::from(name))?))
+			.map_err(From::from)
+	}
+
+	/// Defines the global function `name`, which calls `func` with the arguments it's given.
+	///
+	/// Anything `func` returns is converted to an object, and errors are raised within Quest.
+	pub fn register_fn<F, R>(&self, name: &str, func: F) -> Result<()>
+	where
+		F: Fn(Args) -> quest_core::Result<R> + Send + Sync + 'static,
+		R: IntoObject
+	{
+		let closure = RustClosure::new(move |args| func(args).map(IntoObject::into_object));
+
+		self.set_global(name, Object::from(closure))
+	}
+
+	/// Defines a global for the class of `T`, named after the class.
+	///
+	/// This is usually used with types that were exposed via [`object`](crate::object).
+	pub fn register_type<T: ObjectType>(&self) -> Result<()> {
+		T::initialize()?;
+
+		let class = T::mapping();
+		let name = class.try_downcast::<Class>().map(|class| Text::from(*class).to_string())?;
+
+		self.set_global(&name, class)
+	}
+
+	/// Calls the global function `name` with `args`, converting what it returns into a `T`.
+	pub fn call<T: FromObject>(&self, name: &str, args: Vec<Object>) -> Result<T> {
+		self.run(|| {
+			let func = self.globals.get_attr(&Object::from(name))?;
+			let result = func.call_attr_lit(&Literal::CALL, args.iter().collect::<Args>())?;
+
+			T::from_object(&result)
+		}).map_err(From::from)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Error;
+
+	#[test]
+	fn eval_and_globals() {
+		let interpreter = Interpreter::new();
+
+		assert_eq!(interpreter.eval::<i64>("x = 3; x * 4").unwrap(), 12);
+		assert_eq!(interpreter.eval::<i64>("x + 1").unwrap(), 4);
+		assert_eq!(interpreter.get_global::<i64>("x").unwrap(), 3);
+
+		interpreter.set_global("names", vec!["a", "b"]).unwrap();
+		assert_eq!(interpreter.eval::<Vec<String>>("names.map({ _0 + '!' }).@list()").unwrap(), ["a!", "b!"]);
+		assert_eq!(interpreter.eval::<Option<i64>>("null").unwrap(), None);
+	}
+
+	#[test]
+	fn errors() {
+		let interpreter = Interpreter::new();
+
+		assert!(matches!(interpreter.eval::<Object>("(1"), Err(Error::Parser(_))));
+		assert!(matches!(interpreter.eval::<Object>("undefined_variable"), Err(Error::Quest(_))));
+		assert!(matches!(interpreter.eval::<i64>("'a'"), Err(Error::Quest(_))));
+		assert!(matches!(interpreter.eval_file::<Object>("/does/not/exist.qs"), Err(Error::Io(_))));
+
+		// errors don't leave the interpreter in a bad state.
+		assert_eq!(interpreter.eval::<i64>("1 + 2").unwrap(), 3);
+	}
+
+	#[test]
+	fn returning_from_the_global_scope() {
+		let interpreter = Interpreter::new();
+
+		assert_eq!(interpreter.eval::<i64>("return(5); 6").unwrap(), 5);
+		assert!(matches!(interpreter.eval::<i64>("return('a')"), Err(Error::Quest(_))));
+	}
+
+	#[test]
+	fn functions() {
+		let interpreter = Interpreter::new();
+
+		interpreter.register_fn("add", |args| {
+			Ok(i64::from_object(args.try_arg(0)?)? + i64::from_object(args.try_arg(1)?)?)
+		}).unwrap();
+
+		assert_eq!(interpreter.eval::<i64>("add(2, 3)").unwrap(), 5);
+		assert!(interpreter.eval::<i64>("add(2)").is_err());
+
+		interpreter.eval::<()>("double = { _0 * 2 }").unwrap();
+		assert_eq!(interpreter.call::<i64>("double", vec![21.into()]).unwrap(), 42);
+	}
+
+	#[derive(Debug, Clone, PartialEq)]
+	struct Point {
+		x: i64,
+		y: i64
+	}
+
+	#[crate::object(convert = "@point")]
+	impl Point {
+		fn new(x: i64, y: i64) -> Self {
+			Point { x, y }
+		}
+
+		#[quest(name = "+")]
+		fn add(&self, rhs: Point) -> Self {
+			Point { x: self.x + rhs.x, y: self.y + rhs.y }
+		}
+
+		fn scale(&mut self, by: i64, y_by: Option<i64>) {
+			self.x *= by;
+			self.y *= y_by.unwrap_or(by);
+		}
+
+		fn x(&self) -> i64 {
+			self.x
+		}
+
+		fn checked_div(&self, by: i64) -> quest_core::Result<Self> {
+			if by == 0 {
+				Err(quest_core::Error::Messaged("division by zero".into()))
+			} else {
+				Ok(Point { x: self.x / by, y: self.y / by })
+			}
+		}
+
+		#[quest(skip)]
+		#[allow(dead_code)]
+		fn hidden(&self) {}
+	}
+
+	#[test]
+	fn object_types() {
+		use quest_core::error::ArgumentError;
+
+		let interpreter = Interpreter::new();
+		interpreter.register_type::<Point>().unwrap();
+
+		assert_eq!(interpreter.eval::<Point>("Point.new(1, 2) + Point.new(3, 4)").unwrap(), Point::new(4, 6));
+		assert_eq!(interpreter.eval::<Point>("p = Point.new(1, 2); p.scale(3); p").unwrap(), Point::new(3, 6));
+		assert_eq!(interpreter.eval::<Point>("p.scale(2, 0); p").unwrap(), Point::new(6, 0));
+		assert_eq!(interpreter.eval::<i64>("p.x()").unwrap(), 6);
+		assert_eq!(interpreter.eval::<Point>("p.checked_div(3)").unwrap(), Point::new(2, 0));
+		assert!(interpreter.eval::<Point>("p.checked_div(0)").is_err());
+		assert!(interpreter.eval::<Object>("p.hidden()").is_err());
+
+		// arguments are converted via `@point`.
+		interpreter.eval::<()>("Text.'@point' = { Point.new(_0.@num(), 0) }").unwrap();
+		assert_eq!(interpreter.eval::<Point>("p + '4'").unwrap(), Point::new(10, 0));
+
+		for (src, given, expected) in &[("Point.new(1)", 1, 2), ("p.scale()", 0, 1), ("p.scale(1, 2, 3)", 3, 2)] {
+			match interpreter.eval::<Object>(src) {
+				Err(Error::Quest(err)) => assert!(
+					matches!(err.untraced(), quest_core::Error::ArgumentError(ArgumentError::InvalidLength { given: g, expected: e })
+						if (g, e) == (given, expected)),
+					"{}: unexpected error {:?}", src, err
+				),
+				other => panic!("{}: unexpected result {:?}", src, other)
+			}
+		}
+	}
+
+	#[test]
+	fn sandboxes() {
+		use quest_core::sandbox::Capability;
+		use quest_core::error::SandboxError;
+		use std::time::Duration;
+
+		fn sandbox_error(result: Result<Object>) -> SandboxError {
+			match result {
+				Err(Error::Quest(err)) => match err.untraced() {
+					quest_core::Error::SandboxError(err) => err.clone(),
+					other => panic!("unexpected error {:?}", other)
+				},
+				other => panic!("unexpected result {:?}", other)
+			}
+		}
+
+		let interpreter = Interpreter::sandboxed(Sandbox::new().max_steps(1000).max_depth(50));
+
+		for src in &["system('ls')", "quit()", "Io::File('/etc/passwd')", "TcpStream::connect('localhost', 80)", "'quit()'.eval()"] {
+			assert!(matches!(sandbox_error(interpreter.eval(src)), SandboxError::Denied { .. }), "{}", src);
+		}
+
+		assert!(matches!(sandbox_error(interpreter.eval("while({ true }, {})")), SandboxError::StepLimit(1000)));
+		assert!(matches!(sandbox_error(interpreter.eval("f = { f() }; f()")), SandboxError::DepthLimit(50)));
+
+		// the errors can be caught, but the limits still apply.
+		assert_eq!(interpreter.eval::<String>("try({ system('ls') }, { _0.message() })").unwrap(),
+			"sandbox error: `Kernel::system` isn't allowed (it needs the process capability)");
+		assert!(interpreter.eval::<Object>("try({ while({ true }, {}) }, { 1 + 2 })").is_err());
+
+		// limits start over for each run.
+		assert_eq!(interpreter.eval::<i64>("1 + 2").unwrap(), 3);
+
+		let interpreter = Interpreter::sandboxed(
+			Sandbox::new().allow(Capability::EXIT).timeout(Duration::from_millis(50)));
+		assert!(matches!(sandbox_error(interpreter.eval("while({ true }, {})")), SandboxError::Timeout(_)));
+		assert!(matches!(sandbox_error(interpreter.eval("sleep(10)")), SandboxError::Timeout(_)));
+		assert_eq!(Sandbox::current(), None);
+	}
+
+	#[test]
+	fn interpreters_are_separate() {
+		let first = Interpreter::new();
+		let second = Interpreter::new();
+
+		first.set_global("shared", 1).unwrap();
+		assert!(second.get_global::<Object>("shared").is_err());
+
+		// even when one is run from within the other.
+		let inner = second.clone();
+		first.register_fn("run_second", move |_| {
+			inner.eval::<Object>("shared").map_err(|err| quest_core::Error::Messaged(err.to_string()))
+		}).unwrap();
+
+		assert!(first.eval::<Object>("run_second()").is_err());
+		second.set_global("shared", 2).unwrap();
+		assert_eq!(first.eval::<i64>("run_second() + shared").unwrap(), 3);
+	}
+
+	#[test]
+	fn kernels_are_separate() {
+		let first = Interpreter::new();
+		let second = Interpreter::new();
+
+		first.eval::<()>("Kernel.leak = 1; Kernel.rand = { 'hijacked' }").unwrap();
+		assert_eq!(first.eval::<i64>("leak + { leak }()").unwrap(), 2);
+		assert_eq!(first.eval::<String>("{ rand() }()").unwrap(), "hijacked");
+		assert!(second.eval::<f64>("rand()").is_ok());
+
+		assert!(second.eval::<Object>("leak").is_err());
+		assert!(second.eval::<Object>("Kernel.leak").is_err());
+		assert!(second.eval::<Object>("{ Kernel.leak }()").is_err());
+		assert!(Kernel::mapping().get_attr_lit("leak").is_err());
+	}
+
+	#[test]
+	fn imports_are_separate() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::write(dir.path().join("lib.qs"), "value = 1;").unwrap();
+
+		let first = Interpreter::new();
+		let second = Interpreter::new();
+
+		first.add_search_path(dir.path()).unwrap();
+		assert_eq!(first.eval::<i64>("import('lib').value = 2; import('lib').value").unwrap(), 2);
+		assert!(second.eval::<Object>("import('lib')").is_err());
+
+		second.add_search_path(dir.path()).unwrap();
+		assert_eq!(second.eval::<i64>("import('lib').value").unwrap(), 1);
+	}
+
+	#[test]
+	fn eval_file_restores_file() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("file.qs");
+		std::fs::write(&path, "__file__").unwrap();
+
+		let interpreter = Interpreter::new();
+		assert_eq!(interpreter.eval_file::<String>(&path).unwrap(), path.display().to_string());
+		assert!(interpreter.get_global::<Object>("__file__").is_err());
+
+		interpreter.set_global("__file__", "outer").unwrap();
+		interpreter.eval_file::<Object>(&path).unwrap();
+		assert_eq!(interpreter.get_global::<String>("__file__").unwrap(), "outer");
+	}
+}
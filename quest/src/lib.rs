@@ -0,0 +1,72 @@
+//! Embedding the Quest programming language in Rust programs.
+//!
+//! Everything goes through an [`Interpreter`], which takes care of initializing Quest and running
+//! code within its own global scope:
+//!
+//! ```
+//! use quest::{Interpreter, FromObject};
+//!
+//! let interpreter = Interpreter::new();
+//!
+//! interpreter.set_global("greeting", "Hello").unwrap();
+//! interpreter.register_fn("shout", |args| {
+//! 	Ok(String::from_object(args.try_arg(0)?)?.to_uppercase())
+//! }).unwrap();
+//!
+//! let result: String = interpreter.eval("shout(greeting + ', world')").unwrap();
+//! assert_eq!(result, "HELLO, WORLD");
+//! ```
+//!
+//! Rust types can be exposed to Quest with the [`object`] attribute, and then registered with
+//! [`Interpreter::register_type`]:
+//!
+//! ```
+//! use quest::{Interpreter, object};
+//!
+//! #[derive(Debug, Clone)]
+//! struct Counter(i64);
+//!
+//! #[object]
+//! impl Counter {
+//! 	fn new(start: Option<i64>) -> Self {
+//! 		Counter(start.unwrap_or(0))
+//! 	}
+//!
+//! 	#[quest(name = "+=")]
+//! 	fn increment(&mut self, amount: i64) {
+//! 		self.0 += amount;
+//! 	}
+//!
+//! 	fn count(&self) -> i64 {
+//! 		self.0
+//! 	}
+//! }
+//!
+//! let interpreter = Interpreter::new();
+//! interpreter.register_type::<Counter>().unwrap();
+//!
+//! let result: i64 = interpreter.eval("c = Counter.new(3); c += 4; c.count()").unwrap();
+//! assert_eq!(result, 7);
+//! ```
+//!
+//! # See Also
+//! - [`quest-core`](quest_core) for the objects and types themselves.
+//! - [`quest-parser`](quest_parser) for parsing Quest code.
+#![allow(clippy::tabs_in_doc_comments)]
+
+// Lets the code generated by `object` refer to `::quest` from within this crate too.
+extern crate self as quest;
+
+mod error;
+mod convert;
+mod interpreter;
+
+pub use error::{Error, Result};
+pub use convert::{FromObject, IntoObject};
+pub use interpreter::Interpreter;
+pub use quest_core::{Object, Args};
+pub use quest_macros::object;
+pub use quest_core::sandbox::{Sandbox, Capability};
+
+#[doc(hidden)]
+pub use quest_core as __core;
@@ -0,0 +1,114 @@
+//! Benchmarks running the examples with both the syntax tree walker and the virtual machine.
+//!
+//! Run them with `cargo bench -p qvm`. Pass a name after `--` to only run the benchmarks whose
+//! names contain it, e.g. `cargo bench -p qvm -- fibonacci`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use quest_core::{Object, Binding};
+use quest_core::types::{Kernel, Basic, ObjectType, RustClosure};
+use quest_parser::{Expression, Stream};
+use quest_parser::expression::Executable;
+use quest_parser::stream::BufStream;
+use std::convert::TryFrom;
+use std::path::Path;
+use std::sync::Once;
+use std::time::Duration;
+
+/// The examples to benchmark. Examples that read input, use the network, or redefine core
+/// operators (which would affect every benchmark after them) are left out.
+const EXAMPLES: &[&str] = &[
+	"fibonacci.qs",
+	"fibonacci-unmemoized.qs",
+	"classes.qs",
+	"inheritance.qs",
+	"mixins.qs",
+	"person.qs",
+	"frac.qs",
+	"rational.qs",
+];
+
+/// Attributes of a number to look up, and where they're defined.
+const LOOKUPS: &[(&str, &str)] = &[
+	("number", "+"),
+	("basic", "then"),
+	("comparable", "<"),
+	("pristine", "__call_attr__"),
+	("missing", "not_an_attribute"),
+];
+
+type Engine = fn(&Expression) -> quest_core::Result<Object>;
+
+const ENGINES: &[(&str, Engine)] = &[
+	("tree", |expr| expr.execute()),
+	("vm", qvm::run),
+];
+
+fn parse(path: &Path) -> Expression {
+	let stream = BufStream::try_from(path)
+		.unwrap_or_else(|err| panic!("couldn't open {}: {}", path.display(), err));
+
+	Expression::parse_stream(stream.tokens())
+		.unwrap_or_else(|err| panic!("couldn't parse {}: {}", path.display(), err))
+}
+
+fn run_example(engine: Engine, expr: &Expression) {
+	Binding::new_stackframe(None, Default::default(), |_| engine(expr))
+		.unwrap_or_else(|err| panic!("example raised an error: {}", err));
+}
+
+fn setup() {
+	static SETUP: Once = Once::new();
+
+	SETUP.call_once(|| {
+		quest_core::init();
+		quest_parser::init();
+		qvm::Block::initialize().expect("couldn't initialize block");
+
+		// examples print their results, which would drown out the timings.
+		let silent = Object::from(RustClosure::new(|_| Ok(Object::default())));
+		for mapping in &[Kernel::mapping(), Basic::mapping()] {
+			mapping.set_attr_lit("print", silent.clone()).unwrap();
+			mapping.set_attr_lit("disp", silent.clone()).unwrap();
+		}
+	});
+}
+
+fn examples(c: &mut Criterion) {
+	setup();
+
+	let examples_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+
+	for example in EXAMPLES {
+		let expr = parse(&examples_dir.join(example));
+		let mut group = c.benchmark_group(example.trim_end_matches(".qs"));
+
+		for (engine_name, engine) in ENGINES {
+			group.bench_function(*engine_name, |b| b.iter(|| run_example(*engine, &expr)));
+		}
+
+		group.finish();
+	}
+}
+
+/// How long it takes a new number to find attributes defined at different depths of its parents.
+fn lookups(c: &mut Criterion) {
+	setup();
+
+	let mut group = c.benchmark_group("lookup");
+
+	for (name, attr) in LOOKUPS {
+		group.bench_function(*name, |b| b.iter(|| Object::from(12).has_attr_lit(*attr).unwrap()));
+	}
+
+	group.finish();
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default()
+		.warm_up_time(Duration::from_millis(500))
+		.measurement_time(Duration::from_secs(2));
+	targets = examples, lookups
+}
+
+criterion_main!(benches);
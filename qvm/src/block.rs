@@ -0,0 +1,77 @@
+use crate::Code;
+use quest_core::{Object, Args, Binding, impl_object_type};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// A `{ ... }` block that's been compiled.
+///
+/// These behave like the blocks the syntax tree creates, and are descended from [`Block`](
+/// quest_parser::Block) so they're still considered to be `Block`s.
+#[derive(Clone)]
+pub struct Block {
+	code: Arc<Code>,
+	source: Arc<quest_parser::Block>
+}
+
+impl Debug for Block {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Debug::fmt(&self.source, f)
+	}
+}
+
+impl Block {
+	/// Creates a new block from its compiled `code` and the `source` it was compiled from.
+	pub fn new(code: Code, source: quest_parser::Block) -> Self {
+		Self { code: Arc::new(code), source: Arc::new(source) }
+	}
+
+	/// The block's compiled body.
+	#[inline]
+	pub fn code(&self) -> &Code {
+		&self.code
+	}
+
+	/// The block that was compiled.
+	#[inline]
+	pub fn source(&self) -> &quest_parser::Block {
+		&self.source
+	}
+}
+
+impl Block {
+	/// Runs the block in a new stackframe.
+	pub fn qs_call(this: &Object, args: Args) -> quest_core::Result<Object> {
+		let block = this.try_downcast::<Self>()?.clone();
+
+		Binding::new_stackframe(Some(this.clone()), args, move |binding| {
+			block.source.enter_stackframe(binding)?;
+
+			crate::vm::run(&block.code)
+		})
+	}
+
+	/// Runs the block in the current stackframe.
+	pub fn qs_call_noscope(this: &Object, _: Args) -> quest_core::Result<Object> {
+		let block = this.try_downcast::<Self>()?.clone();
+		let binding = Binding::instance();
+
+		// `->` functions create their own stackframe for the block before calling this.
+		if binding.parents()?.iter().any(|parent| parent.is_identical(this)) {
+			block.source.enter_stackframe(&binding)?;
+		}
+
+		crate::vm::run(&block.code)
+	}
+
+	/// Gets the source code of the block.
+	pub fn qs_at_text(this: &Object, _: Args) -> quest_core::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.source.to_string().into())
+	}
+}
+
+impl_object_type!{
+for Block [(parents quest_parser::Block)]:
+	"@text" => method Self::qs_at_text,
+	"()" => method Self::qs_call,
+	"call_noscope" => method Self::qs_call_noscope,
+}
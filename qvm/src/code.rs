@@ -0,0 +1,79 @@
+use crate::{Instruction, Block};
+use quest_core::Object;
+use quest_core::types::{Text, Number, Regex};
+use std::fmt::{self, Display, Formatter};
+
+/// A literal value within [`Code`].
+///
+/// Literals are mutable once they're objects, so a new object is made each time one's used.
+#[derive(Debug, Clone)]
+pub enum Constant {
+	/// A literal piece of text.
+	Text(Text),
+	/// A literal number.
+	Number(Number),
+	/// A literal regex.
+	Regex(Regex)
+}
+
+impl Constant {
+	/// Creates a new object for the constant.
+	pub fn to_object(&self) -> Object {
+		match self {
+			Self::Text(text) => text.clone().into(),
+			Self::Number(num) => num.clone().into(),
+			Self::Regex(regex) => regex.clone().into()
+		}
+	}
+}
+
+/// Compiled bytecode, along with the tables its [`Instruction`]s refer to.
+#[derive(Debug, Clone, Default)]
+pub struct Code {
+	pub(crate) instructions: Vec<Instruction>,
+	pub(crate) constants: Vec<Constant>,
+	pub(crate) names: Vec<Object>,
+	pub(crate) blocks: Vec<Block>
+}
+
+impl Code {
+	/// The instructions to run.
+	#[inline]
+	pub fn instructions(&self) -> &[Instruction] {
+		&self.instructions
+	}
+
+	/// The constants that [`Instruction::Constant`] refers to.
+	#[inline]
+	pub fn constants(&self) -> &[Constant] {
+		&self.constants
+	}
+
+	/// The blocks that [`Instruction::Block`] refers to.
+	#[inline]
+	pub fn blocks(&self) -> &[Block] {
+		&self.blocks
+	}
+}
+
+impl Display for Code {
+	/// Writes out a listing of the instructions, followed by that of each nested block.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		for (idx, instruction) in self.instructions.iter().enumerate() {
+			write!(f, "{:>4} {}", idx, instruction)?;
+
+			match instruction {
+				Instruction::Constant(idx) => writeln!(f, "\t({:?})", self.constants[*idx as usize])?,
+				Instruction::Load(idx) => writeln!(f, "\t({:?})", self.names[*idx as usize])?,
+				_ => writeln!(f)?
+			}
+		}
+
+		for (idx, block) in self.blocks.iter().enumerate() {
+			writeln!(f, "\nblock {}:", idx)?;
+			Display::fmt(block.code(), f)?;
+		}
+
+		Ok(())
+	}
+}
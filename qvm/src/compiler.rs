@@ -0,0 +1,237 @@
+use crate::{Code, Constant, Instruction, Block};
+use quest_core::Literal;
+use quest_core::types::Text;
+use quest_parser::{Expression, Block as AstBlock};
+use quest_parser::block::Line;
+use quest_parser::expression::{BoundOperator, OperArgs};
+use quest_parser::token::{Operator, ParenType, Primitive};
+use quest_parser::token::primitive::text::Segment;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Compiles syntax trees into [`Code`].
+///
+/// The generated code evaluates everything in the same order the syntax tree would have been
+/// walked in, so the two are interchangeable.
+#[derive(Debug, Default)]
+pub struct Compiler {
+	code: Code,
+	name_indices: HashMap<String, u32>
+}
+
+/// Converts a table's length into an index for an [`Instruction`].
+fn index(len: usize) -> u32 {
+	u32::try_from(len).expect("too many entries for an instruction")
+}
+
+impl Compiler {
+	/// Compiles an expression, such as the one [`Expression::parse_stream`] returns.
+	pub fn compile(expr: &Expression) -> Code {
+		let mut compiler = Self::default();
+		compiler.expression(expr);
+		compiler.code
+	}
+
+	/// Compiles the body of a `{ ... }` block, which is what's run when the block is called.
+	pub fn compile_block(block: &AstBlock) -> Code {
+		let mut compiler = Self::default();
+		compiler.block_value(block);
+		compiler.code
+	}
+
+	#[inline]
+	fn emit(&mut self, instruction: Instruction) {
+		self.code.instructions.push(instruction);
+	}
+
+	fn constant(&mut self, constant: Constant) {
+		self.code.constants.push(constant);
+		self.emit(Instruction::Constant(index(self.code.constants.len() - 1)));
+	}
+
+	fn load(&mut self, name: Text) {
+		if Literal::__STACK__ == name.as_ref() {
+			self.emit(Instruction::Stack);
+			return;
+		}
+
+		let names = &mut self.code.names;
+		let idx = *self.name_indices.entry(name.to_string()).or_insert_with(|| {
+			names.push(name.into());
+			index(names.len() - 1)
+		});
+
+		self.emit(Instruction::Load(idx));
+	}
+
+	fn expression(&mut self, expr: &Expression) {
+		match expr {
+			Expression::Primitive(prim) => self.primitive(prim),
+			Expression::Block(block) if block.paren_type() == ParenType::Curly => {
+				let block = Block::new(Self::compile_block(block), block.clone());
+				self.code.blocks.push(block);
+				self.emit(Instruction::Block(index(self.code.blocks.len() - 1)));
+			},
+			Expression::Block(block) => self.block_value(block),
+			Expression::Operator(oper) => self.operator(oper),
+			Expression::FunctionCall(this, block) => {
+				self.expression(this);
+
+				let oper = if block.paren_type() == ParenType::Square { Operator::Index } else { Operator::Call };
+				let argc = self.arguments(block);
+				self.emit(Instruction::Call(oper, argc));
+			}
+		}
+	}
+
+	fn primitive(&mut self, prim: &Primitive) {
+		match prim {
+			Primitive::Text(text) => self.constant(Constant::Text(text.clone())),
+			Primitive::Interpolation(interp) => {
+				for segment in interp.segments() {
+					match segment {
+						Segment::Text(text) => self.constant(Constant::Text(text.as_str().into())),
+						Segment::Expression(expr) => self.expression(expr)
+					}
+				}
+
+				self.emit(Instruction::Concat(index(interp.segments().len())));
+			},
+			Primitive::Number(num) => self.constant(Constant::Number(num.clone())),
+			Primitive::Regex(regex) => self.constant(Constant::Regex(regex.clone())),
+			Primitive::Variable(var) => self.load(var.clone().into()),
+			Primitive::StackPos(pos) => self.emit(Instruction::StackPos((*pos).into()))
+		}
+	}
+
+	fn operator(&mut self, oper: &BoundOperator) {
+		self.expression(oper.this());
+
+		let argc =
+			match oper.args() {
+				OperArgs::Binary(Expression::Block(block))
+					if oper.oper() == Operator::Call && block.paren_type() == ParenType::Round
+				=> self.arguments(block),
+				OperArgs::Unary => 0,
+				OperArgs::Binary(rhs) => { self.expression(rhs); 1 },
+				OperArgs::Ternary(mid, rhs) => { self.expression(mid); self.expression(rhs); 2 }
+			};
+
+		self.emit(Instruction::Call(oper.oper(), argc));
+	}
+
+	/// Compiles every line but the last, discarding their results, and returns the last one.
+	fn leading_lines<'a>(&mut self, lines: &'a [Line]) -> Option<&'a Line> {
+		let (last, rest) = lines.split_last()?;
+
+		for line in rest {
+			match line {
+				Line::Single(expr) => {
+					self.expression(expr);
+					self.emit(Instruction::Pop);
+				},
+				Line::Multiple(exprs) => for expr in exprs {
+					self.expression(expr);
+					self.emit(Instruction::Pop);
+				}
+			}
+		}
+
+		Some(last)
+	}
+
+	/// Compiles a block that's being used as arguments, returning the amount of arguments.
+	///
+	/// Only the last line is passed as arguments, with each of its expressions being an argument.
+	fn arguments(&mut self, block: &AstBlock) -> u32 {
+		match self.leading_lines(block.lines()) {
+			Some(Line::Single(expr)) => {
+				self.expression(expr);
+				1
+			},
+			Some(Line::Multiple(exprs)) => {
+				exprs.iter().for_each(|expr| self.expression(expr));
+				index(exprs.len())
+			},
+			None => 0
+		}
+	}
+
+	/// Compiles a block that's being run for its value.
+	///
+	/// The value is that of the last line, with multiple expressions becoming a list. A `[...]`
+	/// block always becomes a list.
+	fn block_value(&mut self, block: &AstBlock) {
+		let is_list = block.paren_type() == ParenType::Square;
+
+		match self.leading_lines(block.lines()) {
+			Some(Line::Single(expr)) => {
+				self.expression(expr);
+
+				if is_list {
+					self.emit(Instruction::List(1));
+				}
+			},
+			Some(Line::Multiple(exprs)) => {
+				exprs.iter().for_each(|expr| self.expression(expr));
+				self.emit(Instruction::List(index(exprs.len())));
+			},
+			None if is_list => self.emit(Instruction::List(0)),
+			None => self.emit(Instruction::Null)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use quest_parser::Stream;
+	use quest_parser::stream::BufStream;
+	use Instruction::*;
+
+	fn compile(src: &str) -> Code {
+		quest_core::init();
+		quest_parser::init();
+
+		let expr = Expression::parse_stream(BufStream::from(src.to_string()).tokens()).unwrap();
+		Compiler::compile(&expr)
+	}
+
+	#[test]
+	fn operators() {
+		let code = compile("x = 1 + y");
+
+		assert_eq!(code.instructions(), &[
+			Constant(0), Constant(1), Load(0), Call(Operator::Add, 1), Call(Operator::Assign, 1)
+		]);
+		assert!(matches!(code.constants(), [crate::Constant::Text(_), crate::Constant::Number(_)]));
+	}
+
+	#[test]
+	fn lines_and_lists() {
+		assert_eq!(compile("a; b").instructions(), &[Load(0), Pop, Load(1)]);
+		assert_eq!(compile("a, b").instructions(), &[Load(0), Load(1), List(2)]);
+		assert_eq!(compile("[a]").instructions(), &[Load(0), List(1)]);
+		assert_eq!(compile("").instructions(), &[Null]);
+
+		// variables are only stored once.
+		assert_eq!(compile("a; a").instructions(), &[Load(0), Pop, Load(0)]);
+	}
+
+	#[test]
+	fn calls() {
+		assert_eq!(compile("f(a; b, c)").instructions(),
+			&[Load(0), Load(1), Pop, Load(2), Load(3), Call(Operator::Call, 2)]);
+		assert_eq!(compile("f()").instructions(), &[Load(0), Call(Operator::Call, 0)]);
+		assert_eq!(compile("l[1]").instructions(), &[Load(0), Constant(0), Call(Operator::Index, 1)]);
+	}
+
+	#[test]
+	fn blocks() {
+		let code = compile("f = { _0 }; __stack__; :1");
+
+		assert_eq!(code.instructions(),
+			&[Constant(0), Block(0), Call(Operator::Assign, 1), Pop, Stack, Pop, StackPos(1)]);
+		assert_eq!(code.blocks()[0].code().instructions(), &[Load(0)]);
+	}
+}
@@ -0,0 +1,50 @@
+use quest_parser::token::Operator;
+use std::fmt::{self, Display, Formatter};
+
+/// A single instruction for the virtual machine.
+///
+/// Instructions operate on a stack of values that's local to each piece of [`Code`](crate::Code).
+/// Indices refer to the tables within the code that's being run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+	/// Pushes a new copy of the constant at the given index.
+	Constant(u32),
+	/// Looks up the variable whose name is at the given index, and pushes it.
+	Load(u32),
+	/// Pushes a list of every stackframe, for the `__stack__` variable.
+	Stack,
+	/// Pushes the stackframe at the given position, as in `:1`.
+	StackPos(isize),
+	/// Creates a block from the code at the given index, and pushes it.
+	///
+	/// The block's parent is the stackframe it was created in.
+	Block(u32),
+	/// Pops the given amount of arguments followed by a value, and then pushes the result of
+	/// calling the value's attribute for the operator.
+	Call(Operator, u32),
+	/// Pops the given amount of values, and then pushes a list of them.
+	List(u32),
+	/// Pops the given amount of values, and then pushes the concatenation of their `@text`s.
+	Concat(u32),
+	/// Pushes `null`.
+	Null,
+	/// Discards the top of the stack.
+	Pop
+}
+
+impl Display for Instruction {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Constant(idx) => write!(f, "CONSTANT {}", idx),
+			Self::Load(idx) => write!(f, "LOAD {}", idx),
+			Self::Stack => write!(f, "STACK"),
+			Self::StackPos(pos) => write!(f, "STACKPOS {}", pos),
+			Self::Block(idx) => write!(f, "BLOCK {}", idx),
+			Self::Call(oper, argc) => write!(f, "CALL {:?} {}", oper.repr(), argc),
+			Self::List(len) => write!(f, "LIST {}", len),
+			Self::Concat(len) => write!(f, "CONCAT {}", len),
+			Self::Null => write!(f, "NULL"),
+			Self::Pop => write!(f, "POP")
+		}
+	}
+}
@@ -0,0 +1,40 @@
+//! A bytecode compiler and virtual machine for Quest.
+//!
+//! Rather than walking the syntax tree each time code is run, it's [compiled](Compiler) once into
+//! [`Code`], a flat list of [`Instruction`]s that operate on a value stack. The virtual machine
+//! follows the same semantics as the syntax tree: stackframes, `:N` stack positions, `return`s,
+//! and `__args__` all come from `quest_core` and behave identically.
+
+mod instruction;
+mod code;
+mod compiler;
+mod block;
+pub mod vm;
+
+pub use instruction::Instruction;
+pub use code::{Code, Constant};
+pub use compiler::Compiler;
+pub use block::Block;
+
+use quest_core::Object;
+use quest_core::types::ObjectType;
+use quest_parser::Expression;
+
+/// Sets up the virtual machine, and makes it what runs code that's parsed at runtime.
+///
+/// This should be run after [`quest_core::init`] and [`quest_parser::init`].
+pub fn init() {
+	use std::sync::Once;
+
+	static INITIALIZE: Once = Once::new();
+
+	INITIALIZE.call_once(|| {
+		Block::initialize().expect("couldn't initialize block");
+		quest_parser::set_engine(run);
+	});
+}
+
+/// Compiles `expr` and runs it within the current stackframe.
+pub fn run(expr: &Expression) -> quest_core::Result<Object> {
+	vm::run(&Compiler::compile(expr))
+}
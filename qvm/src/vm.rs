@@ -0,0 +1,110 @@
+use crate::{Code, Instruction};
+use quest_core::{Object, Args, Binding};
+use quest_core::types::Text;
+use quest_core::error::KeyError;
+
+/// Gets the stackframe at `pos`, as in `:1`.
+fn stack_pos(pos: isize) -> quest_core::Result<Object> {
+	let stack = Binding::stack();
+	let len = stack.len();
+
+	match quest_core::utils::correct_index(pos, len) {
+		Ok(idx) => Ok(stack[idx].clone().into()),
+		Err(_) => Err(KeyError::OutOfBounds { idx: pos, len }.into())
+	}
+}
+
+/// Runs `code` within the current stackframe, returning the value it ends with.
+pub fn run(code: &Code) -> quest_core::Result<Object> {
+	let mut stack = Vec::<Object>::with_capacity(8);
+
+	for instruction in code.instructions() {
+		match *instruction {
+			Instruction::Constant(idx) => stack.push(code.constants[idx as usize].to_object()),
+			Instruction::Load(idx) =>
+				stack.push(Binding::instance().as_ref().get_attr(&code.names[idx as usize])?),
+			Instruction::Stack =>
+				stack.push(Binding::stack().into_iter().map(Object::from).collect::<Vec<_>>().into()),
+			Instruction::StackPos(pos) => stack.push(stack_pos(pos)?),
+			Instruction::Block(idx) => {
+				let block = Object::from(code.blocks[idx as usize].clone());
+				block.add_parent(Binding::instance().as_ref().clone())?;
+				stack.push(block);
+			},
+			Instruction::Call(oper, argc) => {
+				let args = stack.split_off(stack.len() - argc as usize);
+				let this = stack.pop().expect("nothing to call");
+
+				stack.push(this.call_attr_lit(oper.repr(), args.iter().collect::<Args>())?);
+			},
+			Instruction::List(len) => {
+				let list = stack.split_off(stack.len() - len as usize);
+				stack.push(list.into());
+			},
+			Instruction::Concat(len) => {
+				let mut text = String::new();
+
+				for value in stack.split_off(stack.len() - len as usize) {
+					text.push_str(value.call_downcast::<Text>()?.as_ref());
+				}
+
+				stack.push(text.into());
+			},
+			Instruction::Null => stack.push(Object::default()),
+			Instruction::Pop => { stack.pop(); }
+		}
+	}
+
+	Ok(stack.pop().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+	use quest_core::{Object, Binding};
+	use quest_core::types::{Boolean, ObjectType};
+	use quest_parser::{Stream, Expression};
+	use quest_parser::expression::Executable;
+	use quest_parser::stream::BufStream;
+
+	/// Runs `src` with both the syntax tree and the virtual machine, asserting they agree.
+	fn assert_same(src: &str) {
+		quest_core::init();
+		quest_parser::init();
+		crate::Block::initialize().unwrap();
+
+		let expr = Expression::parse_stream(BufStream::from(src.to_string()).tokens()).unwrap();
+		let in_frame = |run: &dyn Fn() -> quest_core::Result<Object>|
+			Binding::new_stackframe(None, Default::default(), |_| run()).unwrap();
+
+		let walked = in_frame(&|| expr.execute());
+		let compiled = in_frame(&|| crate::run(&expr));
+		let eql = walked.call_attr_lit("==", &[&compiled]).unwrap();
+
+		assert!(eql.downcast::<Boolean>().unwrap().into_inner(), "{:?}: {:?} != {:?}", src, walked, compiled);
+	}
+
+	#[test]
+	fn arithmetic_and_variables() {
+		assert_same("x = 3; y = x * 4; x + y - -1");
+		assert_same("1, \"2\", 3");
+		assert_same("[]; [1]; [1, 2]");
+		assert_same("()");
+	}
+
+	#[test]
+	fn blocks_and_args() {
+		assert_same("add = { _0 + _1 }; add(3, 4)");
+		assert_same("f = { __args__ }; f(1, 2; 3, 4)");
+		assert_same("fib = { if(_0 <= 1, { _0 }, { fib(_0 - 1) + fib(_0 - 2) }) }; fib(12)");
+		assert_same("x = 1; { :1.x = 2 }(); x");
+		assert_same("f = { return(9, :1); 1 }; f()");
+		assert_same("l = [1, 2, 3]; l[1] = 9; l.map({ _0 * 2 }).@list()");
+		assert_same("c = (a, b) -> { a - b }; c(5, 3)");
+	}
+
+	#[test]
+	fn interpolation() {
+		assert_same("x = 2; \"a#{x + 1}b#{\"#{x}\"}\"");
+		assert_same("f = { \"<#{_0}>\" }; f(1) + f([])");
+	}
+}